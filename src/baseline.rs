@@ -0,0 +1,123 @@
+//! Snapshots of a description that later versions are checked against, for
+//! teams gating drift without storing the base description in git.
+//!
+//! A baseline is the parsed description serialized back to JSON, which
+//! normalizes formatting and key order so later checks are stable.
+
+use crate::diff::{diff_with_options, parse_file, Diff, DiffError, DiffOptions};
+use crate::openapi::OpenAPI;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+/// The version of the baseline format written by `Baseline::capture`.
+pub const BASELINE_VERSION: u32 = 1;
+
+#[derive(Error, Debug)]
+pub enum BaselineError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid baseline: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Diff(#[from] DiffError),
+
+    #[error("baseline format version {found} is not supported, expected {expected}")]
+    VersionMismatch { found: u32, expected: u32 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    /// The version of the baseline format, see `BASELINE_VERSION`.
+    pub baseline_version: u32,
+    pub spec: OpenAPI,
+}
+
+/// The part of a baseline read before the rest, so baselines of other
+/// versions are rejected rather than failing to parse.
+#[derive(Deserialize)]
+struct Header {
+    baseline_version: u32,
+}
+
+impl Baseline {
+    /// Captures a baseline of a description.
+    pub fn capture(spec: OpenAPI) -> Self {
+        Self {
+            baseline_version: BASELINE_VERSION,
+            spec,
+        }
+    }
+
+    /// Captures a baseline of a description file, in YAML or JSON.
+    pub fn capture_file(path: &Path) -> Result<Self, BaselineError> {
+        Ok(Self::capture(parse_file(path)?))
+    }
+
+    pub fn to_json(&self) -> Result<String, BaselineError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(contents: &str) -> Result<Self, BaselineError> {
+        let header: Header = serde_json::from_str(contents)?;
+        if header.baseline_version != BASELINE_VERSION {
+            return Err(BaselineError::VersionMismatch {
+                found: header.baseline_version,
+                expected: BASELINE_VERSION,
+            });
+        }
+
+        Ok(serde_json::from_str(contents)?)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, BaselineError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Diffs a description against this baseline.
+    pub fn check(&self, spec: OpenAPI, options: &DiffOptions) -> Result<Diff, BaselineError> {
+        Ok(diff_with_options(self.spec.clone(), spec, options)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_and_check() {
+        let path = Path::new("fixtures/pet-store.yaml");
+        let baseline = Baseline::capture_file(path).expect("Failed to capture baseline");
+
+        let baseline =
+            Baseline::from_json(&baseline.to_json().unwrap()).expect("Failed to read baseline");
+        let diff = baseline
+            .check(parse_file(path).unwrap(), &DiffOptions::default())
+            .expect("Failed to check baseline");
+        assert!(!diff.has_changes());
+
+        let diff = baseline
+            .check(
+                parse_file(Path::new("fixtures/pet-store-changed.yaml")).unwrap(),
+                &DiffOptions::default(),
+            )
+            .expect("Failed to check baseline");
+        assert!(diff.has_changes());
+    }
+
+    #[test]
+    fn version_mismatch() {
+        let error = Baseline::from_json(r#"{ "baseline_version": 2, "spec": {} }"#)
+            .expect_err("Read a baseline of another version");
+
+        assert!(matches!(
+            error,
+            BaselineError::VersionMismatch {
+                found: 2,
+                expected: BASELINE_VERSION
+            }
+        ));
+    }
+}
@@ -3,5 +3,10 @@ use structopt::StructOpt;
 
 fn main() {
     let app = Anicca::from_args();
-    app.run();
+
+    // Exit status 1 is reserved for breaking changes and baseline drift.
+    if let Err(e) = app.run() {
+        eprintln!("Error: {}", e);
+        std::process::exit(2);
+    }
 }
@@ -0,0 +1,79 @@
+use super::formatters::markdown::Printer;
+use super::formatters::Labels;
+use crate::baseline::Baseline;
+use crate::diff::{parse_file, DiffOptions};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub enum BaselineCommand {
+    /// Print a normalized snapshot of an OpenAPI description, to check
+    /// later versions against
+    Capture {
+        /// The OpenAPI description to capture
+        #[structopt(parse(from_os_str))]
+        spec: PathBuf,
+    },
+    /// Diff an OpenAPI description against a captured baseline. Exits with
+    /// a non-zero status when the description drifted from the baseline
+    Check {
+        /// The OpenAPI description to check
+        #[structopt(parse(from_os_str))]
+        spec: PathBuf,
+
+        /// The baseline printed by `baseline capture`
+        #[structopt(parse(from_os_str))]
+        baseline: PathBuf,
+    },
+}
+
+impl BaselineCommand {
+    /// Runs the command, returning the error message when the description
+    /// or the baseline can't be read.
+    pub fn run(&self) -> Result<(), String> {
+        match self {
+            BaselineCommand::Capture { spec } => {
+                let baseline = Baseline::capture_file(spec)
+                    .and_then(|baseline| baseline.to_json())
+                    .map_err(|e| e.to_string())?;
+                println!("{}", baseline);
+            }
+            BaselineCommand::Check { spec, baseline } => {
+                let diff = Baseline::from_file(baseline)
+                    .and_then(|baseline| baseline.check(parse_file(spec)?, &DiffOptions::default()))
+                    .map_err(|e| e.to_string())?;
+
+                let labels = Labels {
+                    base: String::from("baseline"),
+                    ..Labels::default()
+                };
+                println!("{}", Printer::print(&diff, &labels));
+
+                if diff.has_changes() {
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_files() {
+        let capture = BaselineCommand::Capture {
+            spec: PathBuf::from("fixtures/missing.yaml"),
+        };
+        assert!(capture.run().is_err());
+
+        let check = BaselineCommand::Check {
+            spec: PathBuf::from("fixtures/pet-store.yaml"),
+            baseline: PathBuf::from("fixtures/missing.json"),
+        };
+        assert!(check.run().is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use serde::Deserialize;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid YAML config: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("invalid TOML config: {0}")]
+    Toml(#[from] toml::de::Error),
+}
+
+/// Settings loaded from an `annica.toml` or `annica.yaml` file passed with
+/// `--config`, so a team can check its diff policy in.
+///
+/// Every setting is optional. A flag given on the command line takes
+/// precedence over the config file, which takes precedence over the
+/// defaults.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Output format, see `--format`.
+    pub format: Option<String>,
+    /// Line format of the template output, see `--template`.
+    pub template: Option<String>,
+    pub base_label: Option<String>,
+    pub head_label: Option<String>,
+    /// See `DiffOptions::validate_examples`.
+    pub validate_examples: Option<bool>,
+    /// See `DiffOptions::ignore_examples`.
+    pub ignore_examples: Option<bool>,
+    /// See `DiffOptions::ignore_descriptions`.
+    pub ignore_descriptions: Option<bool>,
+    /// See `DiffOptions::strip_extensions`.
+    pub strip_extensions: Option<String>,
+    /// See `DiffOptions::operation_id_pattern`.
+    pub operation_id_pattern: Option<String>,
+    /// See `DiffOptions::deprecated_extension`.
+    pub deprecated_extension: Option<String>,
+    /// See `DiffOptions::report_path_reordering`.
+    pub report_path_reordering: Option<bool>,
+    /// See `DiffOptions::enum_case_insensitive`.
+    pub enum_case_insensitive: Option<bool>,
+    /// See `DiffOptions::resolve_references`.
+    pub resolve_references: Option<bool>,
+    /// See `DiffOptions::detect_path_renames`.
+    pub detect_path_renames: Option<bool>,
+    /// See `DiffOptions::ignore_path_param_names`.
+    pub ignore_path_param_names: Option<bool>,
+    /// See `DiffOptions::infer_request_body_required`.
+    pub infer_request_body_required: Option<bool>,
+    /// See `DiffOptions::exclude_tags`.
+    pub exclude_tags: Option<Vec<String>>,
+    /// See `DiffOptions::short_circuit_identical`.
+    pub short_circuit_identical: Option<bool>,
+    /// Set to false to compare schemas as written. See
+    /// `DiffOptions::normalize`.
+    pub normalize: Option<bool>,
+    /// Minimum severity of the reported changes, see `--min-severity`.
+    pub min_severity: Option<String>,
+    /// See `--fail-on-breaking`.
+    pub fail_on_breaking: Option<bool>,
+}
+
+impl Config {
+    /// Loads a config file, parsed as TOML when its extension is `.toml`
+    /// and as YAML otherwise.
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path)?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml(&contents),
+            _ => Self::from_yaml(&contents),
+        }
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    pub fn from_yaml(contents: &str) -> Result<Self, ConfigError> {
+        Ok(serde_yaml::from_str(contents)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_toml() {
+        let config = Config::from_toml(
+            r#"
+format = "json"
+validate_examples = true
+deprecated_extension = "x-deprecated"
+"#,
+        )
+        .expect("Failed to parse config");
+
+        assert_eq!(Some("json".into()), config.format);
+        assert_eq!(Some(true), config.validate_examples);
+        assert_eq!(Some("x-deprecated".into()), config.deprecated_extension);
+        assert_eq!(None, config.ignore_examples);
+    }
+
+    #[test]
+    fn from_yaml() {
+        let config = Config::from_yaml(
+            r#"
+base_label: v1.2.0
+head_label: v1.3.0
+ignore_examples: true
+"#,
+        )
+        .expect("Failed to parse config");
+
+        assert_eq!(Some("v1.2.0".into()), config.base_label);
+        assert_eq!(Some("v1.3.0".into()), config.head_label);
+        assert_eq!(Some(true), config.ignore_examples);
+    }
+
+    #[test]
+    fn unknown_setting() {
+        assert!(Config::from_yaml("colour: red").is_err());
+    }
+}
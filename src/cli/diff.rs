@@ -1,7 +1,19 @@
+use super::config::Config;
+use super::formatters::json_lines::render_jsonl_to;
+use super::formatters::junit::render_junit;
 use super::formatters::markdown::Printer;
-use crate::diff::diff_files;
+use super::formatters::template::TemplatePrinter;
+use super::formatters::Labels;
+use crate::diff::{
+    diff_files_with_options, diff_with_options, parse_file, Diff, DiffOptions, NormalizeOptions,
+    Severity,
+};
+use crate::openapi::OpenAPI;
+use crate::overlay::{apply_overlay, Overlay};
 use clap::arg_enum;
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -14,44 +26,641 @@ pub struct DiffCommand {
     #[structopt(parse(from_os_str))]
     head: PathBuf,
 
-    /// Output format for the diff
-    #[structopt(short = "f", long = "format", default_value = "markdown", possible_values = &Format::variants(), case_insensitive = true)]
-    format: Format,
+    /// Config file (annica.toml or annica.yaml) providing defaults for
+    /// these flags. Flags take precedence over the config file, and switches
+    /// it turns on can be turned off with their --no- counterpart
+    #[structopt(long = "config", parse(from_os_str))]
+    config: Option<PathBuf>,
+
+    /// Overlay applied to the base description before diffing
+    #[structopt(long = "base-overlay", parse(from_os_str))]
+    base_overlay: Option<PathBuf>,
+
+    /// Overlay applied to the head description before diffing
+    #[structopt(long = "head-overlay", parse(from_os_str))]
+    head_overlay: Option<PathBuf>,
+
+    /// Output format for the diff [default: markdown]
+    #[structopt(short = "f", long = "format", possible_values = &Format::variants(), case_insensitive = true)]
+    format: Option<Format>,
+
+    /// Report head examples containing readOnly properties in requests
+    /// or writeOnly properties in responses
+    #[structopt(long = "validate-examples", overrides_with = "no_validate_examples")]
+    validate_examples: bool,
+
+    /// Turns off --validate-examples
+    #[structopt(
+        long = "no-validate-examples",
+        overrides_with = "validate_examples",
+        hidden = true
+    )]
+    no_validate_examples: bool,
+
+    /// Ignore example changes, such as reusable examples under components
+    #[structopt(long = "ignore-examples", overrides_with = "no_ignore_examples")]
+    ignore_examples: bool,
+
+    /// Turns off --ignore-examples
+    #[structopt(
+        long = "no-ignore-examples",
+        overrides_with = "ignore_examples",
+        hidden = true
+    )]
+    no_ignore_examples: bool,
+
+    /// Ignore changes to operation and response descriptions
+    #[structopt(
+        long = "ignore-descriptions",
+        overrides_with = "no_ignore_descriptions"
+    )]
+    ignore_descriptions: bool,
+
+    /// Turns off --ignore-descriptions
+    #[structopt(
+        long = "no-ignore-descriptions",
+        overrides_with = "ignore_descriptions",
+        hidden = true
+    )]
+    no_ignore_descriptions: bool,
+
+    /// Strip extensions starting with this prefix from both descriptions
+    /// before diffing [default: x-]
+    #[structopt(long = "strip-extensions")]
+    strip_extensions: Option<Option<String>>,
+
+    /// Regular expression new or changed operationIds must match, e.g.
+    /// "[a-z]+\.[a-z]+". Violations are reported as diagnostics
+    #[structopt(long = "operation-id-pattern")]
+    operation_id_pattern: Option<String>,
+
+    /// Report changes to the declaration order of paths, for documentation
+    /// following it
+    #[structopt(
+        long = "report-path-reordering",
+        overrides_with = "no_report_path_reordering"
+    )]
+    report_path_reordering: bool,
+
+    /// Turns off --report-path-reordering
+    #[structopt(
+        long = "no-report-path-reordering",
+        overrides_with = "report_path_reordering",
+        hidden = true
+    )]
+    no_report_path_reordering: bool,
+
+    /// Compare string enum values ignoring case
+    #[structopt(
+        long = "enum-case-insensitive",
+        overrides_with = "no_enum_case_insensitive"
+    )]
+    enum_case_insensitive: bool,
+
+    /// Turns off --enum-case-insensitive
+    #[structopt(
+        long = "no-enum-case-insensitive",
+        overrides_with = "enum_case_insensitive",
+        hidden = true
+    )]
+    no_enum_case_insensitive: bool,
+
+    /// Resolve references to path items, parameters, request bodies,
+    /// responses, callbacks and part headers before diffing, so inlining a
+    /// component is not reported as a change. Schema references are not
+    /// resolved. Components referenced from other files are bundled first
+    #[structopt(long = "resolve-references", overrides_with = "no_resolve_references")]
+    resolve_references: bool,
+
+    /// Turns off --resolve-references
+    #[structopt(
+        long = "no-resolve-references",
+        overrides_with = "resolve_references",
+        hidden = true
+    )]
+    no_resolve_references: bool,
+
+    /// Report a removed and an added path holding the same path item, or
+    /// sharing an operationId, as a rename
+    #[structopt(
+        long = "detect-path-renames",
+        overrides_with = "no_detect_path_renames"
+    )]
+    detect_path_renames: bool,
+
+    /// Turns off --detect-path-renames
+    #[structopt(
+        long = "no-detect-path-renames",
+        overrides_with = "detect_path_renames",
+        hidden = true
+    )]
+    no_detect_path_renames: bool,
+
+    /// Match paths regardless of the names of their template parameters,
+    /// e.g. /users/{userId} and /users/{id}
+    #[structopt(
+        long = "ignore-path-param-names",
+        overrides_with = "no_ignore_path_param_names"
+    )]
+    ignore_path_param_names: bool,
+
+    /// Turns off --ignore-path-param-names
+    #[structopt(
+        long = "no-ignore-path-param-names",
+        overrides_with = "ignore_path_param_names",
+        hidden = true
+    )]
+    no_ignore_path_param_names: bool,
+
+    /// Treat request bodies whose schema has required properties as
+    /// required
+    #[structopt(
+        long = "infer-request-body-required",
+        overrides_with = "no_infer_request_body_required"
+    )]
+    infer_request_body_required: bool,
+
+    /// Turns off --infer-request-body-required
+    #[structopt(
+        long = "no-infer-request-body-required",
+        overrides_with = "infer_request_body_required",
+        hidden = true
+    )]
+    no_infer_request_body_required: bool,
+
+    /// Drop operations carrying any of these comma-separated tags, e.g.
+    /// "internal", from the diff
+    #[structopt(long = "exclude-tags", use_delimiter = true)]
+    exclude_tags: Vec<String>,
+
+    /// Skip diffing descriptions that are identical once parsed
+    #[structopt(
+        long = "short-circuit-identical",
+        overrides_with = "no_short_circuit_identical"
+    )]
+    short_circuit_identical: bool,
+
+    /// Turns off --short-circuit-identical
+    #[structopt(
+        long = "no-short-circuit-identical",
+        overrides_with = "short_circuit_identical",
+        hidden = true
+    )]
+    no_short_circuit_identical: bool,
+
+    /// Compare schemas as written, without normalizing equivalent OpenAPI
+    /// 3.0 and 3.1 representations such as nullable types
+    #[structopt(long = "no-normalize", overrides_with = "normalize")]
+    no_normalize: bool,
+
+    /// Turns off --no-normalize
+    #[structopt(long = "normalize", overrides_with = "no_normalize", hidden = true)]
+    normalize: bool,
+
+    /// Extension marking a path item deprecated, e.g. "x-deprecated".
+    /// Operations of such path items are reported as deprecated
+    #[structopt(long = "deprecated-extension")]
+    deprecated_extension: Option<String>,
+
+    /// Label for the base description in reports, e.g. a release version
+    /// [default: base]
+    #[structopt(long = "base-label")]
+    base_label: Option<String>,
+
+    /// Label for the head description in reports, e.g. a release version
+    /// [default: head]
+    #[structopt(long = "head-label")]
+    head_label: Option<String>,
+
+    /// Directory the reports are written to with the all format
+    #[structopt(long = "output-dir", parse(from_os_str))]
+    output_dir: Option<PathBuf>,
+
+    /// Only list the operations with breaking changes, one per line along
+    /// with the reason codes of their breaking changes. Breaking changes
+    /// outside of any path are listed on a first `document` line
+    #[structopt(long = "only-breaking-paths")]
+    only_breaking_paths: bool,
+
+    /// Only report changes of at least this severity (info, warning or
+    /// breaking) in the template, JSON lines and JUnit outputs
+    /// [default: info]
+    #[structopt(long = "min-severity")]
+    min_severity: Option<Severity>,
+
+    /// Exit with a non-zero status when the diff has breaking changes,
+    /// e.g. to gate merges in CI
+    #[structopt(long = "fail-on-breaking", overrides_with = "no_fail_on_breaking")]
+    fail_on_breaking: bool,
+
+    /// Turns off --fail-on-breaking
+    #[structopt(
+        long = "no-fail-on-breaking",
+        overrides_with = "fail_on_breaking",
+        hidden = true
+    )]
+    no_fail_on_breaking: bool,
+
+    /// Line format used by the template output, e.g.
+    /// "{severity} {method} {path}: {message}". Available placeholders:
+    /// severity, confidence, kind, path, method, pointer, property, message,
+    /// reason_code, base, head
+    #[structopt(long = "template")]
+    template: Option<String>,
 }
 
 arg_enum! {
-    #[derive(Debug)]
+    #[derive(Debug, Clone)]
     pub enum Format {
         Markdown,
         Json,
         Yaml,
+        Template,
+        JsonLines,
+        Junit,
+        All,
     }
 }
 
 impl DiffCommand {
-    pub fn run(&self) {
-        let res = diff_files(self.base.clone(), self.head.clone());
-
-        match res {
-            Ok(diff) => match self.format {
-                Format::Json => {
-                    let json = serde_json::to_string_pretty(&diff)
-                        .expect("Could not serialize diff to JSON");
-                    println!("{}", json);
-                }
-                Format::Yaml => {
-                    let yaml =
-                        serde_yaml::to_string(&diff).expect("Could not serialize diff to YAML");
-                    println!("{}", yaml);
+    /// Runs the diff, returning the error message when the descriptions
+    /// or the configuration can't be read, or the report can't be written.
+    pub fn run(&self) -> Result<(), String> {
+        let config = match &self.config {
+            Some(path) => Config::from_file(path).map_err(|e| e.to_string())?,
+            None => Config::default(),
+        };
+
+        let format = match (&self.format, &config.format) {
+            (Some(format), _) => format.clone(),
+            (None, Some(format)) => format.parse::<Format>()?,
+            (None, None) => Format::Markdown,
+        };
+
+        let min_severity = match (self.min_severity, &config.min_severity) {
+            (Some(min_severity), _) => min_severity,
+            (None, Some(min_severity)) => min_severity.parse::<Severity>()?,
+            (None, None) => Severity::Info,
+        };
+
+        let output_dir = match (&format, &self.output_dir) {
+            (Format::All, None) => {
+                return Err(String::from("the all format requires --output-dir"));
+            }
+            (_, output_dir) => output_dir.clone(),
+        };
+
+        let template = self.template.as_ref().or(config.template.as_ref());
+
+        let template_printer = match (&format, template) {
+            (Format::Template, Some(template)) | (Format::All, Some(template)) => {
+                Some(TemplatePrinter::new(template).map_err(|e| e.to_string())?)
+            }
+            (Format::Template, None) => {
+                return Err(String::from("the template format requires --template"));
+            }
+            _ => None,
+        };
+
+        let options = self.options(&config);
+
+        let default_labels = Labels::default();
+        let labels = Labels {
+            base: self
+                .base_label
+                .clone()
+                .or(config.base_label)
+                .unwrap_or(default_labels.base),
+            head: self
+                .head_label
+                .clone()
+                .or(config.head_label)
+                .unwrap_or(default_labels.head),
+        };
+
+        let res = match (&self.base_overlay, &self.head_overlay) {
+            (None, None) => diff_files_with_options(self.base.clone(), self.head.clone(), &options)
+                .map_err(|e| e.to_string()),
+            (base_overlay, head_overlay) => Self::load(&self.base, base_overlay.as_deref())
+                .and_then(|base| Ok((base, Self::load(&self.head, head_overlay.as_deref())?)))
+                .and_then(|(base, head)| {
+                    diff_with_options(base, head, &options).map_err(|e| e.to_string())
+                }),
+        };
+
+        let diff = res?;
+
+        let fail = switch(
+            self.fail_on_breaking,
+            self.no_fail_on_breaking,
+            config.fail_on_breaking,
+        )
+        .unwrap_or(false)
+            && diff.has_breaking_changes();
+
+        if self.only_breaking_paths {
+            println!("{}", Self::render_breaking_paths(&diff));
+        } else {
+            match format {
+                Format::All => {
+                    let output_dir = output_dir.expect("output directory was checked above");
+                    Self::write_all(
+                        &output_dir,
+                        &diff,
+                        &labels,
+                        template_printer.as_ref(),
+                        min_severity,
+                    )
+                    .map_err(|e| e.to_string())?;
                 }
-                Format::Markdown => {
-                    let md = Printer::print(&diff);
-                    println!("{}", md);
+                format => {
+                    let stdout = std::io::stdout();
+                    let mut writer = BufWriter::new(stdout.lock());
+
+                    let res = Self::render_to(
+                        &mut writer,
+                        &format,
+                        &diff,
+                        &labels,
+                        template_printer.as_ref(),
+                        min_severity,
+                    )
+                    .and_then(|_| writeln!(writer))
+                    .and_then(|_| writer.flush());
+
+                    res.map_err(|e| e.to_string())?;
                 }
+            }
+        }
+
+        if fail {
+            std::process::exit(1);
+        }
+
+        Ok(())
+    }
+
+    /// The diff options given by the flags, falling back to the config
+    /// file for the ones left out.
+    fn options(&self, config: &Config) -> DiffOptions {
+        let flag =
+            |on: bool, off: bool, config: Option<bool>| switch(on, off, config).unwrap_or(false);
+
+        DiffOptions {
+            validate_examples: flag(
+                self.validate_examples,
+                self.no_validate_examples,
+                config.validate_examples,
+            ),
+            ignore_examples: flag(
+                self.ignore_examples,
+                self.no_ignore_examples,
+                config.ignore_examples,
+            ),
+            ignore_descriptions: flag(
+                self.ignore_descriptions,
+                self.no_ignore_descriptions,
+                config.ignore_descriptions,
+            ),
+            strip_extensions: match &self.strip_extensions {
+                Some(prefix) => Some(prefix.clone().unwrap_or_else(|| String::from("x-"))),
+                None => config.strip_extensions.clone(),
+            },
+            operation_id_pattern: self
+                .operation_id_pattern
+                .clone()
+                .or_else(|| config.operation_id_pattern.clone()),
+            deprecated_extension: self
+                .deprecated_extension
+                .clone()
+                .or_else(|| config.deprecated_extension.clone()),
+            report_path_reordering: flag(
+                self.report_path_reordering,
+                self.no_report_path_reordering,
+                config.report_path_reordering,
+            ),
+            enum_case_insensitive: flag(
+                self.enum_case_insensitive,
+                self.no_enum_case_insensitive,
+                config.enum_case_insensitive,
+            ),
+            resolve_references: flag(
+                self.resolve_references,
+                self.no_resolve_references,
+                config.resolve_references,
+            ),
+            detect_path_renames: flag(
+                self.detect_path_renames,
+                self.no_detect_path_renames,
+                config.detect_path_renames,
+            ),
+            ignore_path_param_names: flag(
+                self.ignore_path_param_names,
+                self.no_ignore_path_param_names,
+                config.ignore_path_param_names,
+            ),
+            infer_request_body_required: flag(
+                self.infer_request_body_required,
+                self.no_infer_request_body_required,
+                config.infer_request_body_required,
+            ),
+            exclude_tags: if self.exclude_tags.is_empty() {
+                config.exclude_tags.clone().unwrap_or_default()
+            } else {
+                self.exclude_tags.clone()
+            },
+            short_circuit_identical: flag(
+                self.short_circuit_identical,
+                self.no_short_circuit_identical,
+                config.short_circuit_identical,
+            ),
+            normalize: if switch(self.normalize, self.no_normalize, config.normalize)
+                .unwrap_or(true)
+            {
+                NormalizeOptions::default()
+            } else {
+                NormalizeOptions::none()
             },
-            Err(e) => {
-                eprintln!("Error: {}", e)
+            // Detected from the OpenAPI versions of the descriptions.
+            json_schema: None,
+            // Read from the descriptions while they are compared.
+            security: Default::default(),
+            resolvers: Default::default(),
+        }
+    }
+
+    /// Reads a description, applying an overlay to it when one is given.
+    fn load(path: &Path, overlay: Option<&Path>) -> Result<OpenAPI, String> {
+        let spec = parse_file(path).map_err(|e| e.to_string())?;
+
+        match overlay {
+            Some(overlay) => Overlay::from_file(overlay)
+                .and_then(|overlay| apply_overlay(spec, &overlay))
+                .map_err(|e| e.to_string()),
+            None => Ok(spec),
+        }
+    }
+
+    /// Writes the report to `writer`. Markdown, template and JSON lines
+    /// reports are streamed as they are rendered.
+    fn render_to(
+        writer: &mut impl Write,
+        format: &Format,
+        diff: &Diff,
+        labels: &Labels,
+        template_printer: Option<&TemplatePrinter>,
+        min_severity: Severity,
+    ) -> std::io::Result<()> {
+        match format {
+            Format::Json => Ok(serde_json::to_writer_pretty(writer, diff)?),
+            Format::Yaml => {
+                let yaml = serde_yaml::to_string(diff).expect("Could not serialize diff to YAML");
+                writer.write_all(yaml.as_bytes())
             }
+            Format::Markdown => Printer::print_to(writer, diff, labels),
+            Format::Junit => writer.write_all(render_junit(diff, min_severity).as_bytes()),
+            Format::Template => match template_printer {
+                Some(printer) => printer.print_to(writer, diff, labels, min_severity),
+                None => Ok(()),
+            },
+            Format::JsonLines => render_jsonl_to(writer, diff, min_severity),
+            Format::All => unreachable!("all formats are written to a directory"),
         }
     }
+
+    /// Renders the operations with breaking changes as
+    /// `METHOD /path: reason_code, ...` lines, sorted by path and method,
+    /// after a `document: reason_code, ...` line for the changes outside of
+    /// any path.
+    fn render_breaking_paths(diff: &Diff) -> String {
+        let mut lines: Vec<(String, Vec<&str>)> = vec![];
+
+        for (path, method, reason_code) in diff.breaking_operations() {
+            let operation = if path.is_empty() {
+                String::from("document")
+            } else {
+                format!("{} {}", method.to_uppercase(), path)
+            };
+
+            match lines.last_mut() {
+                Some((last, reason_codes)) if *last == operation => reason_codes.push(reason_code),
+                _ => lines.push((operation, vec![reason_code])),
+            }
+        }
+
+        lines
+            .into_iter()
+            .map(|(operation, reason_codes)| format!("{}: {}", operation, reason_codes.join(", ")))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Writes the diff in every format to `--output-dir`. The template
+    /// output is only written when a template is configured.
+    fn write_all(
+        output_dir: &Path,
+        diff: &Diff,
+        labels: &Labels,
+        template_printer: Option<&TemplatePrinter>,
+        min_severity: Severity,
+    ) -> std::io::Result<()> {
+        std::fs::create_dir_all(output_dir)?;
+
+        let mut reports = vec![
+            ("report.json", Format::Json),
+            ("report.yaml", Format::Yaml),
+            ("report.md", Format::Markdown),
+            ("report.xml", Format::Junit),
+            ("report.jsonl", Format::JsonLines),
+        ];
+
+        if template_printer.is_some() {
+            reports.push(("report.txt", Format::Template));
+        }
+
+        for (file_name, format) in reports {
+            let mut writer = BufWriter::new(File::create(output_dir.join(file_name))?);
+            Self::render_to(
+                &mut writer,
+                &format,
+                diff,
+                labels,
+                template_printer,
+                min_severity,
+            )?;
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The value of a switch turned on by `on` or off by `off`, falling back
+/// to the config file when neither is given. Only one of them is set, the
+/// last one given overriding the other.
+fn switch(on: bool, off: bool, config: Option<bool>) -> Option<bool> {
+    if on {
+        Some(true)
+    } else if off {
+        Some(false)
+    } else {
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(args: &[&str]) -> DiffCommand {
+        DiffCommand::from_iter(std::iter::once("diff").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn missing_file() {
+        let res = command(&["fixtures/missing.yaml", "fixtures/pet-store.yaml"]).run();
+        assert!(res.is_err());
+
+        let res = command(&[
+            "fixtures/pet-store.yaml",
+            "fixtures/pet-store.yaml",
+            "--config",
+            "fixtures/missing.toml",
+        ])
+        .run();
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn flags_override_config() {
+        let config = Config {
+            resolve_references: Some(true),
+            normalize: Some(false),
+            ..Default::default()
+        };
+
+        let options = command(&["base.yaml", "head.yaml"]).options(&config);
+        assert!(options.resolve_references);
+        assert!(!options.normalize.nullable);
+
+        let options = command(&[
+            "base.yaml",
+            "head.yaml",
+            "--no-resolve-references",
+            "--normalize",
+        ])
+        .options(&config);
+        assert!(!options.resolve_references);
+        assert!(options.normalize.nullable);
+
+        // The last of a flag and its counterpart wins.
+        let options = command(&[
+            "base.yaml",
+            "head.yaml",
+            "--no-resolve-references",
+            "--resolve-references",
+        ])
+        .options(&config);
+        assert!(options.resolve_references);
+    }
 }
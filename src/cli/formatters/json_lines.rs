@@ -0,0 +1,96 @@
+use crate::diff::{Change, Diff, Severity};
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{self, Write};
+
+/// A line of the report: a change along with its severity and the values
+/// found at its pointer.
+#[derive(Serialize)]
+struct Line {
+    #[serde(flatten)]
+    change: Change,
+    severity: Severity,
+    reason_code: &'static str,
+    /// Set for documentation regressions, such as a removed example, see
+    /// `ChangeKind::is_documentation_regression`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<Value>,
+}
+
+/// Renders every change of a diff as one JSON object per line. See
+/// `render_jsonl_to`.
+pub fn render_jsonl(diff: &Diff) -> String {
+    let mut report = vec![];
+    render_jsonl_to(&mut report, diff, Severity::Info).expect("Writing to a Vec cannot fail");
+    String::from_utf8(report).expect("serde_json writes UTF-8")
+}
+
+/// Writes every change of a diff to `writer` as one JSON object per line,
+/// in the deterministic order of `Diff::changes`, for log pipelines and
+/// `jq`. Each line is written as soon as its change is rendered, but the
+/// changes and the serialized descriptions their values are looked up in
+/// are held in memory. Changes below `min_severity` are left out.
+pub fn render_jsonl_to(
+    writer: &mut impl Write,
+    diff: &Diff,
+    min_severity: Severity,
+) -> io::Result<()> {
+    let values = diff.values();
+
+    for change in diff.changes() {
+        let severity = change.severity();
+        if severity < min_severity {
+            continue;
+        }
+
+        let line = Line {
+            severity,
+            reason_code: change.kind.reason_code(),
+            note: if change.kind.is_documentation_regression() {
+                Some("documentation_regression")
+            } else {
+                None
+            },
+            before: values.before(&change),
+            after: values.after(&change),
+            change,
+        };
+
+        serde_json::to_writer(&mut *writer, &line)?;
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::diff_files;
+    use std::path::PathBuf;
+
+    #[test]
+    fn one_change_per_line() {
+        let diff = diff_files(
+            PathBuf::from("fixtures/pet-store.yaml"),
+            PathBuf::from("fixtures/pet-store-changed.yaml"),
+        )
+        .expect("Failed to diff YAML");
+
+        let report = render_jsonl(&diff);
+        let lines: Vec<_> = report.lines().collect();
+
+        assert_eq!(diff.changes().count(), lines.len());
+        for (line, change) in lines.iter().zip(diff.changes()) {
+            let value: Value = serde_json::from_str(line).expect("Invalid JSON line");
+            assert_eq!(change.pointer, value["pointer"]);
+            assert_eq!(change.severity().to_string(), value["severity"]);
+            assert!(value.get("before").is_some() || value.get("after").is_some());
+        }
+        assert_eq!(report, render_jsonl(&diff));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::diff::{Change, Confidence, Diff, Severity};
+use std::collections::BTreeMap;
+
+/// Suite holding the changes that do not belong to a path, such as info
+/// or server changes.
+const DOCUMENT_SUITE: &str = "document";
+
+/// Renders a diff as JUnit XML for CI test reporters. Changes are grouped
+/// into one `<testsuite>` per path; breaking changes are failing test
+/// cases and every other change a passing one. Changes below `min_severity`
+/// are left out.
+pub fn render_junit(diff: &Diff, min_severity: Severity) -> String {
+    let mut suites: BTreeMap<String, Vec<Change>> = BTreeMap::new();
+
+    for change in diff
+        .changes()
+        .filter(|change| change.severity() >= min_severity)
+    {
+        let suite = change
+            .path
+            .clone()
+            .unwrap_or_else(|| String::from(DOCUMENT_SUITE));
+        suites.entry(suite).or_default().push(change);
+    }
+
+    let total: usize = suites.values().map(|changes| changes.len()).sum();
+    let failures = suites
+        .values()
+        .flatten()
+        .filter(|change| change.is_breaking())
+        .count();
+
+    let mut result = String::new();
+    result.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    result.push_str(&format!(
+        "<testsuites name=\"anicca\" tests=\"{}\" failures=\"{}\">\n",
+        total, failures
+    ));
+
+    for (suite, changes) in &suites {
+        let suite_failures = changes.iter().filter(|change| change.is_breaking()).count();
+
+        result.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            escape(suite),
+            changes.len(),
+            suite_failures
+        ));
+
+        for change in changes {
+            let name = match &change.method {
+                Some(method) => format!("{} {}", method.to_uppercase(), change.pointer),
+                None => change.pointer.clone(),
+            };
+
+            result.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\"",
+                escape(&name),
+                change.kind.reason_code()
+            ));
+
+            if change.is_breaking() {
+                let message = match change.confidence {
+                    Confidence::Certain => change.message.clone(),
+                    Confidence::Heuristic => format!("(heuristic) {}", change.message),
+                };
+
+                result.push_str(">\n");
+                result.push_str(&format!(
+                    "      <failure message=\"{}\" type=\"breaking\"/>\n",
+                    escape(&message)
+                ));
+                result.push_str("    </testcase>\n");
+            } else {
+                result.push_str("/>\n");
+            }
+        }
+
+        result.push_str("  </testsuite>\n");
+    }
+
+    result.push_str("</testsuites>");
+    result
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::diff_files;
+    use std::path::PathBuf;
+
+    #[test]
+    fn no_changes() {
+        let diff = Diff::default();
+
+        assert_eq!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"anicca\" tests=\"0\" failures=\"0\">\n</testsuites>",
+            render_junit(&diff, Severity::Info)
+        );
+    }
+
+    #[test]
+    fn suites_by_path() {
+        let diff = diff_files(
+            PathBuf::from("fixtures/pet-store.yaml"),
+            PathBuf::from("fixtures/pet-store-changed.yaml"),
+        )
+        .expect("Failed to diff YAML");
+
+        let junit = render_junit(&diff, Severity::Info);
+
+        assert!(junit.contains("<testsuite name=\"document\""));
+        assert!(junit.contains("<testsuite name=\"/pets\""));
+        assert!(junit.contains("type=\"breaking\""));
+        assert_eq!(junit, render_junit(&diff, Severity::Info));
+    }
+
+    #[test]
+    fn escapes_xml() {
+        assert_eq!("&lt;a href=&quot;x&quot;&gt;", escape("<a href=\"x\">"));
+    }
+}
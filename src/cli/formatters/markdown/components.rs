@@ -0,0 +1,75 @@
+use super::schema::SchemaPrinter;
+use crate::diff::components::ComponentsDiff;
+
+pub struct ComponentsPrinter<'a> {
+    pub diff: Option<&'a ComponentsDiff>,
+    /// Whether head added a `components` section base did not have.
+    pub introduced: bool,
+    /// Whether head removed the `components` section of base.
+    pub removed: bool,
+}
+
+impl<'a> ComponentsPrinter<'a> {
+    pub fn print(&self) -> String {
+        let mut result = String::new();
+
+        if self.introduced {
+            result.push_str("The `components` section was added.\n\n");
+        } else if self.removed {
+            result.push_str("The `components` section was removed.\n\n");
+        }
+
+        if let Some(examples_diff) = self.diff.and_then(|diff| diff.examples.as_ref()) {
+            result.push_str("Reusable examples have changed:\n");
+
+            for (name, _) in &examples_diff.added {
+                result.push_str(format!("  - Example `{}` was added.\n", name).as_str());
+            }
+
+            for (name, _) in &examples_diff.removed {
+                result.push_str(format!("  - Example `{}` was removed.\n", name).as_str());
+            }
+
+            let mut changed: Vec<_> = examples_diff.changed.keys().collect();
+            changed.sort();
+
+            for name in changed {
+                result.push_str(format!("  - Example `{}` was changed.\n", name).as_str());
+            }
+
+            result.push('\n');
+        }
+
+        if let Some(schemas_diff) = self.diff.and_then(|diff| diff.schemas.as_ref()) {
+            result.push_str("Reusable schemas have changed:\n");
+
+            for (name, _) in &schemas_diff.added {
+                result.push_str(format!("  - Schema `{}` was added.\n", name).as_str());
+            }
+
+            for (name, _) in &schemas_diff.removed {
+                result.push_str(format!("  - Schema `{}` was removed.\n", name).as_str());
+            }
+
+            let mut changed: Vec<_> = schemas_diff.changed.iter().collect();
+            changed.sort_by_key(|(name, _)| *name);
+
+            for (name, schema_diff) in changed {
+                result.push_str(format!("  - Schema `{}` was changed:\n", name).as_str());
+                let schema = SchemaPrinter {
+                    diff: schema_diff,
+                    indent: 4,
+                };
+                result.push_str(&schema.print());
+            }
+
+            result.push('\n');
+        }
+
+        if result.is_empty() {
+            result
+        } else {
+            format!("### Components\n\n{}", result)
+        }
+    }
+}
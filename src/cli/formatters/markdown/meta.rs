@@ -1,4 +1,5 @@
 use super::extensions::ExtensionsPrinter;
+use crate::diff::security::SecurityDiff;
 use crate::diff::Diff;
 
 pub struct MetaPrinter<'a> {
@@ -19,7 +20,35 @@ impl<'a> MetaPrinter<'a> {
             );
         }
 
-        result.push('\n');
+        if let Some(security) = &self.diff.security {
+            result.push_str(
+                format!(
+                    "  - Security now requires {} (was {}).\n",
+                    SecurityDiff::describe(&security.to),
+                    SecurityDiff::describe(&security.from)
+                )
+                .as_str(),
+            );
+        }
+
+        if let Some(order) = &self.diff.paths_reordered {
+            result.push_str(
+                format!(
+                    "  - The order of paths changed to {}.\n",
+                    order
+                        .to
+                        .iter()
+                        .map(|path| format!("`{}`", path))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+                .as_str(),
+            );
+        }
+
+        if !result.is_empty() {
+            result.push('\n');
+        }
 
         if let Some(info_diff) = &self.diff.info {
             result.push_str("API info has changed:\n");
@@ -166,6 +195,8 @@ impl<'a> MetaPrinter<'a> {
                 .print();
                 result.push_str(&extensions_printed_diff);
             }
+
+            result.push('\n');
         }
 
         result
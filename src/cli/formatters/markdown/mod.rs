@@ -1,3 +1,4 @@
+pub mod components;
 pub mod extensions;
 pub mod meta;
 pub mod operations;
@@ -7,33 +8,149 @@ pub mod request_body;
 pub mod responses;
 pub mod schema;
 
-use crate::diff::Diff;
+use super::Labels;
+use crate::diff::{ChangelogSection, Diff};
+use components::ComponentsPrinter;
 use meta::MetaPrinter;
 use paths::PathsPrinter;
+use std::collections::HashMap;
+use std::io::{self, Write};
 
 pub struct Printer {}
 
 impl Printer {
-    pub fn print(diff: &Diff) -> String {
-        let mut result = String::new();
+    pub fn print(diff: &Diff, labels: &Labels) -> String {
+        let mut result = vec![];
+        Self::print_to(&mut result, diff, labels).expect("Writing to a Vec cannot fail");
+        String::from_utf8(result).expect("Markdown is valid UTF-8")
+    }
 
-        if !diff.has_changes() {
-            result.push_str("No changes.");
-            return result;
+    /// Writes the report to `writer` section by section, and path by path
+    /// within the paths and webhooks sections, rather than building it as
+    /// one string. The diff itself, and the changes the summary counts,
+    /// are still held in memory.
+    pub fn print_to(writer: &mut impl Write, diff: &Diff, labels: &Labels) -> io::Result<()> {
+        if !diff.has_changes() && diff.diagnostics.is_empty() {
+            return write!(writer, "No changes.");
         }
 
-        result.push_str("## OpenAPI diff\n\n");
+        write!(
+            writer,
+            "## OpenAPI diff between `{}` and `{}`\n\n",
+            labels.base, labels.head
+        )?;
+
+        write!(writer, "{}\n\n", Self::summary(diff))?;
 
-        let meta = MetaPrinter { diff: &diff }.print();
-        result.push_str(&meta);
+        writer.write_all(MetaPrinter { diff }.print().as_bytes())?;
 
         if let Some(paths_diff) = &diff.paths {
-            let paths = PathsPrinter { diff: paths_diff }.print();
-            result.push_str(&paths);
+            PathsPrinter {
+                diff: paths_diff,
+                heading: "Paths",
+            }
+            .print_to(writer)?;
+        }
+
+        if let Some(webhooks_diff) = &diff.webhooks {
+            PathsPrinter {
+                diff: webhooks_diff,
+                heading: "Webhooks",
+            }
+            .print_to(writer)?;
         }
 
-        result
+        let components = ComponentsPrinter {
+            diff: diff.components.as_ref(),
+            introduced: diff.components_introduced,
+            removed: diff.components_removed,
+        }
+        .print();
+        writer.write_all(components.as_bytes())?;
+
+        if let Some(path_items_diff) = diff
+            .components
+            .as_ref()
+            .and_then(|components| components.path_items.as_ref())
+        {
+            PathsPrinter {
+                diff: path_items_diff,
+                heading: "Reusable path items",
+            }
+            .print_to(writer)?;
+        }
+
+        if !diff.diagnostics.is_empty() {
+            writer.write_all(b"### Diagnostics\n\n")?;
+
+            for diagnostic in &diff.diagnostics {
+                writeln!(
+                    writer,
+                    "  - `{}`: {}",
+                    diagnostic.pointer, diagnostic.message
+                )?;
+            }
+        }
+
+        Ok(())
     }
+
+    /// Counts the changes of a diff on a single line, such as
+    /// "3 breaking changes, 2 additions", e.g. for a status check.
+    pub fn summary(diff: &Diff) -> String {
+        let (mut breaking, mut additions, mut other) = (0, 0, 0);
+
+        for change in diff.changes() {
+            if change.is_breaking() {
+                breaking += 1;
+            } else if change.kind.changelog_section() == ChangelogSection::Added {
+                additions += 1;
+            } else {
+                other += 1;
+            }
+        }
+
+        let counts: Vec<_> = [
+            (breaking, "breaking change", "breaking changes"),
+            (additions, "addition", "additions"),
+            (other, "other change", "other changes"),
+        ]
+        .iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, singular, plural)| {
+            format!("{} {}", count, if *count == 1 { singular } else { plural })
+        })
+        .collect();
+
+        if counts.is_empty() {
+            String::from("No changes")
+        } else {
+            counts.join(", ")
+        }
+    }
+
+    /// The title and summary of a status check reporting the diff, such as
+    /// a GitHub check run: the `summary` line, which stays well under the
+    /// 255 characters titles are limited to, and the full report.
+    pub fn status_check(diff: &Diff, labels: &Labels) -> (String, String) {
+        let mut title = Self::summary(diff);
+        if let Some((index, _)) = title.char_indices().nth(MAX_TITLE_LENGTH) {
+            title.truncate(index);
+        }
+
+        (title, Self::print(diff, labels))
+    }
+}
+
+/// The maximum length, in characters, of a status check title.
+const MAX_TITLE_LENGTH: usize = 254;
+
+/// The entries of a map ordered by key, so reports are the same from one
+/// run to the next.
+pub(crate) fn sorted<K: Ord, V>(map: &HashMap<K, V>) -> Vec<(&K, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries
 }
 
 #[cfg(test)]
@@ -44,6 +161,153 @@ mod tests {
     #[test]
     fn no_changes() {
         let diff = Diff::default();
-        assert_eq!("No changes.", Printer::print(&diff));
+        assert_eq!("No changes.", Printer::print(&diff, &Labels::default()));
+    }
+
+    #[test]
+    fn labels_in_header() {
+        let diff = Diff {
+            version: crate::diff::common::StringDiff::from_strings("3.0.0".into(), "3.1.0".into()),
+            ..Default::default()
+        };
+        let labels = Labels {
+            base: "v1.2.0".into(),
+            head: "v1.3.0".into(),
+        };
+
+        assert!(Printer::print(&diff, &labels)
+            .starts_with("## OpenAPI diff between `v1.2.0` and `v1.3.0`\n"));
+    }
+
+    fn spec(paths: &str) -> crate::openapi::OpenAPI {
+        serde_yaml::from_str(&format!(
+            "openapi: 3.0.0\ninfo:\n  title: Pets\n  version: 1.0.0\npaths:\n{}",
+            paths
+        ))
+        .expect("Failed to parse description")
+    }
+
+    #[test]
+    fn snapshot() {
+        let base = spec(
+            r#"
+  /pets:
+    get:
+      summary: List pets
+      responses:
+        "200":
+          description: The pets.
+    delete:
+      summary: Delete pets
+      responses:
+        "204":
+          description: Deleted.
+  /stores:
+    get:
+      responses: {}
+"#,
+        );
+        let head = spec(
+            r#"
+  /pets:
+    get:
+      summary: List all pets
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+      responses:
+        "200":
+          description: The pets.
+        "429":
+          description: Too many requests.
+    delete:
+      summary: Remove pets
+      responses:
+        "204":
+          description: Deleted.
+  /owners:
+    get:
+      operationId: listOwners
+      responses: {}
+"#,
+        );
+
+        let diff = crate::diff::diff(base, head).expect("Failed to diff");
+
+        assert_eq!(
+            concat!(
+                "## OpenAPI diff between `base` and `head`\n\n",
+                "1 breaking change, 3 additions, 2 other changes\n\n",
+                "### Paths\n\n",
+                "  - ➕ GET /owners (`listOwners`) was added.\n\n",
+                "  - ➖ Path `/stores` was removed.\n\n",
+                "  - On operation `DELETE /pets`\n",
+                "    - Operation summary changed from `Delete pets` to `Remove pets`.\n",
+                "  - On operation `GET /pets`\n",
+                "    - Operation summary changed from `List pets` to `List all pets`.\n",
+                "    - ➕ Query parameter `limit` was added.\n",
+                "    - ➕ Response with status `429` was added.\n\n",
+            ),
+            Printer::print(&diff, &Labels::default())
+        );
+    }
+
+    #[test]
+    fn heuristic_renames() {
+        let paths = |path: &str| {
+            format!(
+                "  {}:\n    get:\n      operationId: listCats\n      responses: {{}}\n",
+                path
+            )
+        };
+        let options = crate::diff::DiffOptions {
+            detect_path_renames: true,
+            ..Default::default()
+        };
+
+        let diff = crate::diff::diff_with_options(
+            spec(&paths("/cats")),
+            spec(&paths("/felines")),
+            &options,
+        )
+        .expect("Failed to diff");
+
+        assert!(Printer::print(&diff, &Labels::default())
+            .contains("  - (heuristic) Path `/cats` was renamed to `/felines`.\n"));
+    }
+
+    #[test]
+    fn summary() {
+        assert_eq!("No changes", Printer::summary(&Diff::default()));
+
+        let diff = crate::diff::diff_files(
+            std::path::PathBuf::from("fixtures/pet-store.yaml"),
+            std::path::PathBuf::from("fixtures/pet-store-changed.yaml"),
+        )
+        .expect("Failed to diff");
+
+        let summary = Printer::summary(&diff);
+        assert!(summary.contains("breaking change"), "{}", summary);
+        assert!(Printer::print(&diff, &Labels::default()).contains(&format!("\n\n{}\n\n", summary)));
+    }
+
+    #[test]
+    fn status_check() {
+        let diff = crate::diff::diff_files(
+            std::path::PathBuf::from("fixtures/pet-store.yaml"),
+            std::path::PathBuf::from("fixtures/pet-store-changed.yaml"),
+        )
+        .expect("Failed to diff");
+
+        let (title, summary) = Printer::status_check(&diff, &Labels::default());
+        assert_eq!(Printer::summary(&diff), title);
+        assert!(title.chars().count() < 255);
+        assert_eq!(Printer::print(&diff, &Labels::default()), summary);
+        assert_eq!(
+            (title, summary),
+            Printer::status_check(&diff, &Labels::default())
+        );
     }
 }
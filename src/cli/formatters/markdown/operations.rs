@@ -2,6 +2,7 @@ use super::parameters::ParametersPrinter;
 use super::request_body::RequestBodyPrinter;
 use super::responses::ResponsesPrinter;
 use crate::diff::operations::OperationDiff;
+use crate::diff::security::SecurityDiff;
 
 pub struct OperationsPrinter<'a> {
     pub operation_diff: &'a OperationDiff,
@@ -31,6 +32,17 @@ impl<'a> OperationsPrinter<'a> {
             );
         }
 
+        if let Some(primary_tag_diff) = &self.operation_diff.primary_tag {
+            result.push_str(
+                format!(
+                    "    - Primary tag changed from `{}` to `{}`.\n",
+                    primary_tag_diff.from.clone().unwrap_or(String::from("null")),
+                    primary_tag_diff.to.clone().unwrap_or(String::from("null"))
+                )
+                .as_str(),
+            );
+        }
+
         if let Some(summary_diff) = &self.operation_diff.summary {
             if let Some(from) = &summary_diff.from {
                 if let Some(to) = &summary_diff.to {
@@ -77,6 +89,25 @@ impl<'a> OperationsPrinter<'a> {
             }
         }
 
+        if let Some(deprecated) = &self.operation_diff.deprecated {
+            if deprecated.to {
+                result.push_str("    - Operation was deprecated.\n");
+            } else {
+                result.push_str("    - Operation is no longer deprecated.\n");
+            }
+        }
+
+        if let Some(security) = &self.operation_diff.security {
+            result.push_str(
+                format!(
+                    "    - Security now requires {} (was {}).\n",
+                    SecurityDiff::describe(&security.to),
+                    SecurityDiff::describe(&security.from)
+                )
+                .as_str(),
+            );
+        }
+
         let params = ParametersPrinter {
             parameters: &self.operation_diff.parameters,
         }
@@ -86,19 +117,38 @@ impl<'a> OperationsPrinter<'a> {
 
         if let Some(request_body) = &self.operation_diff.request_body {
             let request_body = RequestBodyPrinter {
-                request_body: &request_body,
+                request_body,
             }
             .print();
 
             result.push_str(&request_body);
         }
 
-        let params = ResponsesPrinter {
-            responses: &self.operation_diff.responses,
+        if let Some(responses) = &self.operation_diff.responses {
+            let responses = ResponsesPrinter {
+                responses,
+            }
+            .print();
+
+            result.push_str(&responses);
         }
-        .print();
 
-        result.push_str(&params);
+        if let Some(callbacks) = &self.operation_diff.callbacks {
+            for (name, _) in &callbacks.added {
+                result.push_str(format!("    - Callback `{}` was added.\n", name).as_str());
+            }
+
+            for (name, _) in &callbacks.removed {
+                result.push_str(format!("    - Callback `{}` was removed.\n", name).as_str());
+            }
+
+            let mut changed: Vec<_> = callbacks.changed.keys().collect();
+            changed.sort();
+
+            for name in changed {
+                result.push_str(format!("    - Callback `{}` was changed.\n", name).as_str());
+            }
+        }
 
         result
     }
@@ -13,7 +13,7 @@ impl<'a> ParametersPrinter<'a> {
             match param {
                 ReferenceOr::Reference { reference } => {
                     result.push_str(
-                        format!("      - Referenced parameter `{}` was added.\n", reference)
+                        format!("    - ➕ Referenced parameter `{}` was added.\n", reference)
                             .as_str(),
                     );
                 }
@@ -26,7 +26,7 @@ impl<'a> ParametersPrinter<'a> {
                     } => {
                         result.push_str(
                             format!(
-                                "       - Query parameter `{}` was added.\n",
+                                "    - ➕ Query parameter `{}` was added.\n",
                                 parameter_data.name
                             )
                             .as_str(),
@@ -37,7 +37,7 @@ impl<'a> ParametersPrinter<'a> {
                         style: _,
                     } => {
                         result.push_str(
-                            format!("      - Header `{}` was added.\n", parameter_data.name)
+                            format!("    - ➕ Header `{}` was added.\n", parameter_data.name)
                                 .as_str(),
                         );
                     }
@@ -47,7 +47,7 @@ impl<'a> ParametersPrinter<'a> {
                     } => {
                         result.push_str(
                             format!(
-                                "      - Path parameter `{}` was added.\n",
+                                "    - ➕ Path parameter `{}` was added.\n",
                                 parameter_data.name
                             )
                             .as_str(),
@@ -58,7 +58,7 @@ impl<'a> ParametersPrinter<'a> {
                         style: _,
                     } => {
                         result.push_str(
-                            format!("      - Cookie `{}` was added.\n", parameter_data.name)
+                            format!("    - ➕ Cookie `{}` was added.\n", parameter_data.name)
                                 .as_str(),
                         );
                     }
@@ -71,7 +71,7 @@ impl<'a> ParametersPrinter<'a> {
                 ReferenceOr::Reference { reference } => {
                     result.push_str(
                         format!(
-                            "      - Referenced parameter `{}` was removed.\n",
+                            "    - ➖ Referenced parameter `{}` was removed.\n",
                             reference
                         )
                         .as_str(),
@@ -86,7 +86,7 @@ impl<'a> ParametersPrinter<'a> {
                     } => {
                         result.push_str(
                             format!(
-                                "      - Query parameter `{}` was removed.\n",
+                                "    - ➖ Query parameter `{}` was removed.\n",
                                 parameter_data.name
                             )
                             .as_str(),
@@ -97,7 +97,7 @@ impl<'a> ParametersPrinter<'a> {
                         style: _,
                     } => {
                         result.push_str(
-                            format!("      - Header `{}` was removed.\n", parameter_data.name)
+                            format!("    - ➖ Header `{}` was removed.\n", parameter_data.name)
                                 .as_str(),
                         );
                     }
@@ -107,7 +107,7 @@ impl<'a> ParametersPrinter<'a> {
                     } => {
                         result.push_str(
                             format!(
-                                "      - Path parameter `{}` was removed.\n",
+                                "    - ➖ Path parameter `{}` was removed.\n",
                                 parameter_data.name
                             )
                             .as_str(),
@@ -118,7 +118,7 @@ impl<'a> ParametersPrinter<'a> {
                         style: _,
                     } => {
                         result.push_str(
-                            format!("      - Cookie `{}` was removed.\n", parameter_data.name)
+                            format!("    - ➖ Cookie `{}` was removed.\n", parameter_data.name)
                                 .as_str(),
                         );
                     }
@@ -1,97 +1,135 @@
 use super::operations::OperationsPrinter;
+use super::sorted;
+use crate::diff::path_items::PathItemDiff;
 use crate::diff::paths::PathsDiff;
 use crate::openapi::ReferenceOr;
+use std::io::{self, Write};
 
 pub struct PathsPrinter<'a> {
     pub diff: &'a PathsDiff,
+    /// Section title, such as `Paths` or `Webhooks`.
+    pub heading: &'a str,
 }
 
 impl<'a> PathsPrinter<'a> {
-    pub fn print(&self) -> String {
-        let mut result = String::new();
-
+    /// Writes the section to `writer`, one changed path at a time in path
+    /// order.
+    pub fn print_to(&self, writer: &mut impl Write) -> io::Result<()> {
         if !self.diff.has_changes() {
-            return result;
+            return Ok(());
         }
 
-        result.push_str("### Paths\n\n");
-        result.push_str(&self.print_added_paths());
-        result.push_str(&self.print_removed_paths());
-        result.push_str(&self.print_changed_paths());
+        write!(writer, "### {}\n\n", self.heading)?;
+        writer.write_all(self.print_added_paths().as_bytes())?;
+        writer.write_all(self.print_removed_paths().as_bytes())?;
+
+        // Renames are inferred from removed and added paths, so they are
+        // marked as heuristic like in the other reports.
+        for rename in &self.diff.renamed {
+            writeln!(
+                writer,
+                "  - (heuristic) Path `{}` was renamed to `{}`.",
+                rename.from, rename.to
+            )?;
+
+            if let Some(path_item_diff) = &rename.changed {
+                writer.write_all(
+                    self.print_changed_path(&rename.to, path_item_diff)
+                        .as_bytes(),
+                )?;
+            }
+        }
 
-        result
+        if !self.diff.renamed.is_empty() {
+            writer.write_all(b"\n")?;
+        }
+
+        if self.diff.changed.is_empty() {
+            return Ok(());
+        }
+
+        let mut paths: Vec<_> = self.diff.changed.keys().collect();
+        paths.sort();
+
+        for path in paths {
+            writer.write_all(
+                self.print_changed_path(path, &self.diff.changed[path])
+                    .as_bytes(),
+            )?;
+        }
+
+        writer.write_all(b"\n")
     }
 
-    pub fn print_changed_paths(&self) -> String {
+    fn print_changed_path(&self, path: &str, path_item_diff: &PathItemDiff) -> String {
         let mut result = String::new();
 
-        if self.diff.changed.is_empty() {
-            return result;
+        for renamed in &path_item_diff.path_parameters_renamed {
+            result.push_str(
+                format!(
+                    "  - Path parameter `{}` was renamed to `{}`.\n",
+                    renamed.from, renamed.to
+                )
+                .as_str(),
+            );
         }
 
-        for (path, path_item_diff) in &self.diff.changed {
-            for operation_method in &path_item_diff.operations_added {
-                match &operation_method.1.operation_id {
-                    Some(op_id) => {
-                        result.push_str(
-                            format!(
-                                "  - Operation {} {} was added ({}).\n",
-                                operation_method.0, path, op_id 
-                            )
-                            .as_str(),
-                        );
-                    }
-                    None => {
-                        result.push_str(
-                            format!(
-                                "  - Operation {} {} was added (Missing operationId).\n",
-                                operation_method.0, path
-                            )
-                            .as_str(),
-                        );
-                    }
+        for operation_method in &path_item_diff.operations_added {
+            match &operation_method.1.operation_id {
+                Some(op_id) => {
+                    result.push_str(
+                        format!(
+                            "  - ➕ Operation {} {} was added ({}).\n",
+                            operation_method.0, path, op_id
+                        )
+                        .as_str(),
+                    );
+                }
+                None => {
+                    result.push_str(
+                        format!(
+                            "  - ➕ Operation {} {} was added (Missing operationId).\n",
+                            operation_method.0, path
+                        )
+                        .as_str(),
+                    );
                 }
             }
+        }
 
-            for operation_method in &path_item_diff.operations_removed {
-                match &operation_method.1.operation_id {
-                    Some(op_id) => {
-                        result.push_str(
-                            format!(
-                                "  - Operation {} {} was removed ({}).\n",
-                                operation_method.0, path, op_id
-                            )
-                            .as_str(),
-                        );
-                    }
-                    None => {
-                        result.push_str(
-                            format!(
-                                "  - Operation {} {} was removed (Missing operationId).\n",
-                                operation_method.0, path
-                            )
-                            .as_str(),
-                        );
-                    }
+        for operation_method in &path_item_diff.operations_removed {
+            match &operation_method.1.operation_id {
+                Some(op_id) => {
+                    result.push_str(
+                        format!(
+                            "  - ➖ Operation {} {} was removed ({}).\n",
+                            operation_method.0, path, op_id
+                        )
+                        .as_str(),
+                    );
+                }
+                None => {
+                    result.push_str(
+                        format!(
+                            "  - ➖ Operation {} {} was removed (Missing operationId).\n",
+                            operation_method.0, path
+                        )
+                        .as_str(),
+                    );
                 }
             }
+        }
 
-            for (method, operation_diff) in &path_item_diff.operations_changed {
-                result.push_str(
-                    format!("  - On operation `{} {}`\n", method.to_uppercase(), path).as_str(),
-                );
+        for (method, operation_diff) in sorted(&path_item_diff.operations_changed) {
+            result.push_str(
+                format!("  - On operation `{} {}`\n", method.to_uppercase(), path).as_str(),
+            );
 
-                let op_diff = OperationsPrinter {
-                    operation_diff: &operation_diff,
-                }
-                .print();
+            let op_diff = OperationsPrinter { operation_diff }.print();
 
-                result.push_str(&op_diff);
-            }
+            result.push_str(&op_diff);
         }
 
-        result.push('\n');
-
         result
     }
 
@@ -103,7 +141,7 @@ impl<'a> PathsPrinter<'a> {
         }
 
         for removed in &self.diff.removed {
-            result.push_str(format!("  - Path `{}` was removed.\n", removed.0).as_str());
+            result.push_str(format!("  - ➖ Path `{}` was removed.\n", removed.0).as_str());
         }
 
         result.push('\n');
@@ -123,7 +161,7 @@ impl<'a> PathsPrinter<'a> {
                 if let Some(get) = &path_item.get {
                     result.push_str(
                         format!(
-                            "  - {} {} (`{}`) was added.\n",
+                            "  - ➕ {} {} (`{}`) was added.\n",
                             "GET",
                             added.0,
                             get.operation_id.clone().unwrap_or("No operation id".into())
@@ -135,7 +173,7 @@ impl<'a> PathsPrinter<'a> {
                 if let Some(post) = &path_item.post {
                     result.push_str(
                         format!(
-                            "  - {} {} (`{}`) was added.\n",
+                            "  - ➕ {} {} (`{}`) was added.\n",
                             "POST",
                             added.0,
                             post.operation_id
@@ -149,7 +187,7 @@ impl<'a> PathsPrinter<'a> {
                 if let Some(put) = &path_item.put {
                     result.push_str(
                         format!(
-                            "  - {} {} (`{}`) was added.\n",
+                            "  - ➕ {} {} (`{}`) was added.\n",
                             "PUT",
                             added.0,
                             put.operation_id.clone().unwrap_or("No operation id".into())
@@ -161,7 +199,7 @@ impl<'a> PathsPrinter<'a> {
                 if let Some(patch) = &path_item.patch {
                     result.push_str(
                         format!(
-                            "  - {} {} (`{}`) was added.\n",
+                            "  - ➕ {} {} (`{}`) was added.\n",
                             "PATCH",
                             added.0,
                             patch
@@ -173,11 +211,11 @@ impl<'a> PathsPrinter<'a> {
                     );
                 }
             } else {
-                result.push_str(format!("  - {}\n", added.0).as_str());
+                result.push_str(format!("  - ➕ Path `{}` was added.\n", added.0).as_str());
             }
         }
 
-        result.push_str("\n");
+        result.push('\n');
 
         result
     }
@@ -1,4 +1,7 @@
 use super::schema::SchemaPrinter;
+use super::sorted;
+use crate::diff::encoding::{EncodingsDiff, HeaderDiff};
+use crate::diff::media_type::MediaTypeDiff;
 use crate::diff::request_body::RequestBodyDiff;
 
 pub struct RequestBodyPrinter<'a> {
@@ -17,6 +20,22 @@ impl<'a> RequestBodyPrinter<'a> {
             result.push_str("    - Request body was removed.\n");
         }
 
+        if let Some(reference) = &self.request_body.reference {
+            result.push_str(&format!(
+                "    - Request body reference changed from `{}` to `{}`.\n",
+                reference.from.as_deref().unwrap_or("inline"),
+                reference.to.as_deref().unwrap_or("inline")
+            ));
+        }
+
+        if let Some(required) = &self.request_body.required {
+            if required.to {
+                result.push_str("    - Request body became required.\n");
+            } else {
+                result.push_str("    - Request body became optional.\n");
+            }
+        }
+
         if let Some(content_diff) = &self.request_body.content_changed {
             for media_type_pair in &content_diff.added {
                 result.push_str(&format!(
@@ -32,7 +51,7 @@ impl<'a> RequestBodyPrinter<'a> {
                 ));
             }
 
-            for (media_type, media_type_diff) in &content_diff.changed {
+            for (media_type, media_type_diff) in sorted(&content_diff.changed) {
                 result.push_str(&format!(
                     "    - Request body media type {} changed:.\n",
                     media_type
@@ -40,15 +59,77 @@ impl<'a> RequestBodyPrinter<'a> {
 
                 if let Some(schema_diff) = &media_type_diff.schema_changed {
                     let schema = SchemaPrinter {
-                        diff: &schema_diff,
+                        diff: schema_diff,
                         indent: 6,
                     }
                     .print();
                     result.push_str(&schema);
                 }
+
+                if media_type_diff.has_example_changes() {
+                    result.push_str(&example_line(media_type_diff, 6));
+                }
+
+                if let Some(encoding_diff) = &media_type_diff.encoding_changed {
+                    result.push_str(&encoding_lines(encoding_diff));
+                }
             }
         }
 
         result
     }
-}
\ No newline at end of file
+}
+
+/// Describes the header changes of the parts of a multipart body.
+fn encoding_lines(encoding_diff: &EncodingsDiff) -> String {
+    let mut result = String::new();
+
+    for (part, part_diff) in sorted(&encoding_diff.changed) {
+        if let Some(headers_diff) = &part_diff.headers {
+            for (name, header) in &headers_diff.added {
+                let required = if HeaderDiff::is_required(header) {
+                    "Required"
+                } else {
+                    "Optional"
+                };
+                result.push_str(&format!(
+                    "      - {} header {} was added to part {}.\n",
+                    required, name, part
+                ));
+            }
+
+            for (name, _) in &headers_diff.removed {
+                result.push_str(&format!(
+                    "      - Header {} was removed from part {}.\n",
+                    name, part
+                ));
+            }
+
+            for (name, header_diff) in sorted(&headers_diff.changed) {
+                if let Some(required) = &header_diff.required {
+                    let state = if required.to { "required" } else { "optional" };
+                    result.push_str(&format!(
+                        "      - Header {} of part {} became {}.\n",
+                        name, part, state
+                    ));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Describes example changes of a media type, pointing out when the schema
+/// itself is unchanged.
+pub(crate) fn example_line(media_type_diff: &MediaTypeDiff, indent: usize) -> String {
+    if media_type_diff.has_schema_changes() {
+        format!("{:indent$}- Examples changed.\n", "", indent = indent)
+    } else {
+        format!(
+            "{:indent$}- Only the examples changed, the schema is unchanged.\n",
+            "",
+            indent = indent
+        )
+    }
+}
@@ -1,4 +1,7 @@
+use super::request_body::example_line;
 use super::schema::SchemaPrinter;
+use super::sorted;
+use crate::diff::response::ResponseDiff;
 use crate::diff::responses::ResponsesDiff;
 
 pub struct ResponsesPrinter<'a> {
@@ -11,53 +14,89 @@ impl<'a> ResponsesPrinter<'a> {
 
         for response_code_pair in &self.responses.added {
             result.push_str(&format!(
-                "      - Response with status `{}` was added.\n",
+                "    - ➕ Response with status `{}` was added.\n",
                 response_code_pair.0
             ));
         }
 
         for response_code_pair in &self.responses.removed {
             result.push_str(&format!(
-                "      - Response with status `{}` was removed.\n",
+                "    - ➖ Response with status `{}` was removed.\n",
                 response_code_pair.0
             ));
         }
 
-        for (status_code, response_diff) in &self.responses.changed {
+        for (status_code, response_diff) in sorted(&self.responses.changed) {
             result.push_str(&format!(
-                "      - Response with status `{}` changed:\n",
+                "    - Response with status `{}` changed:\n",
                 status_code
             ));
+            result.push_str(&Self::print_response(response_diff));
+        }
 
-            if let Some(content_diff) = &response_diff.content {
-                for media_type_pair in &content_diff.added {
-                    result.push_str(&format!(
-                        "        - A response media type `{}` was added.\n",
-                        media_type_pair.0
-                    ));
-                }
+        if self.responses.default_added.is_some() {
+            result.push_str("    - ➕ Default response was added.\n");
+        }
 
-                for media_type_pair in &content_diff.removed {
-                    result.push_str(&format!(
-                        "        - Response media type `{}` was removed.\n",
-                        media_type_pair.0
-                    ));
-                }
+        if self.responses.default_removed.is_some() {
+            result.push_str("    - ➖ Default response was removed.\n");
+        }
+
+        if let Some(response_diff) = &self.responses.default_changed {
+            result.push_str("    - Default response changed:\n");
+            result.push_str(&Self::print_response(response_diff));
+        }
 
-                for (media_type, media_type_diff) in &content_diff.changed {
-                    result.push_str(&format!(
-                        "        - Response media type `{}` changed:\n",
-                        media_type
-                    ));
-
-                    if let Some(schema_diff) = &media_type_diff.schema_changed {
-                        let schema = SchemaPrinter {
-                            diff: &schema_diff,
-                            indent: 10,
-                        }
-                        .print();
-                        result.push_str(&schema);
+        result
+    }
+
+    fn print_response(response_diff: &ResponseDiff) -> String {
+        let mut result = String::new();
+
+        if let Some(reference) = &response_diff.reference {
+            result.push_str(&format!(
+                "      - Reference changed from `{}` to `{}`.\n",
+                reference.from.as_deref().unwrap_or("inline"),
+                reference.to.as_deref().unwrap_or("inline")
+            ));
+        }
+
+        if response_diff.description.is_some() {
+            result.push_str("      - Description changed.\n");
+        }
+
+        if let Some(content_diff) = &response_diff.content {
+            for media_type_pair in &content_diff.added {
+                result.push_str(&format!(
+                    "      - A response media type `{}` was added.\n",
+                    media_type_pair.0
+                ));
+            }
+
+            for media_type_pair in &content_diff.removed {
+                result.push_str(&format!(
+                    "      - Response media type `{}` was removed.\n",
+                    media_type_pair.0
+                ));
+            }
+
+            for (media_type, media_type_diff) in sorted(&content_diff.changed) {
+                result.push_str(&format!(
+                    "      - Response media type `{}` changed:\n",
+                    media_type
+                ));
+
+                if let Some(schema_diff) = &media_type_diff.schema_changed {
+                    let schema = SchemaPrinter {
+                        diff: schema_diff,
+                        indent: 8,
                     }
+                    .print();
+                    result.push_str(&schema);
+                }
+
+                if media_type_diff.has_example_changes() {
+                    result.push_str(&example_line(media_type_diff, 8));
                 }
             }
         }
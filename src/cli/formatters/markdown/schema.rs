@@ -1,3 +1,4 @@
+use super::sorted;
 use crate::diff::schema::SchemaDiff;
 
 pub struct SchemaPrinter<'a> {
@@ -9,12 +10,51 @@ impl<'a> SchemaPrinter<'a> {
     pub fn print(&self) -> String {
         let mut result = String::new();
 
-        if let Some(type_change) = &self.diff.type_changed {
+        if let Some(category_change) = &self.diff.category_changed {
+            let head_schema = serde_json::to_string_pretty(&category_change.head)
+                .expect("Could not serialize schema to JSON");
+
+            result.push_str(&format!(
+                "{:indent$}- Schema changed from {} to {}. New schema:\n\n",
+                "",
+                category_change.from,
+                category_change.to,
+                indent = self.indent,
+            ));
+            result.push_str(&format!(
+                "{:indent$}```json\n",
+                "",
+                indent = self.indent + 2
+            ));
+            for line in head_schema.lines() {
+                result.push_str(&format!(
+                    "{:indent$}{}\n",
+                    "",
+                    line,
+                    indent = self.indent + 2
+                ));
+            }
+            result.push_str(&format!("{:indent$}```\n", "", indent = self.indent + 2));
+        } else if let Some(type_added) = &self.diff.type_added {
+            result.push_str(&format!(
+                "{:indent$}- Schema type `{}` was added.\n",
+                "",
+                type_added,
+                indent = self.indent,
+            ));
+        } else if let Some(type_removed) = &self.diff.type_removed {
+            result.push_str(&format!(
+                "{:indent$}- Schema type `{}` was removed.\n",
+                "",
+                type_removed,
+                indent = self.indent,
+            ));
+        } else if let Some(type_change) = &self.diff.type_changed {
             result.push_str(&format!(
                 "{:indent$}- Schema type changed from `{}` to `{}`.\n",
                 "",
-                type_change.from.clone().unwrap_or("null".into()),
-                type_change.to.clone().unwrap_or("null".into()),
+                type_change.from,
+                type_change.to,
                 indent = self.indent,
             ));
         }
@@ -27,6 +67,67 @@ impl<'a> SchemaPrinter<'a> {
             ));
         }
 
+        if let Some(pattern) = &self.diff.pattern_changed {
+            result.push_str(&format!(
+                "{:indent$}- Schema pattern changed from `{}` to `{}`.\n",
+                "",
+                pattern.from.as_deref().unwrap_or("none"),
+                pattern.to.as_deref().unwrap_or("none"),
+                indent = self.indent,
+            ));
+        }
+
+        for (keyword, reference) in [
+            ("$ref", &self.diff.reference_changed),
+            ("$dynamicRef", &self.diff.dynamic_ref_changed),
+        ] {
+            if let Some(reference) = reference {
+                result.push_str(&format!(
+                    "{:indent$}- Schema `{}` changed from `{}` to `{}`.\n",
+                    "",
+                    keyword,
+                    reference.from.as_deref().unwrap_or("none"),
+                    reference.to.as_deref().unwrap_or("none"),
+                    indent = self.indent,
+                ));
+            }
+        }
+
+        if let Some(max_length) = &self.diff.max_length_changed {
+            let describe = |max_length: Option<u32>| match max_length {
+                Some(max_length) => max_length.to_string(),
+                None => String::from("none"),
+            };
+
+            result.push_str(&format!(
+                "{:indent$}- Schema maxLength changed from `{}` to `{}`.{}\n",
+                "",
+                describe(max_length.from),
+                describe(max_length.to),
+                if max_length.truncation_risk() {
+                    " **Potential data truncation.**"
+                } else {
+                    ""
+                },
+                indent = self.indent,
+            ));
+        }
+
+        if let Some(multiple_of) = &self.diff.multiple_of_changed {
+            let describe = |multiple_of: Option<f64>| match multiple_of {
+                Some(multiple_of) => multiple_of.to_string(),
+                None => String::from("none"),
+            };
+
+            result.push_str(&format!(
+                "{:indent$}- Schema multipleOf changed from `{}` to `{}`.\n",
+                "",
+                describe(multiple_of.from),
+                describe(multiple_of.to),
+                indent = self.indent,
+            ));
+        }
+
         if let Some(properties_diff) = &self.diff.properties_changed {
             for p in &properties_diff.added {
                 result.push_str(&format!(
@@ -46,7 +147,16 @@ impl<'a> SchemaPrinter<'a> {
                 ));
             }
 
-            for (p, diff) in &properties_diff.changed {
+            for p in &properties_diff.no_longer_read_only {
+                result.push_str(&format!(
+                    "{:indent$}- Required property `{}` is no longer readOnly.\n",
+                    "",
+                    p,
+                    indent = self.indent
+                ));
+            }
+
+            for (p, diff) in sorted(&properties_diff.changed) {
                 result.push_str(&format!(
                     "{:indent$}- Property `{}` was changed:\n",
                     "",
@@ -79,6 +189,171 @@ impl<'a> SchemaPrinter<'a> {
             result.push_str(&schema_diff);
         }
 
+        if let Some(examples_diff) = &self.diff.examples_changed {
+            for example in &examples_diff.added {
+                result.push_str(&format!(
+                    "{:indent$}- Schema example `{}` was added.\n",
+                    "",
+                    example,
+                    indent = self.indent
+                ));
+            }
+
+            for example in &examples_diff.removed {
+                result.push_str(&format!(
+                    "{:indent$}- Schema example `{}` was removed.\n",
+                    "",
+                    example,
+                    indent = self.indent
+                ));
+            }
+        }
+
+        if self.diff.not_added.is_some() {
+            result.push_str(&format!(
+                "{:indent$}- A `not` constraint was added.\n",
+                "",
+                indent = self.indent
+            ));
+        }
+
+        if self.diff.not_removed.is_some() {
+            result.push_str(&format!(
+                "{:indent$}- The `not` constraint was removed.\n",
+                "",
+                indent = self.indent
+            ));
+        }
+
+        let subschemas = [
+            ("`if` conditional", &self.diff.if_changed),
+            ("`then` conditional", &self.diff.then_changed),
+            ("`else` conditional", &self.diff.else_changed),
+            (
+                "`propertyNames` constraint",
+                &self.diff.property_names_changed,
+            ),
+        ];
+
+        for (subject, subschema) in subschemas {
+            let subschema = match subschema {
+                Some(subschema) => subschema,
+                None => continue,
+            };
+
+            if subschema.removed.is_some() {
+                result.push_str(&format!(
+                    "{:indent$}- The {} was removed.\n",
+                    "",
+                    subject,
+                    indent = self.indent
+                ));
+            }
+
+            if subschema.added.is_some() {
+                result.push_str(&format!(
+                    "{:indent$}- A {} was added.\n",
+                    "",
+                    subject,
+                    indent = self.indent
+                ));
+            }
+
+            if let Some(schema_diff) = &subschema.changed {
+                result.push_str(&format!(
+                    "{:indent$}- The {} changed:\n",
+                    "",
+                    subject,
+                    indent = self.indent
+                ));
+
+                let schema_diff = SchemaPrinter {
+                    diff: schema_diff,
+                    indent: self.indent + 2,
+                }
+                .print();
+
+                result.push_str(&schema_diff);
+            }
+        }
+
+        if let Some(dependencies) = &self.diff.dependent_required_changed {
+            for (property, list_diff) in sorted(&dependencies.changed) {
+                for dependent in &list_diff.added {
+                    result.push_str(&format!(
+                        "{:indent$}- Property `{}` became required when `{}` is present.\n",
+                        "",
+                        dependent,
+                        property,
+                        indent = self.indent
+                    ));
+                }
+
+                for dependent in &list_diff.removed {
+                    result.push_str(&format!(
+                        "{:indent$}- Property `{}` is no longer required when `{}` is present.\n",
+                        "",
+                        dependent,
+                        property,
+                        indent = self.indent
+                    ));
+                }
+            }
+        }
+
+        if let Some(schemas) = &self.diff.dependent_schemas_changed {
+            for (property, _) in &schemas.added {
+                result.push_str(&format!(
+                    "{:indent$}- A dependent schema was added for `{}`.\n",
+                    "",
+                    property,
+                    indent = self.indent
+                ));
+            }
+
+            for (property, _) in &schemas.removed {
+                result.push_str(&format!(
+                    "{:indent$}- The dependent schema for `{}` was removed.\n",
+                    "",
+                    property,
+                    indent = self.indent
+                ));
+            }
+
+            for (property, schema_diff) in sorted(&schemas.changed) {
+                result.push_str(&format!(
+                    "{:indent$}- The dependent schema for `{}` changed:\n",
+                    "",
+                    property,
+                    indent = self.indent
+                ));
+
+                let schema_diff = SchemaPrinter {
+                    diff: schema_diff,
+                    indent: self.indent + 2,
+                }
+                .print();
+
+                result.push_str(&schema_diff);
+            }
+        }
+
+        if let Some(not_diff) = &self.diff.not_changed {
+            result.push_str(&format!(
+                "{:indent$}- The `not` constraint changed:\n",
+                "",
+                indent = self.indent
+            ));
+
+            let schema_diff = SchemaPrinter {
+                diff: not_diff,
+                indent: self.indent + 2,
+            }
+            .print();
+
+            result.push_str(&schema_diff);
+        }
+
         result
     }
 }
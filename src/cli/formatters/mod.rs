@@ -1 +1,20 @@
+pub mod json_lines;
+pub mod junit;
 pub mod markdown;
+pub mod template;
+
+/// Names given to the two compared descriptions in rendered reports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Labels {
+    pub base: String,
+    pub head: String,
+}
+
+impl Default for Labels {
+    fn default() -> Self {
+        Self {
+            base: String::from("base"),
+            head: String::from("head"),
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use super::Labels;
+use crate::diff::{Change, Diff, Severity};
+use std::io::{self, Write};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum TemplateError {
+    #[error("unknown placeholder `{{{0}}}` in template")]
+    UnknownPlaceholder(String),
+    #[error("unclosed placeholder in template")]
+    UnclosedPlaceholder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Placeholder {
+    Severity,
+    Confidence,
+    Kind,
+    Path,
+    Method,
+    Pointer,
+    Property,
+    Message,
+    ReasonCode,
+    Base,
+    Head,
+}
+
+impl Placeholder {
+    fn from_name(name: &str) -> Result<Self, TemplateError> {
+        match name {
+            "severity" => Ok(Placeholder::Severity),
+            "confidence" => Ok(Placeholder::Confidence),
+            "kind" => Ok(Placeholder::Kind),
+            "path" => Ok(Placeholder::Path),
+            "method" => Ok(Placeholder::Method),
+            "pointer" => Ok(Placeholder::Pointer),
+            "property" => Ok(Placeholder::Property),
+            "message" => Ok(Placeholder::Message),
+            "reason_code" => Ok(Placeholder::ReasonCode),
+            "base" => Ok(Placeholder::Base),
+            "head" => Ok(Placeholder::Head),
+            _ => Err(TemplateError::UnknownPlaceholder(name.to_string())),
+        }
+    }
+
+    fn render(&self, change: &Change, labels: &Labels) -> String {
+        match self {
+            Placeholder::Severity => change.severity().to_string(),
+            Placeholder::Confidence => change.confidence.to_string(),
+            Placeholder::Kind => change.kind.to_string(),
+            Placeholder::Path => change.path.clone().unwrap_or_default(),
+            Placeholder::Method => change
+                .method
+                .as_ref()
+                .map(|method| method.to_uppercase())
+                .unwrap_or_default(),
+            Placeholder::Pointer => change.pointer.clone(),
+            Placeholder::Property => change.property.clone().unwrap_or_default(),
+            Placeholder::Message => change.message.clone(),
+            Placeholder::ReasonCode => change.kind.reason_code().to_string(),
+            Placeholder::Base => labels.base.clone(),
+            Placeholder::Head => labels.head.clone(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// Renders every change of a diff on its own line, following a user
+/// provided format string such as `{severity} {method} {path}: {message}`.
+/// Literal braces are written as `{{` and `}}`.
+pub struct TemplatePrinter {
+    segments: Vec<Segment>,
+}
+
+impl TemplatePrinter {
+    pub fn new(template: &str) -> Result<Self, TemplateError> {
+        let mut segments = vec![];
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(TemplateError::UnclosedPlaceholder),
+                        }
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Placeholder(Placeholder::from_name(name.trim())?));
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Writes one line per change to `writer` as changes are rendered,
+    /// rather than building the whole report first. Changes below
+    /// `min_severity` are left out.
+    pub fn print_to(
+        &self,
+        writer: &mut impl Write,
+        diff: &Diff,
+        labels: &Labels,
+        min_severity: Severity,
+    ) -> io::Result<()> {
+        let changes = diff
+            .changes()
+            .filter(|change| change.severity() >= min_severity);
+
+        for (index, change) in changes.enumerate() {
+            if index > 0 {
+                writer.write_all(b"\n")?;
+            }
+            writer.write_all(self.print_change(&change, labels).as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn print_change(&self, change: &Change, labels: &Labels) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(literal) => literal.clone(),
+                Segment::Placeholder(placeholder) => placeholder.render(change, labels),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::{diff, ChangeKind, Confidence};
+    use crate::openapi::OpenAPI;
+
+    /// The first change between two versions of the paths of a
+    /// description.
+    fn change(base: &str, head: &str) -> Change {
+        let spec = |paths: &str| -> OpenAPI {
+            serde_yaml::from_str(&format!(
+                "openapi: \"3.0.0\"\ninfo: {{ title: Pets, version: 1.0.0 }}\npaths: {}\n",
+                paths
+            ))
+            .expect("Failed to parse description")
+        };
+
+        diff(spec(base), spec(head))
+            .expect("Failed to diff")
+            .changes()
+            .next()
+            .expect("paths should have changed")
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let printer = TemplatePrinter::new("{severity} {method} {path}: {message} [{reason_code}]")
+            .expect("Failed to parse template");
+
+        let change = change(
+            "{ /pets: { get: { responses: {} }, post: { responses: {} } } }",
+            "{ /pets: { post: { responses: {} } } }",
+        );
+
+        assert_eq!(
+            "breaking GET /pets: Operation `GET /pets` was removed. [operation_removed]",
+            printer.print_change(&change, &Labels::default())
+        );
+    }
+
+    #[test]
+    fn confidence_marker() {
+        let printer =
+            TemplatePrinter::new("{confidence}: {message}").expect("Failed to parse template");
+
+        let change = Change {
+            confidence: Confidence::Heuristic,
+            ..change("{ /pets: {} }", "{}")
+        };
+
+        assert_eq!(
+            "heuristic: Path `/pets` was removed.",
+            printer.print_change(&change, &Labels::default())
+        );
+    }
+
+    #[test]
+    fn escaped_braces() {
+        let printer = TemplatePrinter::new("{{{kind}}}").expect("Failed to parse template");
+
+        assert_eq!(
+            "{PathAdded}",
+            printer.print_change(&change("{}", "{ /pets: {} }"), &Labels::default())
+        );
+    }
+
+    #[test]
+    fn kind_without_details() {
+        let printer = TemplatePrinter::new("{kind}").expect("Failed to parse template");
+
+        let change = change(
+            "{ /pets: { get: { responses: {} } } }",
+            "{ /pets: { get: { parameters: [{ name: limit, in: query, required: true, schema: { type: integer } }], responses: {} } } }",
+        );
+
+        assert_eq!(ChangeKind::ParameterAdded { required: true }, change.kind);
+        assert_eq!(
+            "ParameterAdded",
+            printer.print_change(&change, &Labels::default())
+        );
+    }
+
+    #[test]
+    fn unknown_placeholder() {
+        assert_eq!(
+            Some(TemplateError::UnknownPlaceholder(String::from("status"))),
+            TemplatePrinter::new("{status} {path}").err()
+        );
+        assert_eq!(
+            Some(TemplateError::UnclosedPlaceholder),
+            TemplatePrinter::new("{path").err()
+        );
+    }
+}
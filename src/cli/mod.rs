@@ -1,5 +1,7 @@
+pub(crate) mod baseline;
+pub(crate) mod config;
 pub(crate) mod diff;
-pub(crate) mod formatters;
+pub mod formatters;
 
 use structopt::StructOpt;
 
@@ -10,16 +12,23 @@ pub struct Anicca {
     pub command: Command,
 }
 
+// Parsed once from the arguments, so the size of the variants is irrelevant.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, StructOpt)]
 pub enum Command {
     /// Diff two OpenAPI descriptions
     Diff(diff::DiffCommand),
+    /// Capture a baseline of an OpenAPI description, or check a description
+    /// against one
+    Baseline(baseline::BaselineCommand),
 }
 
 impl Anicca {
-    pub fn run(&self) {
+    /// Runs the command, returning the error message when it failed.
+    pub fn run(&self) -> Result<(), String> {
         match &self.command {
             Command::Diff(command) => command.run(),
+            Command::Baseline(command) => command.run(),
         }
     }
 }
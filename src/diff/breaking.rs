@@ -0,0 +1,221 @@
+use super::changes::{collect_paths, Change};
+use super::paths::PathsDiff;
+use crate::openapi::OpenAPI;
+use serde::Serialize;
+use serde_json::Value;
+
+/// A breaking change along with the values it changed. See
+/// `Diff::breaking_changes_iter`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BreakingChange {
+    #[serde(flatten)]
+    pub change: Change,
+    /// A stable, machine readable code for the kind of change.
+    pub reason_code: &'static str,
+    /// The value at the pointer of the change in the base description,
+    /// absent for additions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+    /// The value at the pointer of the change in the head description,
+    /// absent for removals.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+/// Classifies the changes to the paths of a diff, for callers holding a
+/// `PathsDiff` such as the one returned by `PathsDiff::from_paths`. Changes are
+/// classified with `Change::is_breaking`, see `Diff::breaking_changes_iter`
+/// to classify a whole diff.
+pub struct BreakingChanges<'a> {
+    paths: &'a PathsDiff,
+}
+
+impl<'a> BreakingChanges<'a> {
+    pub fn new(paths: &'a PathsDiff) -> Self {
+        Self { paths }
+    }
+
+    /// The breaking changes, in the deterministic order of `Diff::changes`.
+    /// Their values are left out since a `PathsDiff` does not keep the
+    /// descriptions it was computed from.
+    pub fn breaking_changes(&self) -> Vec<BreakingChange> {
+        let values = Values::default();
+
+        collect_paths(self.paths)
+            .into_iter()
+            .filter(Change::is_breaking)
+            .map(|change| values.breaking_change(change))
+            .collect()
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        collect_paths(self.paths).iter().any(Change::is_breaking)
+    }
+}
+
+/// Looks up the values of changes in the descriptions a diff was
+/// computed from.
+#[derive(Default)]
+pub(crate) struct Values {
+    base: Value,
+    head: Value,
+}
+
+impl Values {
+    pub(crate) fn from_specs(base: &OpenAPI, head: &OpenAPI) -> Self {
+        Self {
+            base: serde_json::to_value(base).unwrap_or_default(),
+            head: serde_json::to_value(head).unwrap_or_default(),
+        }
+    }
+
+    pub(crate) fn breaking_change(&self, change: Change) -> BreakingChange {
+        BreakingChange {
+            reason_code: change.kind.reason_code(),
+            before: self.before(&change),
+            after: self.after(&change),
+            change,
+        }
+    }
+
+    /// The value at the pointer of a change in the base description.
+    pub(crate) fn before(&self, change: &Change) -> Option<Value> {
+        resolve(&self.base, &change.pointer).cloned()
+    }
+
+    /// The value at the pointer of a change in the head description.
+    pub(crate) fn after(&self, change: &Change) -> Option<Value> {
+        resolve(&self.head, &change.pointer).cloned()
+    }
+}
+
+/// Resolves a change pointer. Unlike a plain JSON pointer, array elements
+/// such as parameters may be addressed by their `name` or `$ref`.
+fn resolve<'a>(value: &'a Value, pointer: &str) -> Option<&'a Value> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .try_fold(value, |value, segment| match value {
+            Value::Object(object) => object.get(&segment),
+            Value::Array(items) => match segment.parse::<usize>() {
+                Ok(index) => items.get(index),
+                Err(_) => items.iter().find(|item| {
+                    item.get("name").and_then(Value::as_str) == Some(&segment)
+                        || item.get("$ref").and_then(Value::as_str) == Some(&segment)
+                }),
+            },
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::openapi::Paths;
+    use serde_json::json;
+
+    #[test]
+    fn resolves_parameters_by_name() {
+        let value = json!({
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            { "name": "limit", "in": "query" },
+                            { "$ref": "#/components/parameters/page" }
+                        ]
+                    }
+                }
+            }
+        });
+
+        assert_eq!(
+            Some(&json!("query")),
+            resolve(&value, "/paths/~1pets/get/parameters/limit/in")
+        );
+        assert!(resolve(
+            &value,
+            "/paths/~1pets/get/parameters/#~1components~1parameters~1page"
+        )
+        .is_some());
+        assert!(resolve(&value, "/paths/~1pets/get/parameters/offset").is_none());
+        assert_eq!(
+            Some(&json!({ "name": "limit", "in": "query" })),
+            resolve(&value, "/paths/~1pets/get/parameters/0")
+        );
+    }
+
+    #[test]
+    fn breaking_paths() {
+        let base: Paths = serde_yaml::from_str(
+            r#"
+/pets:
+  get:
+    parameters:
+      - name: limit
+        in: query
+        schema:
+          type: integer
+    responses:
+      "200":
+        description: The pets.
+      "404":
+        description: No pets.
+  post:
+    responses: {}
+/stores:
+  get:
+    responses: {}
+"#,
+        )
+        .expect("Failed to parse base");
+        let head: Paths = serde_yaml::from_str(
+            r#"
+/pets:
+  get:
+    parameters:
+      - name: limit
+        in: query
+        schema:
+          type: string
+      - name: owner
+        in: query
+        required: true
+        schema:
+          type: string
+      - name: page
+        in: query
+        schema:
+          type: integer
+    responses:
+      "200":
+        description: The pets.
+"#,
+        )
+        .expect("Failed to parse head");
+
+        let diff = PathsDiff::from_paths(&base, &head).expect("Failed to diff");
+        let breaking = BreakingChanges::new(&diff);
+
+        let reason_codes: Vec<_> = breaking
+            .breaking_changes()
+            .iter()
+            .map(|breaking| breaking.reason_code)
+            .collect();
+        assert_eq!(
+            vec![
+                "path_removed",
+                "operation_removed",
+                "required_parameter_added",
+                "schema_type_changed",
+                "response_removed",
+            ],
+            reason_codes
+        );
+        assert!(breaking.is_breaking());
+
+        let diff = PathsDiff::from_paths(&base, &base).expect("Failed to diff");
+        assert!(!BreakingChanges::new(&diff).is_breaking());
+    }
+}
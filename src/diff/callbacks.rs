@@ -0,0 +1,169 @@
+use super::options::DiffOptions;
+use super::path_items::PathItemDiff;
+use super::paths::PathsDiff;
+use crate::openapi::{Callback, ReferenceOr};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+pub type CallbackPair = (String, ReferenceOr<Callback>);
+
+/// Changes to the callbacks of an operation, matched by name. Each changed
+/// callback is diffed like a set of paths keyed by callback expression.
+///
+/// Callbacks are requests the API sends to the client, so their roles are
+/// inverted compared to regular operations: the callback request body is
+/// produced by the API and its responses are produced by the client. See
+/// `Change::is_breaking`.
+#[derive(Debug, Serialize)]
+pub struct CallbacksDiff {
+    pub added: Vec<CallbackPair>,
+    pub removed: Vec<CallbackPair>,
+    pub changed: HashMap<String, PathsDiff>,
+}
+
+impl CallbacksDiff {
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+
+    pub fn from_callbacks(
+        base: &BTreeMap<String, ReferenceOr<Callback>>,
+        head: &BTreeMap<String, ReferenceOr<Callback>>,
+        options: &DiffOptions,
+    ) -> Self {
+        let mut added = vec![];
+        let mut removed = vec![];
+        let mut changed = HashMap::new();
+
+        for (name, base_callback) in base {
+            match head.get(name) {
+                Some(head_callback) => match (base_callback, head_callback) {
+                    (ReferenceOr::Item(base_callback), ReferenceOr::Item(head_callback)) => {
+                        let callback_diff =
+                            Self::from_expressions(base_callback, head_callback, options);
+
+                        if callback_diff.has_changes() {
+                            changed.insert(name.clone(), callback_diff);
+                        }
+                    }
+                    _ => {
+                        if base_callback != head_callback {
+                            removed.push((name.clone(), base_callback.clone()));
+                            added.push((name.clone(), head_callback.clone()));
+                        }
+                    }
+                },
+                None => removed.push((name.clone(), base_callback.clone())),
+            }
+        }
+
+        for (name, head_callback) in head {
+            if !base.contains_key(name) {
+                added.push((name.clone(), head_callback.clone()));
+            }
+        }
+
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+
+    fn from_expressions(base: &Callback, head: &Callback, options: &DiffOptions) -> PathsDiff {
+        let mut diff = PathsDiff::default();
+
+        for (expression, base_path_item) in base {
+            match head.get(expression) {
+                Some(head_path_item) => {
+                    let path_item_diff =
+                        PathItemDiff::from_items(base_path_item, head_path_item, options);
+
+                    if path_item_diff.has_change() {
+                        diff.changed.insert(expression.clone(), path_item_diff);
+                    }
+                }
+                None => diff.removed.push((
+                    expression.clone(),
+                    ReferenceOr::Item(base_path_item.clone()),
+                )),
+            }
+        }
+
+        for (expression, head_path_item) in head {
+            if !base.contains_key(expression) {
+                diff.added.push((
+                    expression.clone(),
+                    ReferenceOr::Item(head_path_item.clone()),
+                ));
+            }
+        }
+
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::{diff, ChangeKind};
+    use crate::openapi::OpenAPI;
+
+    const SPEC: &str = r#"
+openapi: "3.0.0"
+info:
+  title: Subscriptions
+  version: 1.0.0
+paths:
+  /subscriptions:
+    post:
+      responses:
+        "201":
+          description: Subscribed
+      callbacks:
+        onEvent:
+          "{$request.body#/callbackUrl}":
+            post:
+              requestBody:
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        status:
+                          type: string
+              responses:
+                "200":
+                  description: Received
+"#;
+
+    #[test]
+    fn tightened_callback_payload() {
+        let base: OpenAPI = serde_yaml::from_str(SPEC).expect("Failed to parse base");
+        let head: OpenAPI = serde_yaml::from_str(&SPEC.replace(
+            "                          type: string\n",
+            "                          type: string\n                          enum: [active]\n",
+        ))
+        .expect("Failed to parse head");
+
+        let diff = diff(base.clone(), head.clone()).expect("Failed to diff");
+
+        let changes: Vec<_> = diff.changes().collect();
+        assert_eq!(1, changes.len());
+        assert_eq!(ChangeKind::EnumAdded, changes[0].kind);
+        assert_eq!(
+            "/paths/~1subscriptions/post/callbacks/onEvent/{$request.body#~1callbackUrl}/post/requestBody/content/application~1json/schema/properties/status/enum",
+            changes[0].pointer
+        );
+        // Clients receive the callback payload, so only loosening it can
+        // break them.
+        assert!(!changes[0].is_breaking());
+
+        let diff = crate::diff::diff(head, base).expect("Failed to diff");
+        let change = diff
+            .changes()
+            .next()
+            .expect("enum should have been removed");
+        assert_eq!(ChangeKind::EnumRemoved, change.kind);
+        assert!(change.is_breaking());
+    }
+}
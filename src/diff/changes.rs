@@ -0,0 +1,2979 @@
+use super::callbacks::CallbacksDiff;
+use super::common::{escape_pointer, OptionalStringDiff};
+use super::components::{ComponentsDiff, ExamplesDiff, SchemasDiff};
+use super::content::ContentDiff;
+use super::encoding::{EncodingsDiff, HeaderDiff, HeadersDiff};
+use super::extensions::ExtensionsDiff;
+use super::info::InfoDiff;
+use super::messages::{English, MessageProvider};
+use super::operations::OperationDiff;
+use super::parameters::ParametersDiff;
+use super::path_items::PathItemDiff;
+use super::paths::PathsDiff;
+use super::request_body::RequestBodyDiff;
+use super::response::ResponseDiff;
+use super::responses::ResponsesDiff;
+use super::schema::{SchemaDiff, SubschemaDiff};
+use super::security::SecurityDiff;
+use super::servers::ServersDiff;
+use super::Diff;
+use crate::openapi::{Parameter, ReferenceOr, SchemaType};
+use serde::Serialize;
+
+/// The canonical taxonomy of changes reported by a diff. Every `Change`
+/// yielded by `Diff::changes` maps to exactly one kind.
+///
+/// New kinds may be added without a major release, so matches on this
+/// enum need a wildcard arm.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub enum ChangeKind {
+    /// The `openapi` version of the description changed.
+    OpenApiVersionChanged,
+    InfoTitleChanged,
+    InfoDescriptionChanged,
+    InfoTermsOfServiceChanged,
+    InfoVersionChanged,
+    ContactAdded,
+    ContactRemoved,
+    ContactChanged,
+    LicenseAdded,
+    LicenseRemoved,
+    LicenseChanged,
+    ServerAdded,
+    ServerRemoved,
+    ServerDescriptionChanged,
+    PathAdded,
+    PathRemoved,
+    /// A path was removed and an identical path added under another name.
+    /// See `DiffOptions::detect_path_renames`.
+    PathRenamed,
+    /// The declaration order of the paths changed, which only matters for
+    /// documentation. See `DiffOptions::report_path_reordering`.
+    PathsReordered,
+    /// A template parameter of a path was renamed, such as from
+    /// `/users/{userId}` to `/users/{id}`. See
+    /// `DiffOptions::ignore_path_param_names`.
+    PathParameterRenamed,
+    OperationAdded,
+    OperationRemoved,
+    /// A tag was added to an operation.
+    TagAdded,
+    /// A tag was removed from an operation.
+    TagRemoved,
+    /// The first tag of an operation changed, moving it to another group.
+    PrimaryTagChanged,
+    OperationSummaryChanged,
+    OperationDescriptionChanged,
+    OperationIdChanged,
+    /// An operation became deprecated, directly or through its path item.
+    OperationDeprecated,
+    /// An operation is no longer deprecated.
+    OperationUndeprecated,
+    /// The security requirements of an operation changed in a way that
+    /// rejects some previously accepted credentials.
+    SecurityRequirementsTightened,
+    /// The security requirements of an operation changed while accepting
+    /// every previously accepted credential.
+    SecurityRequirementsChanged,
+    ParameterAdded {
+        required: bool,
+    },
+    ParameterRemoved,
+    /// A parameter moved to another location (`in`), e.g. from query to header.
+    ParameterLocationChanged,
+    /// A parameter switched between `schema` and `content`.
+    ParameterFormatChanged,
+    /// An optional parameter became required.
+    ParameterBecameRequired,
+    /// A required parameter became optional.
+    ParameterBecameOptional,
+    ParameterDeprecated,
+    ParameterUndeprecated,
+    /// A request body was added to an operation.
+    RequestBodyAdded {
+        required: bool,
+    },
+    RequestBodyRemoved,
+    RequestBodyBecameRequired,
+    RequestBodyBecameOptional,
+    /// The component a request body refers to changed, or the body was
+    /// inlined or moved to a component.
+    RequestBodyReferenceChanged,
+    MediaTypeAdded,
+    MediaTypeRemoved,
+    /// An `encoding` entry was added for a part of a multipart or form body.
+    EncodingAdded,
+    EncodingRemoved,
+    /// The `contentType` of an encoding entry was added, removed or changed.
+    EncodingContentTypeChanged,
+    /// A header was added to a part of a multipart body.
+    EncodingHeaderAdded {
+        required: bool,
+    },
+    EncodingHeaderRemoved,
+    /// An optional header of a multipart part became required.
+    EncodingHeaderBecameRequired,
+    /// A required header of a multipart part became optional.
+    EncodingHeaderBecameOptional,
+    EncodingHeaderReferenceChanged,
+    /// A schema was added where there was none, e.g. on a media type.
+    SchemaAdded,
+    /// A schema was removed, e.g. from a media type.
+    SchemaRemoved,
+    SchemaTypeChanged,
+    /// A type was given to a schema that accepted any value.
+    SchemaTypeAdded,
+    /// The type of a schema was removed, so it accepts any value.
+    SchemaTypeRemoved,
+    /// The type of a schema moved between scalar, object and array.
+    SchemaTypeCategoryChanged,
+    /// A custom format of a schema changed.
+    SchemaFormatChanged,
+    /// The format of a schema moved from or to a well-known format such as
+    /// `date` or `uuid`, changing how clients parse its values.
+    SchemaWellKnownFormatChanged,
+    SchemaDescriptionChanged,
+    SchemaNullableChanged,
+    /// The `maxLength` of a schema decreased or was added, so existing
+    /// values may be truncated.
+    SchemaMaxLengthDecreased,
+    /// The `maxLength` of a schema increased or was removed.
+    SchemaMaxLengthIncreased,
+    /// A `multipleOf` was added to a schema, or changed so that some
+    /// previously valid numbers are rejected.
+    SchemaMultipleOfNarrowed,
+    /// The `multipleOf` of a schema was removed, or changed so that every
+    /// previously valid number is still accepted.
+    SchemaMultipleOfWidened,
+    /// A `not` constraint was added to a schema.
+    NotAdded,
+    /// The `not` constraint of a schema was removed.
+    NotRemoved,
+    /// The `not` subschema of a schema changed.
+    NotChanged,
+    /// An example was added to a schema.
+    SchemaExampleAdded,
+    /// An `if`, `then` or `else` conditional was added to a schema.
+    ConditionalAdded,
+    /// An `if`, `then` or `else` conditional was removed from a schema.
+    ConditionalRemoved,
+    /// An `if`, `then` or `else` conditional of a schema changed.
+    ConditionalChanged,
+    /// A `propertyNames` constraint was added to a schema.
+    PropertyNamesAdded,
+    /// The `propertyNames` constraint of a schema was removed.
+    PropertyNamesRemoved,
+    /// The `propertyNames` constraint of a schema changed.
+    PropertyNamesChanged,
+    /// The `pattern` of a schema was added, removed or changed.
+    SchemaPatternChanged,
+    /// A schema given by `$ref` or `$dynamicRef` now points somewhere else.
+    /// References are compared by name and not followed.
+    SchemaReferenceChanged,
+    /// The `$dynamicAnchor` of a schema was added, removed or changed,
+    /// retargeting the `$dynamicRef`s resolving to it.
+    SchemaDynamicAnchorChanged,
+    /// The `contentEncoding` of a string schema was added, removed or
+    /// changed, changing how clients decode its values.
+    SchemaContentEncodingChanged,
+    /// The `contentMediaType` of a string schema was added, removed or
+    /// changed.
+    SchemaContentMediaTypeChanged,
+    /// A property became required by `dependentRequired` when another
+    /// property is present.
+    DependentRequiredAdded,
+    /// A property is no longer required by `dependentRequired`.
+    DependentRequiredRemoved,
+    /// A schema was added to `dependentSchemas`.
+    DependentSchemaAdded,
+    /// A schema was removed from `dependentSchemas`.
+    DependentSchemaRemoved,
+    /// An example was removed from a schema.
+    SchemaExampleRemoved,
+    /// An `enum` constraint was added to a schema.
+    EnumAdded,
+    /// The `enum` constraint of a schema was removed.
+    EnumRemoved,
+    EnumValueAdded,
+    EnumValueRemoved,
+    /// A property was added to the `required` list of a schema.
+    RequiredPropertyAdded,
+    /// A property was removed from the `required` list of a schema.
+    RequiredPropertyRemoved,
+    /// A required property lost `readOnly`, so clients may now have to send
+    /// it in requests.
+    RequiredPropertyNoLongerReadOnly,
+    PropertyAdded,
+    PropertyRemoved,
+    ResponseAdded,
+    ResponseRemoved,
+    ResponseDescriptionChanged,
+    ResponseReferenceChanged,
+    CallbackAdded,
+    CallbackRemoved,
+    ExtensionAdded,
+    ExtensionRemoved,
+    ExtensionChanged,
+    /// The description gained its first `components` section.
+    ComponentsAdded,
+    /// The `components` section of the description was removed.
+    ComponentsRemoved,
+    /// A named example was added under `components/examples` or to a
+    /// media type.
+    ExampleAdded,
+    /// A named example was removed from `components/examples` or from a
+    /// media type.
+    ExampleRemoved,
+    /// An example under `components/examples`, or the example payload of a
+    /// media type, was changed.
+    ExampleChanged,
+}
+
+/// The name of the kind, such as `ParameterAdded`, see `ChangeKind::name`.
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl ChangeKind {
+    /// The name of the variant, such as `ParameterAdded`, without the
+    /// details some kinds carry.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChangeKind::OpenApiVersionChanged => "OpenApiVersionChanged",
+            ChangeKind::InfoTitleChanged => "InfoTitleChanged",
+            ChangeKind::InfoDescriptionChanged => "InfoDescriptionChanged",
+            ChangeKind::InfoTermsOfServiceChanged => "InfoTermsOfServiceChanged",
+            ChangeKind::InfoVersionChanged => "InfoVersionChanged",
+            ChangeKind::ContactAdded => "ContactAdded",
+            ChangeKind::ContactRemoved => "ContactRemoved",
+            ChangeKind::ContactChanged => "ContactChanged",
+            ChangeKind::LicenseAdded => "LicenseAdded",
+            ChangeKind::LicenseRemoved => "LicenseRemoved",
+            ChangeKind::LicenseChanged => "LicenseChanged",
+            ChangeKind::ServerAdded => "ServerAdded",
+            ChangeKind::ServerRemoved => "ServerRemoved",
+            ChangeKind::ServerDescriptionChanged => "ServerDescriptionChanged",
+            ChangeKind::PathAdded => "PathAdded",
+            ChangeKind::PathRemoved => "PathRemoved",
+            ChangeKind::PathRenamed => "PathRenamed",
+            ChangeKind::PathsReordered => "PathsReordered",
+            ChangeKind::PathParameterRenamed => "PathParameterRenamed",
+            ChangeKind::OperationAdded => "OperationAdded",
+            ChangeKind::OperationRemoved => "OperationRemoved",
+            ChangeKind::TagAdded => "TagAdded",
+            ChangeKind::TagRemoved => "TagRemoved",
+            ChangeKind::PrimaryTagChanged => "PrimaryTagChanged",
+            ChangeKind::OperationSummaryChanged => "OperationSummaryChanged",
+            ChangeKind::OperationDescriptionChanged => "OperationDescriptionChanged",
+            ChangeKind::OperationIdChanged => "OperationIdChanged",
+            ChangeKind::OperationDeprecated => "OperationDeprecated",
+            ChangeKind::OperationUndeprecated => "OperationUndeprecated",
+            ChangeKind::SecurityRequirementsTightened => "SecurityRequirementsTightened",
+            ChangeKind::SecurityRequirementsChanged => "SecurityRequirementsChanged",
+            ChangeKind::ParameterAdded { .. } => "ParameterAdded",
+            ChangeKind::ParameterRemoved => "ParameterRemoved",
+            ChangeKind::ParameterLocationChanged => "ParameterLocationChanged",
+            ChangeKind::ParameterFormatChanged => "ParameterFormatChanged",
+            ChangeKind::ParameterBecameRequired => "ParameterBecameRequired",
+            ChangeKind::ParameterBecameOptional => "ParameterBecameOptional",
+            ChangeKind::ParameterDeprecated => "ParameterDeprecated",
+            ChangeKind::ParameterUndeprecated => "ParameterUndeprecated",
+            ChangeKind::RequestBodyAdded { .. } => "RequestBodyAdded",
+            ChangeKind::RequestBodyRemoved => "RequestBodyRemoved",
+            ChangeKind::RequestBodyBecameRequired => "RequestBodyBecameRequired",
+            ChangeKind::RequestBodyBecameOptional => "RequestBodyBecameOptional",
+            ChangeKind::RequestBodyReferenceChanged => "RequestBodyReferenceChanged",
+            ChangeKind::MediaTypeAdded => "MediaTypeAdded",
+            ChangeKind::MediaTypeRemoved => "MediaTypeRemoved",
+            ChangeKind::EncodingAdded => "EncodingAdded",
+            ChangeKind::EncodingRemoved => "EncodingRemoved",
+            ChangeKind::EncodingContentTypeChanged => "EncodingContentTypeChanged",
+            ChangeKind::EncodingHeaderAdded { .. } => "EncodingHeaderAdded",
+            ChangeKind::EncodingHeaderRemoved => "EncodingHeaderRemoved",
+            ChangeKind::EncodingHeaderBecameRequired => "EncodingHeaderBecameRequired",
+            ChangeKind::EncodingHeaderBecameOptional => "EncodingHeaderBecameOptional",
+            ChangeKind::EncodingHeaderReferenceChanged => "EncodingHeaderReferenceChanged",
+            ChangeKind::SchemaAdded => "SchemaAdded",
+            ChangeKind::SchemaRemoved => "SchemaRemoved",
+            ChangeKind::SchemaTypeChanged => "SchemaTypeChanged",
+            ChangeKind::SchemaTypeAdded => "SchemaTypeAdded",
+            ChangeKind::SchemaTypeRemoved => "SchemaTypeRemoved",
+            ChangeKind::SchemaTypeCategoryChanged => "SchemaTypeCategoryChanged",
+            ChangeKind::SchemaFormatChanged => "SchemaFormatChanged",
+            ChangeKind::SchemaWellKnownFormatChanged => "SchemaWellKnownFormatChanged",
+            ChangeKind::SchemaDescriptionChanged => "SchemaDescriptionChanged",
+            ChangeKind::SchemaNullableChanged => "SchemaNullableChanged",
+            ChangeKind::SchemaMaxLengthDecreased => "SchemaMaxLengthDecreased",
+            ChangeKind::SchemaMaxLengthIncreased => "SchemaMaxLengthIncreased",
+            ChangeKind::SchemaMultipleOfNarrowed => "SchemaMultipleOfNarrowed",
+            ChangeKind::SchemaMultipleOfWidened => "SchemaMultipleOfWidened",
+            ChangeKind::NotAdded => "NotAdded",
+            ChangeKind::NotRemoved => "NotRemoved",
+            ChangeKind::NotChanged => "NotChanged",
+            ChangeKind::SchemaExampleAdded => "SchemaExampleAdded",
+            ChangeKind::ConditionalAdded => "ConditionalAdded",
+            ChangeKind::ConditionalRemoved => "ConditionalRemoved",
+            ChangeKind::ConditionalChanged => "ConditionalChanged",
+            ChangeKind::PropertyNamesAdded => "PropertyNamesAdded",
+            ChangeKind::PropertyNamesRemoved => "PropertyNamesRemoved",
+            ChangeKind::PropertyNamesChanged => "PropertyNamesChanged",
+            ChangeKind::SchemaPatternChanged => "SchemaPatternChanged",
+            ChangeKind::SchemaReferenceChanged => "SchemaReferenceChanged",
+            ChangeKind::SchemaDynamicAnchorChanged => "SchemaDynamicAnchorChanged",
+            ChangeKind::SchemaContentEncodingChanged => "SchemaContentEncodingChanged",
+            ChangeKind::SchemaContentMediaTypeChanged => "SchemaContentMediaTypeChanged",
+            ChangeKind::DependentRequiredAdded => "DependentRequiredAdded",
+            ChangeKind::DependentRequiredRemoved => "DependentRequiredRemoved",
+            ChangeKind::DependentSchemaAdded => "DependentSchemaAdded",
+            ChangeKind::DependentSchemaRemoved => "DependentSchemaRemoved",
+            ChangeKind::SchemaExampleRemoved => "SchemaExampleRemoved",
+            ChangeKind::EnumAdded => "EnumAdded",
+            ChangeKind::EnumRemoved => "EnumRemoved",
+            ChangeKind::EnumValueAdded => "EnumValueAdded",
+            ChangeKind::EnumValueRemoved => "EnumValueRemoved",
+            ChangeKind::RequiredPropertyAdded => "RequiredPropertyAdded",
+            ChangeKind::RequiredPropertyRemoved => "RequiredPropertyRemoved",
+            ChangeKind::RequiredPropertyNoLongerReadOnly => "RequiredPropertyNoLongerReadOnly",
+            ChangeKind::PropertyAdded => "PropertyAdded",
+            ChangeKind::PropertyRemoved => "PropertyRemoved",
+            ChangeKind::ResponseAdded => "ResponseAdded",
+            ChangeKind::ResponseRemoved => "ResponseRemoved",
+            ChangeKind::ResponseDescriptionChanged => "ResponseDescriptionChanged",
+            ChangeKind::ResponseReferenceChanged => "ResponseReferenceChanged",
+            ChangeKind::CallbackAdded => "CallbackAdded",
+            ChangeKind::CallbackRemoved => "CallbackRemoved",
+            ChangeKind::ExtensionAdded => "ExtensionAdded",
+            ChangeKind::ExtensionRemoved => "ExtensionRemoved",
+            ChangeKind::ExtensionChanged => "ExtensionChanged",
+            ChangeKind::ComponentsAdded => "ComponentsAdded",
+            ChangeKind::ComponentsRemoved => "ComponentsRemoved",
+            ChangeKind::ExampleAdded => "ExampleAdded",
+            ChangeKind::ExampleRemoved => "ExampleRemoved",
+            ChangeKind::ExampleChanged => "ExampleChanged",
+        }
+    }
+
+    /// A stable, machine readable code for this kind of change.
+    pub fn reason_code(&self) -> &'static str {
+        match self {
+            ChangeKind::OpenApiVersionChanged => "openapi_version_changed",
+            ChangeKind::InfoTitleChanged => "info_title_changed",
+            ChangeKind::InfoDescriptionChanged => "info_description_changed",
+            ChangeKind::InfoTermsOfServiceChanged => "info_terms_of_service_changed",
+            ChangeKind::InfoVersionChanged => "info_version_changed",
+            ChangeKind::ContactAdded => "contact_added",
+            ChangeKind::ContactRemoved => "contact_removed",
+            ChangeKind::ContactChanged => "contact_changed",
+            ChangeKind::LicenseAdded => "license_added",
+            ChangeKind::LicenseRemoved => "license_removed",
+            ChangeKind::LicenseChanged => "license_changed",
+            ChangeKind::ServerAdded => "server_added",
+            ChangeKind::ServerRemoved => "server_removed",
+            ChangeKind::ServerDescriptionChanged => "server_description_changed",
+            ChangeKind::PathAdded => "path_added",
+            ChangeKind::PathRemoved => "path_removed",
+            ChangeKind::PathRenamed => "path_renamed",
+            ChangeKind::PathsReordered => "paths_reordered",
+            ChangeKind::PathParameterRenamed => "path_parameter_renamed",
+            ChangeKind::OperationAdded => "operation_added",
+            ChangeKind::OperationRemoved => "operation_removed",
+            ChangeKind::TagAdded => "tag_added",
+            ChangeKind::TagRemoved => "tag_removed",
+            ChangeKind::PrimaryTagChanged => "primary_tag_changed",
+            ChangeKind::OperationSummaryChanged => "operation_summary_changed",
+            ChangeKind::OperationDescriptionChanged => "operation_description_changed",
+            ChangeKind::OperationIdChanged => "operation_id_changed",
+            ChangeKind::OperationDeprecated => "operation_deprecated",
+            ChangeKind::OperationUndeprecated => "operation_undeprecated",
+            ChangeKind::SecurityRequirementsTightened => "security_requirements_tightened",
+            ChangeKind::SecurityRequirementsChanged => "security_requirements_changed",
+            ChangeKind::ParameterAdded { required: true } => "required_parameter_added",
+            ChangeKind::ParameterAdded { required: false } => "optional_parameter_added",
+            ChangeKind::ParameterRemoved => "parameter_removed",
+            ChangeKind::ParameterLocationChanged => "parameter_location_changed",
+            ChangeKind::ParameterFormatChanged => "parameter_format_changed",
+            ChangeKind::ParameterBecameRequired => "parameter_became_required",
+            ChangeKind::ParameterBecameOptional => "parameter_became_optional",
+            ChangeKind::ParameterDeprecated => "parameter_deprecated",
+            ChangeKind::ParameterUndeprecated => "parameter_undeprecated",
+            ChangeKind::RequestBodyAdded { required: true } => "required_request_body_added",
+            ChangeKind::RequestBodyAdded { required: false } => "optional_request_body_added",
+            ChangeKind::RequestBodyRemoved => "request_body_removed",
+            ChangeKind::RequestBodyBecameRequired => "request_body_became_required",
+            ChangeKind::RequestBodyBecameOptional => "request_body_became_optional",
+            ChangeKind::RequestBodyReferenceChanged => "request_body_reference_changed",
+            ChangeKind::MediaTypeAdded => "media_type_added",
+            ChangeKind::MediaTypeRemoved => "media_type_removed",
+            ChangeKind::EncodingAdded => "encoding_added",
+            ChangeKind::EncodingRemoved => "encoding_removed",
+            ChangeKind::EncodingContentTypeChanged => "encoding_content_type_changed",
+            ChangeKind::EncodingHeaderAdded { required: true } => "required_encoding_header_added",
+            ChangeKind::EncodingHeaderAdded { required: false } => "optional_encoding_header_added",
+            ChangeKind::EncodingHeaderRemoved => "encoding_header_removed",
+            ChangeKind::EncodingHeaderBecameRequired => "encoding_header_became_required",
+            ChangeKind::EncodingHeaderBecameOptional => "encoding_header_became_optional",
+            ChangeKind::EncodingHeaderReferenceChanged => "encoding_header_reference_changed",
+            ChangeKind::SchemaAdded => "schema_added",
+            ChangeKind::SchemaRemoved => "schema_removed",
+            ChangeKind::SchemaTypeChanged => "schema_type_changed",
+            ChangeKind::SchemaTypeAdded => "schema_type_added",
+            ChangeKind::SchemaTypeRemoved => "schema_type_removed",
+            ChangeKind::SchemaTypeCategoryChanged => "schema_type_category_changed",
+            ChangeKind::SchemaFormatChanged => "schema_format_changed",
+            ChangeKind::SchemaWellKnownFormatChanged => "schema_well_known_format_changed",
+            ChangeKind::SchemaDescriptionChanged => "schema_description_changed",
+            ChangeKind::SchemaNullableChanged => "schema_nullable_changed",
+            ChangeKind::SchemaMaxLengthDecreased => "schema_max_length_decreased",
+            ChangeKind::SchemaMaxLengthIncreased => "schema_max_length_increased",
+            ChangeKind::SchemaMultipleOfNarrowed => "schema_multiple_of_narrowed",
+            ChangeKind::SchemaMultipleOfWidened => "schema_multiple_of_widened",
+            ChangeKind::NotAdded => "not_added",
+            ChangeKind::NotRemoved => "not_removed",
+            ChangeKind::NotChanged => "not_changed",
+            ChangeKind::SchemaExampleAdded => "schema_example_added",
+            ChangeKind::ConditionalAdded => "conditional_added",
+            ChangeKind::ConditionalRemoved => "conditional_removed",
+            ChangeKind::ConditionalChanged => "conditional_changed",
+            ChangeKind::PropertyNamesAdded => "property_names_added",
+            ChangeKind::PropertyNamesRemoved => "property_names_removed",
+            ChangeKind::PropertyNamesChanged => "property_names_changed",
+            ChangeKind::SchemaPatternChanged => "schema_pattern_changed",
+            ChangeKind::SchemaReferenceChanged => "schema_reference_changed",
+            ChangeKind::SchemaDynamicAnchorChanged => "schema_dynamic_anchor_changed",
+            ChangeKind::SchemaContentEncodingChanged => "schema_content_encoding_changed",
+            ChangeKind::SchemaContentMediaTypeChanged => "schema_content_media_type_changed",
+            ChangeKind::DependentRequiredAdded => "dependent_required_added",
+            ChangeKind::DependentRequiredRemoved => "dependent_required_removed",
+            ChangeKind::DependentSchemaAdded => "dependent_schema_added",
+            ChangeKind::DependentSchemaRemoved => "dependent_schema_removed",
+            ChangeKind::SchemaExampleRemoved => "schema_example_removed",
+            ChangeKind::EnumAdded => "enum_added",
+            ChangeKind::EnumRemoved => "enum_removed",
+            ChangeKind::EnumValueAdded => "enum_value_added",
+            ChangeKind::EnumValueRemoved => "enum_value_removed",
+            ChangeKind::RequiredPropertyAdded => "required_property_added",
+            ChangeKind::RequiredPropertyNoLongerReadOnly => "required_property_no_longer_read_only",
+            ChangeKind::RequiredPropertyRemoved => "required_property_removed",
+            ChangeKind::PropertyAdded => "property_added",
+            ChangeKind::PropertyRemoved => "property_removed",
+            ChangeKind::ResponseAdded => "response_added",
+            ChangeKind::ResponseRemoved => "response_removed",
+            ChangeKind::ResponseDescriptionChanged => "response_description_changed",
+            ChangeKind::ResponseReferenceChanged => "response_reference_changed",
+            ChangeKind::CallbackAdded => "callback_added",
+            ChangeKind::CallbackRemoved => "callback_removed",
+            ChangeKind::ExtensionAdded => "extension_added",
+            ChangeKind::ExtensionRemoved => "extension_removed",
+            ChangeKind::ExtensionChanged => "extension_changed",
+            ChangeKind::ComponentsAdded => "components_added",
+            ChangeKind::ComponentsRemoved => "components_removed",
+            ChangeKind::ExampleAdded => "example_added",
+            ChangeKind::ExampleRemoved => "example_removed",
+            ChangeKind::ExampleChanged => "example_changed",
+        }
+    }
+
+    /// The Keep a Changelog section this kind of change is listed under:
+    ///
+    /// - `Added`: new API surface, such as paths, operations, parameters,
+    ///   properties, responses, media types, enum values, servers, tags,
+    ///   reusable schemas and examples.
+    /// - `Removed`: the removal of any of these.
+    /// - `Deprecated`: operations and parameters that became deprecated.
+    /// - `Changed`: every other change, including new or removed constraints
+    ///   on existing schemas, such as a property becoming required.
+    pub fn changelog_section(&self) -> ChangelogSection {
+        match self {
+            ChangeKind::ContactAdded
+            | ChangeKind::LicenseAdded
+            | ChangeKind::ServerAdded
+            | ChangeKind::PathAdded
+            | ChangeKind::OperationAdded
+            | ChangeKind::TagAdded
+            | ChangeKind::ParameterAdded { .. }
+            | ChangeKind::RequestBodyAdded { .. }
+            | ChangeKind::MediaTypeAdded
+            | ChangeKind::SchemaAdded
+            | ChangeKind::SchemaExampleAdded
+            | ChangeKind::EnumValueAdded
+            | ChangeKind::PropertyAdded
+            | ChangeKind::ResponseAdded
+            | ChangeKind::CallbackAdded
+            | ChangeKind::ExtensionAdded
+            | ChangeKind::ComponentsAdded
+            | ChangeKind::ExampleAdded => ChangelogSection::Added,
+            ChangeKind::ContactRemoved
+            | ChangeKind::LicenseRemoved
+            | ChangeKind::ServerRemoved
+            | ChangeKind::PathRemoved
+            | ChangeKind::OperationRemoved
+            | ChangeKind::TagRemoved
+            | ChangeKind::ParameterRemoved
+            | ChangeKind::RequestBodyRemoved
+            | ChangeKind::MediaTypeRemoved
+            | ChangeKind::SchemaRemoved
+            | ChangeKind::SchemaExampleRemoved
+            | ChangeKind::EnumValueRemoved
+            | ChangeKind::PropertyRemoved
+            | ChangeKind::ResponseRemoved
+            | ChangeKind::CallbackRemoved
+            | ChangeKind::ExtensionRemoved
+            | ChangeKind::ComponentsRemoved
+            | ChangeKind::ExampleRemoved => ChangelogSection::Removed,
+            ChangeKind::OperationDeprecated | ChangeKind::ParameterDeprecated => {
+                ChangelogSection::Deprecated
+            }
+            _ => ChangelogSection::Changed,
+        }
+    }
+
+    /// Whether this kind of change only affects documentation, such as
+    /// descriptions, summaries and examples.
+    pub fn is_cosmetic(&self) -> bool {
+        self.is_example()
+            || matches!(
+                self,
+                ChangeKind::InfoTitleChanged
+                    | ChangeKind::InfoDescriptionChanged
+                    | ChangeKind::InfoTermsOfServiceChanged
+                    | ChangeKind::ServerDescriptionChanged
+                    | ChangeKind::PathsReordered
+                    | ChangeKind::OperationSummaryChanged
+                    | ChangeKind::OperationDescriptionChanged
+                    | ChangeKind::SchemaDescriptionChanged
+                    | ChangeKind::ResponseDescriptionChanged
+            )
+    }
+
+    /// Whether this kind of change only affects examples, never what the
+    /// API accepts or returns.
+    pub fn is_example(&self) -> bool {
+        matches!(
+            self,
+            ChangeKind::ExampleAdded
+                | ChangeKind::ExampleRemoved
+                | ChangeKind::ExampleChanged
+                | ChangeKind::SchemaExampleAdded
+                | ChangeKind::SchemaExampleRemoved
+        )
+    }
+
+    /// Whether this kind of change degrades the documentation without
+    /// changing the API, such as a removed example. These are `Info`
+    /// changes, flagged so documentation owners can still catch them.
+    pub fn is_documentation_regression(&self) -> bool {
+        matches!(
+            self,
+            ChangeKind::ExampleRemoved | ChangeKind::SchemaExampleRemoved
+        )
+    }
+
+    /// Whether this kind of change can break existing clients of the API.
+    pub fn is_breaking(&self) -> bool {
+        matches!(
+            self,
+            ChangeKind::ServerRemoved
+                | ChangeKind::PathRemoved
+                | ChangeKind::PathRenamed
+                | ChangeKind::OperationRemoved
+                | ChangeKind::SecurityRequirementsTightened
+                | ChangeKind::ParameterAdded { required: true }
+                | ChangeKind::RequestBodyAdded { required: true }
+                | ChangeKind::ParameterLocationChanged
+                | ChangeKind::ParameterFormatChanged
+                | ChangeKind::ParameterBecameRequired
+                | ChangeKind::RequestBodyBecameRequired
+                | ChangeKind::MediaTypeRemoved
+                | ChangeKind::SchemaTypeChanged
+                | ChangeKind::SchemaTypeCategoryChanged
+                | ChangeKind::SchemaMaxLengthDecreased
+                | ChangeKind::SchemaWellKnownFormatChanged
+                | ChangeKind::SchemaContentEncodingChanged
+                | ChangeKind::SchemaContentMediaTypeChanged
+                | ChangeKind::EnumValueRemoved
+                | ChangeKind::RequiredPropertyAdded
+                | ChangeKind::PropertyRemoved
+                | ChangeKind::ResponseRemoved
+        )
+    }
+
+    /// Whether this kind of change lets a request or schema hold values it
+    /// could not hold before, such as a new enum value or a property that
+    /// is no longer required.
+    pub fn is_loosening(&self) -> bool {
+        matches!(
+            self,
+            ChangeKind::ParameterRemoved
+                | ChangeKind::ParameterBecameOptional
+                | ChangeKind::RequestBodyRemoved
+                | ChangeKind::RequestBodyBecameOptional
+                | ChangeKind::MediaTypeAdded
+                | ChangeKind::SchemaTypeRemoved
+                | ChangeKind::SchemaMaxLengthIncreased
+                | ChangeKind::SchemaMultipleOfWidened
+                | ChangeKind::NotRemoved
+                | ChangeKind::ConditionalRemoved
+                | ChangeKind::PropertyNamesRemoved
+                | ChangeKind::DependentRequiredRemoved
+                | ChangeKind::DependentSchemaRemoved
+                | ChangeKind::EnumRemoved
+                | ChangeKind::EnumValueAdded
+                | ChangeKind::RequiredPropertyRemoved
+        )
+    }
+
+    /// Whether this kind of change rejects requests or schema values that
+    /// were valid before, such as a removed enum value or a new required
+    /// property.
+    pub fn is_tightening(&self) -> bool {
+        matches!(
+            self,
+            ChangeKind::ParameterAdded { required: true }
+                | ChangeKind::ParameterBecameRequired
+                | ChangeKind::RequestBodyAdded { required: true }
+                | ChangeKind::RequestBodyBecameRequired
+                | ChangeKind::MediaTypeRemoved
+                | ChangeKind::SchemaMaxLengthDecreased
+                | ChangeKind::EnumValueRemoved
+                | ChangeKind::RequiredPropertyAdded
+        )
+    }
+}
+
+/// How a change was detected.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Confidence {
+    /// Read directly from the structure of both descriptions.
+    #[default]
+    Certain,
+    /// Inferred, such as a rename detected from similar definitions, and
+    /// worth a closer look from reviewers.
+    Heuristic,
+}
+
+impl std::fmt::Display for Confidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Confidence::Certain => write!(f, "certain"),
+            Confidence::Heuristic => write!(f, "heuristic"),
+        }
+    }
+}
+
+/// The part of an HTTP exchange a change is located in. Callbacks and
+/// webhooks swap the roles of client and server: the API sends their
+/// requests and receives their responses.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeContext {
+    /// The parameters or request body of an operation, sent by clients.
+    Request,
+    /// The responses of an operation, received by clients.
+    Response,
+    /// The parameters or request body of a callback, received by clients.
+    CallbackRequest,
+    /// The responses of a callback, sent by clients.
+    CallbackResponse,
+    /// The parameters or request body of a webhook, received by clients.
+    WebhookPayload,
+    /// The responses of a webhook, sent by clients.
+    WebhookResponse,
+}
+
+/// How much attention a change deserves, see `Change::severity`. Severities
+/// are ordered from `Info` to `Breaking`, e.g. to keep the changes of at
+/// least a given severity.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    Breaking,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Breaking => write!(f, "breaking"),
+        }
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "warning" => Ok(Severity::Warning),
+            "breaking" => Ok(Severity::Breaking),
+            _ => Err(format!(
+                "unknown severity `{}`, expected info, warning or breaking",
+                s
+            )),
+        }
+    }
+}
+
+/// A section of a changelog following [Keep a Changelog](https://keepachangelog.com).
+/// See `ChangeKind::changelog_section` for how changes map to sections.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ChangelogSection {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+}
+
+impl std::fmt::Display for ChangelogSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A single change between two descriptions, flattened out of the diff tree.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// JSON pointer to the changed location, in the head description for
+    /// additions and in the base description otherwise. Parameters are
+    /// addressed by name rather than by index.
+    pub pointer: String,
+    /// The path of the operation this change belongs to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// The lowercase HTTP method of the operation this change belongs to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// The dotted path of the schema property this change is nested in,
+    /// such as `data.items[].address.zip`, with `[]` standing for the items
+    /// of an array.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+    /// The part of an exchange this change is located in, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<ChangeContext>,
+    /// A human readable description of the change, in English. See
+    /// `Change::localized_message` for other languages.
+    pub message: String,
+    /// The English message with a `{}` placeholder for each argument.
+    #[serde(skip)]
+    pub template: String,
+    /// The values the message is built from, in order, such as
+    /// `["userId", "id"]` for "Path parameter `userId` was renamed to
+    /// `id`.".
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<String>,
+    pub confidence: Confidence,
+}
+
+impl Change {
+    /// How much attention the change deserves from reviewers:
+    ///
+    /// - `Breaking`: changes for which `is_breaking` holds.
+    /// - `Warning`: deprecations and additions to responses, such as a new
+    ///   status code, which clients may have to handle.
+    /// - `Info`: every other change, including cosmetic ones such as
+    ///   descriptions and examples.
+    pub fn severity(&self) -> Severity {
+        if self.is_breaking() {
+            Severity::Breaking
+        } else if self.kind.is_cosmetic() {
+            Severity::Info
+        } else if self.kind.changelog_section() == ChangelogSection::Deprecated
+            || (self.is_response() && self.kind.changelog_section() == ChangelogSection::Added)
+            || self.kind == ChangeKind::RequiredPropertyNoLongerReadOnly
+        {
+            Severity::Warning
+        } else {
+            Severity::Info
+        }
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        // Clients receiving a response, like receivers of the request of a
+        // webhook or callback, invert the rules for requests: loosening
+        // what they receive can break them, tightening it can't.
+        if self.is_response() {
+            return !self.kind.is_cosmetic()
+                && (self.kind.is_loosening()
+                    || (self.kind.is_breaking() && !self.kind.is_tightening()));
+        }
+
+        match self.kind {
+            // Adding or changing what a request schema rejects can turn
+            // previously valid requests into invalid ones. Removing it
+            // only accepts more.
+            ChangeKind::NotAdded | ChangeKind::NotChanged => self.is_request(),
+            // New conditional constraints reject requests that were valid.
+            ChangeKind::ConditionalAdded
+            | ChangeKind::ConditionalChanged
+            | ChangeKind::PropertyNamesAdded
+            | ChangeKind::PropertyNamesChanged
+            | ChangeKind::SchemaPatternChanged
+            | ChangeKind::SchemaMultipleOfNarrowed
+            | ChangeKind::DependentRequiredAdded
+            | ChangeKind::DependentSchemaAdded => self.is_request(),
+            // Typing an untyped schema rejects values requests could send.
+            ChangeKind::SchemaTypeAdded => self.is_request(),
+            // An untyped response may hold values consumers cannot handle.
+            ChangeKind::SchemaTypeRemoved => self.is_response(),
+            // Uploaders have to send the new required headers of a part.
+            ChangeKind::EncodingHeaderAdded { required: true }
+            | ChangeKind::EncodingHeaderBecameRequired => self.is_request(),
+            // Clients now have to send a required property they could omit
+            // while it was readOnly.
+            ChangeKind::RequiredPropertyNoLongerReadOnly => self.is_request(),
+            _ => self.kind.is_breaking(),
+        }
+    }
+
+    /// Whether the change is located in what the client receives: the
+    /// responses of an operation, or the request of a callback or webhook.
+    fn is_response(&self) -> bool {
+        matches!(
+            self.context,
+            Some(ChangeContext::Response)
+                | Some(ChangeContext::CallbackRequest)
+                | Some(ChangeContext::WebhookPayload)
+        )
+    }
+
+    /// Whether the change is located in what the client sends: the request
+    /// of an operation, or the responses of a callback or webhook.
+    fn is_request(&self) -> bool {
+        matches!(
+            self.context,
+            Some(ChangeContext::Request)
+                | Some(ChangeContext::CallbackResponse)
+                | Some(ChangeContext::WebhookResponse)
+        )
+    }
+}
+
+/// The message of a change while it is collected: an English template
+/// along with the values filling its placeholders.
+#[derive(Clone, Default)]
+struct Message {
+    template: String,
+    arguments: Vec<String>,
+}
+
+impl Message {
+    /// Fills the placeholders of `template` with `parts`, in order. The
+    /// template and arguments of each part are spliced in, so values
+    /// nested in parts such as "response `{}`" remain arguments.
+    fn new(template: &str, parts: Vec<Message>) -> Self {
+        let mut message = Self::default();
+        let mut parts = parts.into_iter();
+        let mut pieces = template.split("{}");
+
+        message.template.push_str(pieces.next().unwrap_or_default());
+        for piece in pieces {
+            if let Some(part) = parts.next() {
+                message.template.push_str(&part.template);
+                message.arguments.extend(part.arguments);
+            }
+            message.template.push_str(piece);
+        }
+
+        message
+    }
+}
+
+/// A value or a nested message filling a placeholder of a `Message`.
+trait Argument {
+    fn into_message(self) -> Message;
+}
+
+impl<T: std::fmt::Display> Argument for T {
+    fn into_message(self) -> Message {
+        Message {
+            template: String::from("{}"),
+            arguments: vec![self.to_string()],
+        }
+    }
+}
+
+impl Argument for &Message {
+    fn into_message(self) -> Message {
+        self.clone()
+    }
+}
+
+/// Builds a `Message` like `format!` builds a string, keeping the
+/// arguments apart from the template.
+macro_rules! message {
+    ($template:expr $(, $argument:expr)* $(,)?) => {
+        Message::new($template, vec![$(Argument::into_message(&$argument)),*])
+    };
+}
+
+/// Flattens a diff into the list of its changes, in a deterministic order.
+pub(crate) fn collect(diff: &Diff) -> Vec<Change> {
+    let mut collector = ChangeCollector::default();
+    let root = Location::default();
+
+    if let Some(version) = &diff.version {
+        collector.push(
+            ChangeKind::OpenApiVersionChanged,
+            &root.child("openapi"),
+            message!(
+                "OpenAPI version changed from `{}` to `{}`.",
+                version.from,
+                version.to
+            ),
+        );
+    }
+
+    if let Some(info) = &diff.info {
+        collector.info(info, &root.child("info"));
+    }
+
+    if let Some(servers) = &diff.servers {
+        collector.servers(servers, &root.child("servers"));
+    }
+
+    if let Some(security) = &diff.security {
+        collector.push(
+            if security.is_breaking() {
+                ChangeKind::SecurityRequirementsTightened
+            } else {
+                ChangeKind::SecurityRequirementsChanged
+            },
+            &root.child("security"),
+            message!(
+                "The description now requires {} (was {}).",
+                SecurityDiff::describe(&security.to),
+                SecurityDiff::describe(&security.from)
+            ),
+        );
+    }
+
+    if let Some(paths) = &diff.paths {
+        collector.paths(paths, &root.child("paths"));
+    }
+
+    if let Some(order) = &diff.paths_reordered {
+        collector.push(
+            ChangeKind::PathsReordered,
+            &root.child("paths"),
+            message!(
+                "The order of paths changed to {}.",
+                order
+                    .to
+                    .iter()
+                    .map(|path| format!("`{}`", path))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+    }
+
+    if let Some(webhooks) = &diff.webhooks {
+        let location = Location {
+            operations: Operations::Webhooks,
+            ..root.child("webhooks")
+        };
+        collector.paths(webhooks, &location);
+    }
+
+    if diff.components_introduced {
+        collector.push(
+            ChangeKind::ComponentsAdded,
+            &root.child("components"),
+            message!("The `components` section was added."),
+        );
+    }
+
+    if diff.components_removed {
+        collector.push(
+            ChangeKind::ComponentsRemoved,
+            &root.child("components"),
+            message!("The `components` section was removed."),
+        );
+    }
+
+    if let Some(components) = &diff.components {
+        collector.components(components, &root.child("components"));
+    }
+
+    collector.changes
+}
+
+/// Flattens the changes to the paths of a diff, for callers holding a
+/// `PathsDiff` rather than a whole `Diff`.
+pub(crate) fn collect_paths(diff: &PathsDiff) -> Vec<Change> {
+    let mut collector = ChangeCollector::default();
+    collector.paths(diff, &Location::default().child("paths"));
+    collector.changes
+}
+
+/// What the operations below a location are, which decides the context of
+/// their requests and responses.
+#[derive(Clone, Copy, Default)]
+enum Operations {
+    #[default]
+    Paths,
+    Callbacks,
+    Webhooks,
+}
+
+#[derive(Clone, Default)]
+struct Location {
+    pointer: String,
+    path: Option<String>,
+    method: Option<String>,
+    property: Option<String>,
+    operations: Operations,
+    context: Option<ChangeContext>,
+}
+
+impl Location {
+    fn child(&self, segment: &str) -> Self {
+        Self {
+            pointer: format!("{}/{}", self.pointer, escape_pointer(segment)),
+            ..self.clone()
+        }
+    }
+
+    /// The location of a schema property, extending the property path.
+    fn property(&self, name: &str) -> Self {
+        Self {
+            property: Some(self.property_name(name)),
+            ..self.child(name)
+        }
+    }
+
+    /// The location of the items of an array schema.
+    fn items(&self) -> Self {
+        Self {
+            property: self
+                .property
+                .as_ref()
+                .map(|property| format!("{}[]", property)),
+            ..self.child("items")
+        }
+    }
+
+    /// The location of the parameters or request body of the operation at
+    /// this location.
+    fn request(&self, segment: &str) -> Self {
+        Self {
+            context: Some(match self.operations {
+                Operations::Paths => ChangeContext::Request,
+                Operations::Callbacks => ChangeContext::CallbackRequest,
+                Operations::Webhooks => ChangeContext::WebhookPayload,
+            }),
+            ..self.child(segment)
+        }
+    }
+
+    /// The location of the responses of the operation at this location.
+    fn responses(&self) -> Self {
+        Self {
+            context: Some(match self.operations {
+                Operations::Paths => ChangeContext::Response,
+                Operations::Callbacks => ChangeContext::CallbackResponse,
+                Operations::Webhooks => ChangeContext::WebhookResponse,
+            }),
+            ..self.child("responses")
+        }
+    }
+
+    /// The full dotted path of a property of the schema at this location.
+    fn property_name(&self, name: &str) -> String {
+        match &self.property {
+            Some(property) => format!("{}.{}", property, name),
+            None => name.to_string(),
+        }
+    }
+
+    fn operation_name(&self) -> String {
+        format!(
+            "{} {}",
+            self.method.clone().unwrap_or_default().to_uppercase(),
+            self.path.clone().unwrap_or_default()
+        )
+    }
+}
+
+#[derive(Default)]
+struct ChangeCollector {
+    changes: Vec<Change>,
+}
+
+impl ChangeCollector {
+    fn push(&mut self, kind: ChangeKind, location: &Location, message: Message) {
+        self.push_with_confidence(kind, location, message, Confidence::Certain);
+    }
+
+    fn push_with_confidence(
+        &mut self,
+        kind: ChangeKind,
+        location: &Location,
+        message: Message,
+        confidence: Confidence,
+    ) {
+        let mut change = Change {
+            kind,
+            pointer: location.pointer.clone(),
+            path: location.path.clone(),
+            method: location.method.clone(),
+            property: location.property.clone(),
+            context: location.context,
+            message: String::new(),
+            template: message.template,
+            arguments: message.arguments,
+            confidence,
+        };
+        change.message = English.message(&change);
+        self.changes.push(change);
+    }
+
+    fn info(&mut self, diff: &InfoDiff, location: &Location) {
+        if let Some(title) = &diff.title {
+            self.push(
+                ChangeKind::InfoTitleChanged,
+                &location.child("title"),
+                message!("Title changed from `{}` to `{}`.", title.from, title.to),
+            );
+        }
+
+        if let Some(description) = &diff.description {
+            self.push(
+                ChangeKind::InfoDescriptionChanged,
+                &location.child("description"),
+                optional_string_message(message!("Description"), description),
+            );
+        }
+
+        if let Some(terms) = &diff.terms_of_service {
+            self.push(
+                ChangeKind::InfoTermsOfServiceChanged,
+                &location.child("termsOfService"),
+                optional_string_message(message!("Terms of service"), terms),
+            );
+        }
+
+        if let Some(version) = &diff.version {
+            self.push(
+                ChangeKind::InfoVersionChanged,
+                &location.child("version"),
+                message!(
+                    "Version changed from `{}` to `{}`.",
+                    version.from,
+                    version.to
+                ),
+            );
+        }
+
+        if let Some(contact) = &diff.contact {
+            let location = location.child("contact");
+
+            if contact.added.is_some() {
+                self.push(
+                    ChangeKind::ContactAdded,
+                    &location,
+                    message!("API contact info was added."),
+                );
+            } else if contact.removed.is_some() {
+                self.push(
+                    ChangeKind::ContactRemoved,
+                    &location,
+                    message!("API contact info was removed."),
+                );
+            } else if contact.name.is_some() || contact.url.is_some() || contact.email.is_some() {
+                self.push(
+                    ChangeKind::ContactChanged,
+                    &location,
+                    message!("API contact info has changed."),
+                );
+            }
+
+            if let Some(extensions) = &contact.extensions {
+                self.extensions(extensions, &location);
+            }
+        }
+
+        if let Some(license) = &diff.license {
+            let location = location.child("license");
+
+            if license.added.is_some() {
+                self.push(
+                    ChangeKind::LicenseAdded,
+                    &location,
+                    message!("API license info was added."),
+                );
+            } else if license.removed.is_some() {
+                self.push(
+                    ChangeKind::LicenseRemoved,
+                    &location,
+                    message!("API license info was removed."),
+                );
+            } else if license.name.is_some() || license.url.is_some() {
+                self.push(
+                    ChangeKind::LicenseChanged,
+                    &location,
+                    message!("API license info has changed."),
+                );
+            }
+
+            if let Some(extensions) = &license.extensions {
+                self.extensions(extensions, &location);
+            }
+        }
+
+        if let Some(extensions) = &diff.extensions {
+            self.extensions(extensions, location);
+        }
+    }
+
+    fn extensions(&mut self, diff: &ExtensionsDiff, location: &Location) {
+        for (key, _) in &diff.added {
+            self.push(
+                ChangeKind::ExtensionAdded,
+                &location.child(key),
+                message!("Extension `{}` was added.", key),
+            );
+        }
+
+        for (key, _) in &diff.removed {
+            self.push(
+                ChangeKind::ExtensionRemoved,
+                &location.child(key),
+                message!("Extension `{}` was removed.", key),
+            );
+        }
+
+        for key in sorted_keys(diff.changed.keys()) {
+            self.push(
+                ChangeKind::ExtensionChanged,
+                &location.child(key),
+                message!("Extension `{}` was changed.", key),
+            );
+        }
+    }
+
+    fn components(&mut self, diff: &ComponentsDiff, location: &Location) {
+        if let Some(examples) = &diff.examples {
+            self.examples(examples, &location.child("examples"));
+        }
+
+        if let Some(schemas) = &diff.schemas {
+            self.component_schemas(schemas, &location.child("schemas"));
+        }
+
+        if let Some(path_items) = &diff.path_items {
+            self.paths(path_items, &location.child("pathItems"));
+        }
+    }
+
+    fn examples(&mut self, examples: &ExamplesDiff, location: &Location) {
+        for (name, _) in &examples.added {
+            self.push(
+                ChangeKind::ExampleAdded,
+                &location.child(name),
+                message!("Example `{}` was added.", name),
+            );
+        }
+
+        for (name, _) in &examples.removed {
+            self.push(
+                ChangeKind::ExampleRemoved,
+                &location.child(name),
+                message!("Example `{}` was removed.", name),
+            );
+        }
+
+        for name in sorted_keys(examples.changed.keys()) {
+            self.push(
+                ChangeKind::ExampleChanged,
+                &location.child(name),
+                message!("Example `{}` was changed.", name),
+            );
+        }
+    }
+
+    /// Reports the schemas under `components/schemas`. They are not tied to
+    /// a request or a response, so their changes have no context.
+    fn component_schemas(&mut self, schemas: &SchemasDiff, location: &Location) {
+        for (name, _) in &schemas.added {
+            self.push(
+                ChangeKind::SchemaAdded,
+                &location.child(name),
+                message!("Schema `{}` was added.", name),
+            );
+        }
+
+        for (name, _) in &schemas.removed {
+            self.push(
+                ChangeKind::SchemaRemoved,
+                &location.child(name),
+                message!("Schema `{}` was removed.", name),
+            );
+        }
+
+        for name in sorted_keys(schemas.changed.keys()) {
+            self.schema(&schemas.changed[name], &location.child(name));
+        }
+    }
+
+    fn servers(&mut self, diff: &ServersDiff, location: &Location) {
+        let mut added: Vec<_> = diff.added.iter().map(|server| &server.url).collect();
+        added.sort();
+        for url in added {
+            self.push(
+                ChangeKind::ServerAdded,
+                &location.child(url),
+                message!("Server `{}` was added.", url),
+            );
+        }
+
+        let mut removed: Vec<_> = diff.removed.iter().map(|server| &server.url).collect();
+        removed.sort();
+        for url in removed {
+            self.push(
+                ChangeKind::ServerRemoved,
+                &location.child(url),
+                message!("Server `{}` was removed.", url),
+            );
+        }
+
+        for url in sorted_keys(diff.changed.keys()) {
+            let server_diff = &diff.changed[url];
+            let location = location.child(url);
+
+            if let Some(description) = &server_diff.description {
+                self.push(
+                    ChangeKind::ServerDescriptionChanged,
+                    &location.child("description"),
+                    optional_string_message(message!("Server `{}` description", url), description),
+                );
+            }
+
+            if let Some(extensions) = &server_diff.extensions {
+                self.extensions(extensions, &location);
+            }
+        }
+    }
+
+    fn paths(&mut self, diff: &PathsDiff, location: &Location) {
+        for (path, _) in &diff.added {
+            let location = Location {
+                path: Some(path.clone()),
+                ..location.child(path)
+            };
+            self.push(
+                ChangeKind::PathAdded,
+                &location,
+                message!("Path `{}` was added.", path),
+            );
+        }
+
+        for (path, _) in &diff.removed {
+            let location = Location {
+                path: Some(path.clone()),
+                ..location.child(path)
+            };
+            self.push(
+                ChangeKind::PathRemoved,
+                &location,
+                message!("Path `{}` was removed.", path),
+            );
+        }
+
+        for rename in &diff.renamed {
+            let location = Location {
+                path: Some(rename.from.clone()),
+                ..location.child(&rename.from)
+            };
+            self.push_with_confidence(
+                ChangeKind::PathRenamed,
+                &location,
+                message!("Path `{}` was renamed to `{}`.", rename.from, rename.to),
+                Confidence::Heuristic,
+            );
+
+            if let Some(path_item_diff) = &rename.changed {
+                self.path_item(path_item_diff, &location);
+            }
+        }
+
+        for path in sorted_keys(diff.changed.keys()) {
+            let path_location = Location {
+                path: Some(path.clone()),
+                ..location.child(path)
+            };
+            self.path_item(&diff.changed[path], &path_location);
+        }
+    }
+
+    fn path_item(&mut self, diff: &PathItemDiff, location: &Location) {
+        for renamed in &diff.path_parameters_renamed {
+            self.push(
+                ChangeKind::PathParameterRenamed,
+                location,
+                message!(
+                    "Path parameter `{}` was renamed to `{}`.",
+                    renamed.from,
+                    renamed.to
+                ),
+            );
+        }
+
+        if let Some(servers) = &diff.servers {
+            self.servers(servers, &location.child("servers"));
+        }
+
+        for (method, _) in &diff.operations_added {
+            let location = Location {
+                method: Some(method.clone()),
+                ..location.child(method)
+            };
+            self.push(
+                ChangeKind::OperationAdded,
+                &location,
+                message!("Operation `{}` was added.", location.operation_name()),
+            );
+        }
+
+        for (method, _) in &diff.operations_removed {
+            let location = Location {
+                method: Some(method.clone()),
+                ..location.child(method)
+            };
+            self.push(
+                ChangeKind::OperationRemoved,
+                &location,
+                message!("Operation `{}` was removed.", location.operation_name()),
+            );
+        }
+
+        for method in sorted_keys(diff.operations_changed.keys()) {
+            let location = Location {
+                method: Some(method.clone()),
+                ..location.child(method)
+            };
+            self.operation(&diff.operations_changed[method], &location);
+        }
+    }
+
+    fn operation(&mut self, diff: &OperationDiff, location: &Location) {
+        for tag in &diff.tags.added {
+            self.push(
+                ChangeKind::TagAdded,
+                &location.child("tags"),
+                message!("Tag `{}` was added.", tag),
+            );
+        }
+
+        for tag in &diff.tags.removed {
+            self.push(
+                ChangeKind::TagRemoved,
+                &location.child("tags"),
+                message!("Tag `{}` was removed.", tag),
+            );
+        }
+
+        if let Some(primary_tag) = &diff.primary_tag {
+            self.push(
+                ChangeKind::PrimaryTagChanged,
+                &location.child("tags"),
+                optional_string_message(message!("Primary tag"), primary_tag),
+            );
+        }
+
+        if let Some(summary) = &diff.summary {
+            self.push(
+                ChangeKind::OperationSummaryChanged,
+                &location.child("summary"),
+                optional_string_message(message!("Operation summary"), summary),
+            );
+        }
+
+        if let Some(description) = &diff.description {
+            self.push(
+                ChangeKind::OperationDescriptionChanged,
+                &location.child("description"),
+                optional_string_message(message!("Operation description"), description),
+            );
+        }
+
+        if let Some(operation_id) = &diff.operation_id {
+            self.push(
+                ChangeKind::OperationIdChanged,
+                &location.child("operationId"),
+                optional_string_message(message!("Operation id"), operation_id),
+            );
+        }
+
+        if let Some(deprecated) = &diff.deprecated {
+            if deprecated.to {
+                self.push(
+                    ChangeKind::OperationDeprecated,
+                    &location.child("deprecated"),
+                    message!("Operation `{}` was deprecated.", location.operation_name()),
+                );
+            } else {
+                self.push(
+                    ChangeKind::OperationUndeprecated,
+                    &location.child("deprecated"),
+                    message!(
+                        "Operation `{}` is no longer deprecated.",
+                        location.operation_name()
+                    ),
+                );
+            }
+        }
+
+        if let Some(security) = &diff.security {
+            self.push(
+                if security.is_breaking() {
+                    ChangeKind::SecurityRequirementsTightened
+                } else {
+                    ChangeKind::SecurityRequirementsChanged
+                },
+                &location.child("security"),
+                message!(
+                    "Operation `{}` now requires {} (was {}).",
+                    location.operation_name(),
+                    SecurityDiff::describe(&security.to),
+                    SecurityDiff::describe(&security.from)
+                ),
+            );
+        }
+
+        self.parameters(&diff.parameters, &location.request("parameters"));
+
+        if let Some(request_body) = &diff.request_body {
+            self.request_body(request_body, &location.request("requestBody"));
+        }
+
+        if let Some(responses) = &diff.responses {
+            self.responses(responses, &location.responses());
+        }
+
+        if let Some(callbacks) = &diff.callbacks {
+            let location = Location {
+                operations: Operations::Callbacks,
+                ..location.child("callbacks")
+            };
+            self.callbacks(callbacks, &location);
+        }
+    }
+
+    fn callbacks(&mut self, diff: &CallbacksDiff, location: &Location) {
+        for (name, _) in &diff.added {
+            self.push(
+                ChangeKind::CallbackAdded,
+                &location.child(name),
+                message!("Callback `{}` was added.", name),
+            );
+        }
+
+        for (name, _) in &diff.removed {
+            self.push(
+                ChangeKind::CallbackRemoved,
+                &location.child(name),
+                message!("Callback `{}` was removed.", name),
+            );
+        }
+
+        for name in sorted_keys(diff.changed.keys()) {
+            self.paths(&diff.changed[name], &location.child(name));
+        }
+    }
+
+    fn parameters(&mut self, diff: &ParametersDiff, location: &Location) {
+        for parameter in &diff.added {
+            match parameter {
+                ReferenceOr::Reference { reference } => {
+                    // A reference to a component missing from head is
+                    // assumed to be required.
+                    let (required, confidence) = match diff.added_references_required.get(reference)
+                    {
+                        Some(required) => (*required, Confidence::Certain),
+                        None => (true, Confidence::Heuristic),
+                    };
+                    self.push_with_confidence(
+                        ChangeKind::ParameterAdded { required },
+                        &location.child(reference),
+                        if required {
+                            message!("Required referenced parameter `{}` was added.", reference)
+                        } else {
+                            message!("Referenced parameter `{}` was added.", reference)
+                        },
+                        confidence,
+                    );
+                }
+                ReferenceOr::Item(parameter) => {
+                    let name = ParametersDiff::param_name(parameter);
+                    let required = parameter_required(parameter);
+                    self.push(
+                        ChangeKind::ParameterAdded { required },
+                        &location.child(&name),
+                        if required {
+                            message!("Required parameter `{}` was added.", name)
+                        } else {
+                            message!("Parameter `{}` was added.", name)
+                        },
+                    );
+                }
+            }
+        }
+
+        for parameter in &diff.removed {
+            let name = match parameter {
+                ReferenceOr::Reference { reference } => reference.clone(),
+                ReferenceOr::Item(parameter) => ParametersDiff::param_name(parameter),
+            };
+            self.push(
+                ChangeKind::ParameterRemoved,
+                &location.child(&name),
+                message!("Parameter `{}` was removed.", name),
+            );
+        }
+
+        for name in sorted_keys(diff.changed.keys()) {
+            let parameter_diff = &diff.changed[name];
+            let location = location.child(name);
+
+            if let Some(in_change) = &parameter_diff.in_change {
+                self.push(
+                    ChangeKind::ParameterLocationChanged,
+                    &location.child("in"),
+                    message!(
+                        "Parameter `{}` moved from {} to {}.",
+                        name,
+                        in_change.from,
+                        in_change.to
+                    ),
+                );
+            }
+
+            if let Some(required) = &parameter_diff.required {
+                if required.to {
+                    self.push(
+                        ChangeKind::ParameterBecameRequired,
+                        &location.child("required"),
+                        message!("Parameter `{}` became required.", name),
+                    );
+                } else {
+                    self.push(
+                        ChangeKind::ParameterBecameOptional,
+                        &location.child("required"),
+                        message!("Parameter `{}` became optional.", name),
+                    );
+                }
+            }
+
+            if let Some(deprecated) = &parameter_diff.deprecated {
+                if deprecated.to {
+                    self.push(
+                        ChangeKind::ParameterDeprecated,
+                        &location.child("deprecated"),
+                        message!("Parameter `{}` was deprecated.", name),
+                    );
+                } else {
+                    self.push(
+                        ChangeKind::ParameterUndeprecated,
+                        &location.child("deprecated"),
+                        message!("Parameter `{}` is no longer deprecated.", name),
+                    );
+                }
+            }
+
+            if let Some(format) = &parameter_diff.format_changed {
+                self.push(
+                    ChangeKind::ParameterFormatChanged,
+                    &location,
+                    message!(
+                        "Parameter `{}` is now described with `{}` instead of `{}`.",
+                        name,
+                        format.to,
+                        format.from
+                    ),
+                );
+            }
+
+            if let Some(schema) = &parameter_diff.schema {
+                self.schema(schema, &location.child("schema"));
+            }
+
+            if let Some(content) = &parameter_diff.content {
+                self.content(content, &location.child("content"));
+            }
+        }
+    }
+
+    fn request_body(&mut self, diff: &RequestBodyDiff, location: &Location) {
+        if let Some(added) = &diff.added {
+            let required = diff.added_required;
+            let message = if required {
+                message!("Required request body was added.")
+            } else {
+                message!("Optional request body was added.")
+            };
+            // The component of a referenced body is not looked up, so it is
+            // only assumed to be required.
+            let confidence = match added {
+                ReferenceOr::Reference { .. } => Confidence::Heuristic,
+                ReferenceOr::Item(_) => Confidence::Certain,
+            };
+            self.push_with_confidence(
+                ChangeKind::RequestBodyAdded { required },
+                location,
+                message,
+                confidence,
+            );
+        }
+
+        if diff.removed.is_some() {
+            self.push(
+                ChangeKind::RequestBodyRemoved,
+                location,
+                message!("Request body was removed."),
+            );
+        }
+
+        if let Some(reference) = &diff.reference {
+            self.push(
+                ChangeKind::RequestBodyReferenceChanged,
+                location,
+                optional_string_message(message!("Reference of request body"), reference),
+            );
+        }
+
+        if let Some(required) = &diff.required {
+            if required.to {
+                self.push(
+                    ChangeKind::RequestBodyBecameRequired,
+                    &location.child("required"),
+                    message!("Request body became required."),
+                );
+            } else {
+                self.push(
+                    ChangeKind::RequestBodyBecameOptional,
+                    &location.child("required"),
+                    message!("Request body became optional."),
+                );
+            }
+        }
+
+        if let Some(content) = &diff.content_changed {
+            self.content(content, &location.child("content"));
+        }
+    }
+
+    fn responses(&mut self, diff: &ResponsesDiff, location: &Location) {
+        for (status_code, _) in &diff.added {
+            self.push(
+                ChangeKind::ResponseAdded,
+                &location.child(&status_code.to_string()),
+                message!("Response with status `{}` was added.", status_code),
+            );
+        }
+
+        for (status_code, _) in &diff.removed {
+            self.push(
+                ChangeKind::ResponseRemoved,
+                &location.child(&status_code.to_string()),
+                message!("Response with status `{}` was removed.", status_code),
+            );
+        }
+
+        for status_code in sorted_keys(diff.changed.keys()) {
+            self.response(
+                &diff.changed[status_code],
+                &location.child(&status_code.to_string()),
+                message!("response `{}`", status_code),
+            );
+        }
+
+        if diff.default_added.is_some() {
+            self.push(
+                ChangeKind::ResponseAdded,
+                &location.child("default"),
+                message!("Default response was added."),
+            );
+        }
+
+        if diff.default_removed.is_some() {
+            self.push(
+                ChangeKind::ResponseRemoved,
+                &location.child("default"),
+                message!("Default response was removed."),
+            );
+        }
+
+        if let Some(response_diff) = &diff.default_changed {
+            self.response(
+                response_diff,
+                &location.child("default"),
+                message!("default response"),
+            );
+        }
+    }
+
+    fn response(&mut self, diff: &ResponseDiff, location: &Location, name: Message) {
+        if let Some(reference) = &diff.reference {
+            self.push(
+                ChangeKind::ResponseReferenceChanged,
+                location,
+                optional_string_message(message!("Reference of {}", name), reference),
+            );
+        }
+
+        if let Some(description) = &diff.description {
+            self.push(
+                ChangeKind::ResponseDescriptionChanged,
+                &location.child("description"),
+                optional_string_message(message!("Description of {}", name), description),
+            );
+        }
+
+        if let Some(content) = &diff.content {
+            self.content(content, &location.child("content"));
+        }
+    }
+
+    fn content(&mut self, diff: &ContentDiff, location: &Location) {
+        for (media_type, _) in &diff.added {
+            self.push(
+                ChangeKind::MediaTypeAdded,
+                &location.child(media_type),
+                message!("Media type `{}` was added.", media_type),
+            );
+        }
+
+        for (media_type, _) in &diff.removed {
+            self.push(
+                ChangeKind::MediaTypeRemoved,
+                &location.child(media_type),
+                message!("Media type `{}` was removed.", media_type),
+            );
+        }
+
+        for media_type in sorted_keys(diff.changed.keys()) {
+            let media_type_diff = &diff.changed[media_type];
+            let media_type_location = location.child(media_type);
+            let location = media_type_location.child("schema");
+
+            if let Some(example) = &media_type_diff.example_changed {
+                let (kind, action) = match (&example.from, &example.to) {
+                    (None, _) => (ChangeKind::ExampleAdded, "added"),
+                    (_, None) => (ChangeKind::ExampleRemoved, "removed"),
+                    _ => (ChangeKind::ExampleChanged, "changed"),
+                };
+                self.push(
+                    kind,
+                    &media_type_location.child("example"),
+                    message!("Example of media type `{}` was {}.", media_type, action),
+                );
+            }
+
+            if let Some(examples) = &media_type_diff.examples_changed {
+                self.examples(examples, &media_type_location.child("examples"));
+            }
+
+            if let Some(encoding) = &media_type_diff.encoding_changed {
+                self.encoding(encoding, &media_type_location.child("encoding"));
+            }
+
+            if media_type_diff.schema_added.is_some() {
+                self.push(
+                    ChangeKind::SchemaAdded,
+                    &location,
+                    message!("Schema of media type `{}` was added.", media_type),
+                );
+            }
+
+            if media_type_diff.schema_removed.is_some() {
+                self.push(
+                    ChangeKind::SchemaRemoved,
+                    &location,
+                    message!("Schema of media type `{}` was removed.", media_type),
+                );
+            }
+
+            if let Some(schema) = &media_type_diff.schema_changed {
+                self.schema(schema, &location);
+            }
+        }
+    }
+
+    fn encoding(&mut self, diff: &EncodingsDiff, location: &Location) {
+        for part in &diff.added {
+            self.push(
+                ChangeKind::EncodingAdded,
+                &location.child(part),
+                message!("Encoding of part `{}` was added.", part),
+            );
+        }
+
+        for part in &diff.removed {
+            self.push(
+                ChangeKind::EncodingRemoved,
+                &location.child(part),
+                message!("Encoding of part `{}` was removed.", part),
+            );
+        }
+
+        for part in sorted_keys(diff.changed.keys()) {
+            let encoding_diff = &diff.changed[part];
+            let location = location.child(part);
+
+            if let Some(content_type) = &encoding_diff.content_type {
+                self.push(
+                    ChangeKind::EncodingContentTypeChanged,
+                    &location.child("contentType"),
+                    optional_string_message(
+                        message!("Content type of part `{}`", part),
+                        content_type,
+                    ),
+                );
+            }
+
+            if let Some(headers) = &encoding_diff.headers {
+                self.encoding_headers(headers, &location.child("headers"), part);
+            }
+        }
+    }
+
+    fn encoding_headers(&mut self, diff: &HeadersDiff, location: &Location, part: &str) {
+        for (name, header) in &diff.added {
+            let required = HeaderDiff::is_required(header);
+            self.push(
+                ChangeKind::EncodingHeaderAdded { required },
+                &location.child(name),
+                message!(
+                    "{} header `{}` was added to part `{}`.",
+                    if required { "Required" } else { "Optional" },
+                    name,
+                    part
+                ),
+            );
+        }
+
+        for (name, _) in &diff.removed {
+            self.push(
+                ChangeKind::EncodingHeaderRemoved,
+                &location.child(name),
+                message!("Header `{}` was removed from part `{}`.", name, part),
+            );
+        }
+
+        for name in sorted_keys(diff.changed.keys()) {
+            let header_diff = &diff.changed[name];
+            let location = location.child(name);
+
+            if let Some(reference) = &header_diff.reference {
+                self.push(
+                    ChangeKind::EncodingHeaderReferenceChanged,
+                    &location,
+                    optional_string_message(
+                        message!("Reference of header `{}` of part `{}`", name, part),
+                        reference,
+                    ),
+                );
+            }
+
+            if let Some(required) = &header_diff.required {
+                if required.to {
+                    self.push(
+                        ChangeKind::EncodingHeaderBecameRequired,
+                        &location.child("required"),
+                        message!("Header `{}` of part `{}` became required.", name, part),
+                    );
+                } else {
+                    self.push(
+                        ChangeKind::EncodingHeaderBecameOptional,
+                        &location.child("required"),
+                        message!("Header `{}` of part `{}` became optional.", name, part),
+                    );
+                }
+            }
+
+            if let Some(schema) = &header_diff.schema {
+                self.schema(schema, &location.child("schema"));
+            }
+        }
+    }
+
+    fn schema(&mut self, diff: &SchemaDiff, location: &Location) {
+        if let Some(reference) = &diff.reference_changed {
+            self.push(
+                ChangeKind::SchemaReferenceChanged,
+                location,
+                optional_string_message(message!("Schema reference"), reference),
+            );
+        }
+
+        if let Some(category_changed) = &diff.category_changed {
+            self.push(
+                ChangeKind::SchemaTypeCategoryChanged,
+                &location.child("type"),
+                message!(
+                    "Schema changed from {} `{}` to {} `{}`.",
+                    category_changed.from,
+                    optional_type(&category_changed.base.schema_type),
+                    category_changed.to,
+                    optional_type(&category_changed.head.schema_type)
+                ),
+            );
+        } else if let Some(type_added) = &diff.type_added {
+            self.push(
+                ChangeKind::SchemaTypeAdded,
+                &location.child("type"),
+                message!("Schema type `{}` was added.", type_added),
+            );
+        } else if let Some(type_removed) = &diff.type_removed {
+            self.push(
+                ChangeKind::SchemaTypeRemoved,
+                &location.child("type"),
+                message!(
+                    "Schema type `{}` was removed, the schema accepts any value.",
+                    type_removed
+                ),
+            );
+        } else if let Some(type_changed) = &diff.type_changed {
+            self.push(
+                ChangeKind::SchemaTypeChanged,
+                &location.child("type"),
+                message!(
+                    "Schema type changed from `{}` to `{}`.",
+                    type_changed.from,
+                    type_changed.to
+                ),
+            );
+        }
+
+        if let Some(format_changed) = &diff.format_changed {
+            self.push(
+                if diff.format_change_is_well_known() {
+                    ChangeKind::SchemaWellKnownFormatChanged
+                } else {
+                    ChangeKind::SchemaFormatChanged
+                },
+                &location.child("format"),
+                message!(
+                    "Schema format changed from `{}` to `{}`.",
+                    format_changed.from.as_deref().unwrap_or("null"),
+                    format_changed.to.as_deref().unwrap_or("null")
+                ),
+            );
+        }
+
+        if let Some(pattern) = &diff.pattern_changed {
+            self.push(
+                ChangeKind::SchemaPatternChanged,
+                &location.child("pattern"),
+                optional_string_message(message!("Schema pattern"), pattern),
+            );
+        }
+
+        if let Some(dynamic_ref) = &diff.dynamic_ref_changed {
+            self.push(
+                ChangeKind::SchemaReferenceChanged,
+                &location.child("$dynamicRef"),
+                optional_string_message(message!("Schema `$dynamicRef`"), dynamic_ref),
+            );
+        }
+
+        if let Some(dynamic_anchor) = &diff.dynamic_anchor_changed {
+            self.push(
+                ChangeKind::SchemaDynamicAnchorChanged,
+                &location.child("$dynamicAnchor"),
+                optional_string_message(message!("Schema `$dynamicAnchor`"), dynamic_anchor),
+            );
+        }
+
+        if let Some(encoding) = &diff.content_encoding_changed {
+            self.push(
+                ChangeKind::SchemaContentEncodingChanged,
+                &location.child("contentEncoding"),
+                optional_string_message(message!("Schema content encoding"), encoding),
+            );
+        }
+
+        if let Some(media_type) = &diff.content_media_type_changed {
+            self.push(
+                ChangeKind::SchemaContentMediaTypeChanged,
+                &location.child("contentMediaType"),
+                optional_string_message(message!("Schema content media type"), media_type),
+            );
+        }
+
+        if let Some(description) = &diff.description_changed {
+            self.push(
+                ChangeKind::SchemaDescriptionChanged,
+                &location.child("description"),
+                optional_string_message(message!("Schema description"), description),
+            );
+        }
+
+        if let Some(nullable) = &diff.nullable_changed {
+            self.push(
+                ChangeKind::SchemaNullableChanged,
+                &location.child("nullable"),
+                if nullable.to {
+                    message!("Schema became nullable.")
+                } else {
+                    message!("Schema is no longer nullable.")
+                },
+            );
+        }
+
+        if let Some(max_length) = &diff.max_length_changed {
+            let describe = |max_length: Option<u32>| match max_length {
+                Some(max_length) => max_length.to_string(),
+                None => String::from("none"),
+            };
+
+            if max_length.truncation_risk() {
+                self.push(
+                    ChangeKind::SchemaMaxLengthDecreased,
+                    &location.child("maxLength"),
+                    message!(
+                        "Schema maxLength decreased from `{}` to `{}`: potential data truncation.",
+                        describe(max_length.from),
+                        describe(max_length.to)
+                    ),
+                );
+            } else {
+                self.push(
+                    ChangeKind::SchemaMaxLengthIncreased,
+                    &location.child("maxLength"),
+                    message!(
+                        "Schema maxLength increased from `{}` to `{}`.",
+                        describe(max_length.from),
+                        describe(max_length.to)
+                    ),
+                );
+            }
+        }
+
+        if let Some(multiple_of) = &diff.multiple_of_changed {
+            let describe = |multiple_of: Option<f64>| match multiple_of {
+                Some(multiple_of) => multiple_of.to_string(),
+                None => String::from("none"),
+            };
+
+            self.push(
+                if multiple_of.narrows() {
+                    ChangeKind::SchemaMultipleOfNarrowed
+                } else {
+                    ChangeKind::SchemaMultipleOfWidened
+                },
+                &location.child("multipleOf"),
+                message!(
+                    "Schema multipleOf changed from `{}` to `{}`.",
+                    describe(multiple_of.from),
+                    describe(multiple_of.to)
+                ),
+            );
+        }
+
+        if let Some(examples) = &diff.examples_changed {
+            for example in &examples.added {
+                self.push(
+                    ChangeKind::SchemaExampleAdded,
+                    &location.child("examples"),
+                    message!("Schema example `{}` was added.", example),
+                );
+            }
+
+            for example in &examples.removed {
+                self.push(
+                    ChangeKind::SchemaExampleRemoved,
+                    &location.child("examples"),
+                    message!("Schema example `{}` was removed.", example),
+                );
+            }
+        }
+
+        if diff.not_added.is_some() {
+            self.push(
+                ChangeKind::NotAdded,
+                &location.child("not"),
+                message!("A `not` constraint was added to the schema."),
+            );
+        }
+
+        if diff.not_removed.is_some() {
+            self.push(
+                ChangeKind::NotRemoved,
+                &location.child("not"),
+                message!("The `not` constraint of the schema was removed."),
+            );
+        }
+
+        // Changes inside `not` invert their usual meaning, so they are
+        // reported as a single change rather than walked.
+        if diff.not_changed.is_some() {
+            self.push(
+                ChangeKind::NotChanged,
+                &location.child("not"),
+                message!("The `not` constraint of the schema changed."),
+            );
+        }
+
+        let conditionals = [
+            ("if", &diff.if_changed),
+            ("then", &diff.then_changed),
+            ("else", &diff.else_changed),
+        ];
+
+        for (keyword, conditional) in conditionals {
+            if let Some(conditional) = conditional {
+                self.subschema(
+                    conditional,
+                    &location.child(keyword),
+                    message!("`{}` conditional", keyword),
+                    [
+                        ChangeKind::ConditionalAdded,
+                        ChangeKind::ConditionalRemoved,
+                        ChangeKind::ConditionalChanged,
+                    ],
+                );
+            }
+        }
+
+        if let Some(property_names) = &diff.property_names_changed {
+            self.subschema(
+                property_names,
+                &location.child("propertyNames"),
+                message!("`propertyNames` constraint"),
+                [
+                    ChangeKind::PropertyNamesAdded,
+                    ChangeKind::PropertyNamesRemoved,
+                    ChangeKind::PropertyNamesChanged,
+                ],
+            );
+        }
+
+        if let Some(dependencies) = &diff.dependent_required_changed {
+            let location = location.child("dependentRequired");
+
+            for property in sorted_keys(dependencies.changed.keys()) {
+                let list_diff = &dependencies.changed[property];
+
+                for dependent in &list_diff.added {
+                    self.push(
+                        ChangeKind::DependentRequiredAdded,
+                        &location.child(property),
+                        message!(
+                            "Property `{}` became required when `{}` is present.",
+                            dependent,
+                            property
+                        ),
+                    );
+                }
+
+                for dependent in &list_diff.removed {
+                    self.push(
+                        ChangeKind::DependentRequiredRemoved,
+                        &location.child(property),
+                        message!(
+                            "Property `{}` is no longer required when `{}` is present.",
+                            dependent,
+                            property
+                        ),
+                    );
+                }
+            }
+        }
+
+        if let Some(schemas) = &diff.dependent_schemas_changed {
+            let location = location.child("dependentSchemas");
+
+            for (property, _) in &schemas.added {
+                self.push(
+                    ChangeKind::DependentSchemaAdded,
+                    &location.child(property),
+                    message!("A dependent schema was added for `{}`.", property),
+                );
+            }
+
+            for (property, _) in &schemas.removed {
+                self.push(
+                    ChangeKind::DependentSchemaRemoved,
+                    &location.child(property),
+                    message!("The dependent schema for `{}` was removed.", property),
+                );
+            }
+
+            for property in sorted_keys(schemas.changed.keys()) {
+                self.schema(&schemas.changed[property], &location.child(property));
+            }
+        }
+
+        if let Some(enum_diff) = &diff.enum_changed {
+            let location = location.child("enum");
+
+            if enum_diff.added {
+                self.push(
+                    ChangeKind::EnumAdded,
+                    &location,
+                    message!("Schema enum was added."),
+                );
+            }
+
+            if enum_diff.removed {
+                self.push(
+                    ChangeKind::EnumRemoved,
+                    &location,
+                    message!("Schema enum was removed."),
+                );
+            }
+
+            for value in &enum_diff.values_added {
+                self.push(
+                    ChangeKind::EnumValueAdded,
+                    &location,
+                    message!(
+                        "Enum value `{}` was added.",
+                        value.as_deref().unwrap_or("null")
+                    ),
+                );
+            }
+
+            for value in &enum_diff.values_removed {
+                self.push(
+                    ChangeKind::EnumValueRemoved,
+                    &location,
+                    message!(
+                        "Enum value `{}` was removed.",
+                        value.as_deref().unwrap_or("null")
+                    ),
+                );
+            }
+        }
+
+        if let Some(required) = &diff.required_changed {
+            for property in &required.added {
+                self.push(
+                    ChangeKind::RequiredPropertyAdded,
+                    &location.child("required"),
+                    message!(
+                        "Property `{}` became required.",
+                        location.property_name(property)
+                    ),
+                );
+            }
+
+            for property in &required.removed {
+                self.push(
+                    ChangeKind::RequiredPropertyRemoved,
+                    &location.child("required"),
+                    message!(
+                        "Property `{}` is no longer required.",
+                        location.property_name(property)
+                    ),
+                );
+            }
+        }
+
+        if let Some(properties) = &diff.properties_changed {
+            let location = location.child("properties");
+
+            for (property, _) in &properties.added {
+                self.push(
+                    ChangeKind::PropertyAdded,
+                    &location.property(property),
+                    message!("Property `{}` was added.", location.property_name(property)),
+                );
+            }
+
+            for (property, _) in &properties.removed {
+                self.push(
+                    ChangeKind::PropertyRemoved,
+                    &location.property(property),
+                    message!(
+                        "Property `{}` was removed.",
+                        location.property_name(property)
+                    ),
+                );
+            }
+
+            for property in &properties.no_longer_read_only {
+                self.push(
+                    ChangeKind::RequiredPropertyNoLongerReadOnly,
+                    &location.property(property).child("readOnly"),
+                    message!(
+                        "Required property `{}` is no longer readOnly, clients may have to send it.",
+                        location.property_name(property)
+                    ),
+                );
+            }
+
+            for property in sorted_keys(properties.changed.keys()) {
+                self.schema(&properties.changed[property], &location.property(property));
+            }
+        }
+
+        if let Some(items) = &diff.items_changed {
+            self.schema(items, &location.items());
+        }
+    }
+
+    /// Reports the changes to a subschema with the given added, removed
+    /// and changed kinds, then walks the changes inside of it.
+    fn subschema(
+        &mut self,
+        diff: &SubschemaDiff,
+        location: &Location,
+        subject: Message,
+        [added, removed, changed]: [ChangeKind; 3],
+    ) {
+        if diff.removed.is_some() {
+            self.push(
+                removed,
+                location,
+                message!("The {} of the schema was removed.", subject),
+            );
+        }
+
+        if diff.added.is_some() {
+            self.push(
+                added,
+                location,
+                message!("A {} was added to the schema.", subject),
+            );
+        }
+
+        if let Some(schema) = &diff.changed {
+            self.push(
+                changed,
+                location,
+                message!("The {} of the schema changed.", subject),
+            );
+            self.schema(schema, location);
+        }
+    }
+}
+
+fn sorted_keys<'a, K: Ord + 'a>(keys: impl Iterator<Item = &'a K>) -> Vec<&'a K> {
+    let mut keys: Vec<_> = keys.collect();
+    keys.sort();
+    keys
+}
+
+fn parameter_required(parameter: &Parameter) -> bool {
+    super::parameter::ParameterDiff::parameter_data(parameter).required
+}
+
+/// The type of a schema as quoted in messages, `null` when untyped.
+fn optional_type(schema_type: &Option<SchemaType>) -> String {
+    schema_type
+        .as_ref()
+        .map_or_else(|| String::from("null"), ToString::to_string)
+}
+
+fn optional_string_message(subject: Message, diff: &OptionalStringDiff) -> Message {
+    match (&diff.from, &diff.to) {
+        (Some(from), Some(to)) => message!("{} changed from `{}` to `{}`.", subject, from, to),
+        (None, Some(to)) => message!("{} was added: `{}`.", subject, to),
+        (Some(_), None) => message!("{} was removed.", subject),
+        (None, None) => message!("{} changed.", subject),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::diff_files;
+    use std::path::PathBuf;
+
+    /// A change of the given kind to `GET /pets`, for the rules that only
+    /// depend on the kind and context of a change.
+    fn change(kind: ChangeKind, context: Option<ChangeContext>) -> Change {
+        Change {
+            kind,
+            pointer: String::from("/paths/~1pets/get"),
+            path: Some(String::from("/pets")),
+            method: Some(String::from("get")),
+            property: None,
+            context,
+            message: String::new(),
+            template: String::new(),
+            arguments: Vec::new(),
+            confidence: Confidence::Certain,
+        }
+    }
+
+    /// The first request and response changes between two versions of the
+    /// schema of both the request body and the response of `POST /pets`.
+    fn schema_changes(base: &str, head: &str) -> (Change, Change) {
+        let spec = |schema: &str| -> crate::openapi::OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.1.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: {0}
+      responses:
+        "200":
+          description: The pet.
+          content:
+            application/json:
+              schema: {0}
+"#,
+                schema
+            ))
+            .expect("Failed to parse description")
+        };
+
+        let diff = crate::diff::diff(spec(base), spec(head)).expect("Failed to diff");
+        let first = |context| {
+            diff.changes()
+                .find(|change| change.context == Some(context))
+                .expect("schema should have changed")
+        };
+
+        (
+            first(ChangeContext::Request),
+            first(ChangeContext::Response),
+        )
+    }
+    #[test]
+    fn pet_store_changes() {
+        let diff = diff_files(
+            PathBuf::from("fixtures/pet-store.yaml"),
+            PathBuf::from("fixtures/pet-store-changed.yaml"),
+        )
+        .expect("Failed to diff YAML");
+
+        let changes: Vec<_> = diff.changes().collect();
+
+        assert_eq!(ChangeKind::OpenApiVersionChanged, changes[0].kind);
+        assert_eq!("/openapi", changes[0].pointer);
+        assert_eq!(vec!["3.0.0", "3.1.0"], changes[0].arguments);
+        assert_eq!(changes, diff.changes().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn nested_message_arguments() {
+        let name = message!("response `{}`", 404);
+        let message = message!(
+            "Description of {} changed from `{}` to `{}`.",
+            name,
+            "a",
+            "b"
+        );
+
+        assert_eq!(
+            "Description of response `{}` changed from `{}` to `{}`.",
+            message.template
+        );
+        assert_eq!(vec!["404", "a", "b"], message.arguments);
+    }
+
+    #[test]
+    fn path_and_operation_locations() {
+        let diff = diff_files(
+            PathBuf::from("fixtures/pet-store.json"),
+            PathBuf::from("fixtures/pet-store-changed.json"),
+        )
+        .expect("Failed to diff JSON");
+
+        for change in diff.changes() {
+            assert!(change.pointer.starts_with('/'));
+            if change.method.is_some() {
+                assert!(change.path.is_some());
+            }
+            assert!(!change.kind.reason_code().is_empty());
+        }
+    }
+
+    #[test]
+    fn reason_codes() {
+        assert_eq!(
+            "required_parameter_added",
+            ChangeKind::ParameterAdded { required: true }.reason_code()
+        );
+        assert_eq!("path_removed", ChangeKind::PathRemoved.reason_code());
+    }
+
+    #[test]
+    fn not_changes_break_requests_only() {
+        let (request, response) =
+            schema_changes("{ type: string }", "{ type: string, not: { enum: [''] } }");
+        assert_eq!(ChangeKind::NotAdded, request.kind);
+        assert!(request.is_breaking());
+        assert!(!response.is_breaking());
+
+        // Requests only get looser, responses may hold values they couldn't.
+        let (request, response) =
+            schema_changes("{ type: string, not: { enum: [''] } }", "{ type: string }");
+        assert_eq!(ChangeKind::NotRemoved, request.kind);
+        assert!(!request.is_breaking());
+        assert!(response.is_breaking());
+    }
+
+    #[test]
+    fn context_ignores_property_names() {
+        let spec = |not: &str| -> crate::openapi::OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          description: The pets.
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  parameters:
+                    type: object
+                    {}
+"#,
+                not
+            ))
+            .expect("Failed to parse description")
+        };
+
+        let diff =
+            crate::diff::diff(spec(""), spec("not: { type: string }")).expect("Failed to diff");
+        let change = diff.changes().next().expect("schema should have changed");
+
+        assert_eq!(ChangeKind::NotAdded, change.kind);
+        assert!(change.pointer.contains("/properties/parameters/"));
+        assert_eq!(Some(ChangeContext::Response), change.context);
+        assert!(!change.is_breaking());
+    }
+
+    #[test]
+    fn component_schema_changes() {
+        let spec = |name_type: &str| -> crate::openapi::OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths: {{}}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: {}
+"#,
+                name_type
+            ))
+            .expect("Failed to parse description")
+        };
+
+        let diff = crate::diff::diff(spec("string"), spec("integer")).expect("Failed to diff");
+        let change = diff.changes().next().expect("schema should have changed");
+
+        assert_eq!(ChangeKind::SchemaTypeChanged, change.kind);
+        assert_eq!(
+            "/components/schemas/Pet/properties/name/type",
+            change.pointer
+        );
+        assert_eq!(Some("name"), change.property.as_deref());
+        assert_eq!(None, change.context);
+        assert!(change.is_breaking());
+    }
+
+    #[test]
+    fn response_rules() {
+        let spec = |required: &str, statuses: &str| -> crate::openapi::OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          description: The pet.
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [{}]
+                properties:
+                  id: {{ type: string }}
+                  status: {{ type: string, enum: [{}] }}
+"#,
+                required, statuses
+            ))
+            .expect("Failed to parse description")
+        };
+        let change = |base: crate::openapi::OpenAPI, head: crate::openapi::OpenAPI| {
+            let diff = crate::diff::diff(base, head).expect("Failed to diff");
+            let changes: Vec<_> = diff.changes().collect();
+            assert_eq!(1, changes.len());
+            changes[0].clone()
+        };
+
+        // Clients can rely on a property once it is required.
+        let required_added = change(spec("", "sold"), spec("id", "sold"));
+        assert_eq!(ChangeKind::RequiredPropertyAdded, required_added.kind);
+        assert!(!required_added.is_breaking());
+
+        // Clients can no longer rely on it once it is not.
+        let required_removed = change(spec("id", "sold"), spec("", "sold"));
+        assert_eq!(ChangeKind::RequiredPropertyRemoved, required_removed.kind);
+        assert!(required_removed.is_breaking());
+
+        // Clients still handle every value they may receive.
+        let enum_value_removed = change(spec("", "available, sold"), spec("", "sold"));
+        assert_eq!(ChangeKind::EnumValueRemoved, enum_value_removed.kind);
+        assert!(!enum_value_removed.is_breaking());
+    }
+
+    #[test]
+    fn loosening_payloads_breaks_receivers() {
+        let breaks =
+            |kind: ChangeKind, context: ChangeContext| change(kind, Some(context)).is_breaking();
+
+        assert!(breaks(
+            ChangeKind::EnumValueAdded,
+            ChangeContext::WebhookPayload
+        ));
+        assert!(breaks(
+            ChangeKind::RequiredPropertyRemoved,
+            ChangeContext::CallbackRequest
+        ));
+        assert!(breaks(
+            ChangeKind::PropertyRemoved,
+            ChangeContext::WebhookPayload
+        ));
+        assert!(!breaks(
+            ChangeKind::EnumValueRemoved,
+            ChangeContext::WebhookPayload
+        ));
+        assert!(!breaks(
+            ChangeKind::RequiredPropertyAdded,
+            ChangeContext::CallbackRequest
+        ));
+        assert!(!breaks(
+            ChangeKind::SchemaDescriptionChanged,
+            ChangeContext::WebhookPayload
+        ));
+        assert!(!breaks(ChangeKind::EnumValueAdded, ChangeContext::Request));
+    }
+
+    #[test]
+    fn type_removed_breaks_responses_only() {
+        let (request, response) = schema_changes("{ type: string }", "{}");
+        assert_eq!(ChangeKind::SchemaTypeRemoved, request.kind);
+        assert!(!request.is_breaking());
+        assert!(response.is_breaking());
+
+        let (request, response) = schema_changes("{}", "{ type: string }");
+        assert_eq!(ChangeKind::SchemaTypeAdded, request.kind);
+        assert!(request.is_breaking());
+        assert!(!response.is_breaking());
+    }
+
+    #[test]
+    fn encoding_headers_break_requests_only() {
+        let required = ChangeKind::EncodingHeaderBecameRequired;
+        assert!(change(required.clone(), Some(ChangeContext::Request)).is_breaking());
+        assert!(!change(required, Some(ChangeContext::Response)).is_breaking());
+
+        let optional = ChangeKind::EncodingHeaderBecameOptional;
+        assert!(!change(optional, Some(ChangeContext::Request)).is_breaking());
+    }
+
+    #[test]
+    fn required_property_no_longer_read_only() {
+        let spec = |read_only: bool| -> crate::openapi::OpenAPI {
+            let schema = format!(
+                r#"
+                type: object
+                required: [id]
+                properties:
+                  id:
+                    type: string
+                    readOnly: {}"#,
+                read_only
+            );
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:{0}
+      responses:
+        "200":
+          description: The pet.
+          content:
+            application/json:
+              schema:{0}
+"#,
+                schema
+            ))
+            .expect("Failed to parse description")
+        };
+
+        let diff = crate::diff::diff(spec(true), spec(false)).expect("Failed to diff");
+        let changes: Vec<_> = diff.changes().collect();
+
+        assert_eq!(2, changes.len());
+        for change in &changes {
+            assert_eq!(ChangeKind::RequiredPropertyNoLongerReadOnly, change.kind);
+            assert_eq!(Some("id"), change.property.as_deref());
+            assert_eq!(
+                "Required property `id` is no longer readOnly, clients may have to send it.",
+                change.message
+            );
+        }
+        let request = changes
+            .iter()
+            .find(|change| change.pointer.contains("/requestBody/"))
+            .expect("request body should have changed");
+        assert!(request.pointer.ends_with("/schema/properties/id/readOnly"));
+        assert_eq!(Severity::Breaking, request.severity());
+        let response = changes
+            .iter()
+            .find(|change| change.pointer.contains("/responses/"))
+            .expect("response should have changed");
+        assert_eq!(Severity::Warning, response.severity());
+    }
+
+    #[test]
+    fn example_removed() {
+        let spec = |example: &str| -> crate::openapi::OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          description: The pets.
+          content:
+            application/json:
+              schema:
+                type: string
+                {0}
+              {0}
+"#,
+                example
+            ))
+            .expect("Failed to parse description")
+        };
+
+        let diff = crate::diff::diff(spec("example: Rex"), spec("")).expect("Failed to diff");
+        let changes: Vec<_> = diff.changes().collect();
+
+        let kinds: Vec<_> = changes.iter().map(|change| &change.kind).collect();
+        assert_eq!(
+            vec![
+                &ChangeKind::ExampleRemoved,
+                &ChangeKind::SchemaExampleRemoved
+            ],
+            kinds
+        );
+        assert_eq!(
+            "/paths/~1pets/get/responses/200/content/application~1json/example",
+            changes[0].pointer
+        );
+        for change in &changes {
+            assert!(change.kind.is_documentation_regression());
+            assert_eq!(Severity::Info, change.severity());
+        }
+
+        let options = crate::diff::DiffOptions {
+            ignore_examples: true,
+            ..Default::default()
+        };
+        let diff = crate::diff::diff_with_options(spec("example: Rex"), spec(""), &options)
+            .expect("Failed to diff");
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn severity() {
+        let severity =
+            |kind: ChangeKind, context: Option<ChangeContext>| change(kind, context).severity();
+
+        assert_eq!(
+            Severity::Warning,
+            severity(ChangeKind::ResponseAdded, Some(ChangeContext::Response))
+        );
+        assert_eq!(
+            Severity::Info,
+            severity(ChangeKind::EnumValueAdded, Some(ChangeContext::Request))
+        );
+        assert_eq!(
+            Severity::Info,
+            severity(ChangeKind::OperationDescriptionChanged, None)
+        );
+        assert_eq!(
+            Severity::Warning,
+            severity(ChangeKind::OperationDeprecated, None)
+        );
+        assert_eq!(
+            Severity::Breaking,
+            severity(ChangeKind::OperationRemoved, None)
+        );
+        assert!(Severity::Breaking > Severity::Warning);
+        assert_eq!(Ok(Severity::Warning), "Warning".parse());
+    }
+
+    #[test]
+    fn multiple_of_narrowed_breaks_requests_only() {
+        let (request, response) = schema_changes(
+            "{ type: number, multipleOf: 2 }",
+            "{ type: number, multipleOf: 4 }",
+        );
+        assert_eq!(ChangeKind::SchemaMultipleOfNarrowed, request.kind);
+        assert!(request.is_breaking());
+        assert!(!response.is_breaking());
+    }
+
+    #[test]
+    fn dependent_required_breaks_requests_only() {
+        let (request, response) = schema_changes(
+            "{ type: object }",
+            "{ type: object, dependentRequired: { name: [id] } }",
+        );
+        assert_eq!(ChangeKind::DependentRequiredAdded, request.kind);
+        assert!(request.is_breaking());
+        assert!(!response.is_breaking());
+    }
+}
@@ -1,5 +1,7 @@
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::Hash;
 
 #[derive(Debug, Serialize)]
 pub struct StringListDiff {
@@ -8,7 +10,7 @@ pub struct StringListDiff {
 }
 
 impl StringListDiff {
-    pub fn from_lists(base: &Vec<String>, head: &Vec<String>) -> Self {
+    pub fn from_lists(base: &[String], head: &[String]) -> Self {
         let base_set: HashSet<_> = base.iter().collect();
         let added: Vec<_> = head
             .iter()
@@ -50,6 +52,28 @@ impl OptionalStringDiff {
     }
 }
 
+#[derive(Debug, Serialize)]
+pub struct OptionalValueDiff {
+    pub from: Option<serde_json::Value>,
+    pub to: Option<serde_json::Value>,
+}
+
+impl OptionalValueDiff {
+    pub fn from_values(
+        base: &Option<serde_json::Value>,
+        head: &Option<serde_json::Value>,
+    ) -> Option<Self> {
+        if base != head {
+            Some(Self {
+                from: base.clone(),
+                to: head.clone(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct StringDiff {
     pub from: String,
@@ -87,3 +111,46 @@ impl BooleanDiff {
         }
     }
 }
+
+/// Escapes a single JSON pointer reference token, as per RFC 6901.
+pub(crate) fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Checks that each entry of a sub-diff is either added, removed or
+/// changed, and that changed entries hold changes. See `Diff::validate_self`.
+pub(crate) fn validate_entries<K, V>(
+    subject: &str,
+    added: Vec<&K>,
+    removed: Vec<&K>,
+    changed: &HashMap<K, V>,
+    has_changes: impl Fn(&V) -> bool,
+) -> Result<(), String>
+where
+    K: Display + Eq + Hash + Ord,
+{
+    let added: HashSet<_> = added.into_iter().collect();
+
+    for key in removed {
+        if added.contains(key) {
+            return Err(format!("{} `{}` is both added and removed", subject, key));
+        }
+        if changed.contains_key(key) {
+            return Err(format!("{} `{}` is both removed and changed", subject, key));
+        }
+    }
+
+    let mut keys: Vec<_> = changed.keys().collect();
+    keys.sort();
+
+    for key in keys {
+        if added.contains(key) {
+            return Err(format!("{} `{}` is both added and changed", subject, key));
+        }
+        if !has_changes(&changed[key]) {
+            return Err(format!("{} `{}` is changed without changes", subject, key));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,338 @@
+use super::common::{OptionalStringDiff, OptionalValueDiff};
+use super::options::DiffOptions;
+use super::paths::PathsDiff;
+use super::schema::SchemaDiff;
+use super::DiffError;
+use crate::openapi::{Components, Example, ReferenceOr, Schema};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+pub type ExamplePair = (String, ReferenceOr<Example>);
+pub type SchemaPair = (String, ReferenceOr<Schema>);
+
+#[derive(Debug, Serialize, Default)]
+pub struct ComponentsDiff {
+    pub examples: Option<ExamplesDiff>,
+    /// Changes to the reusable schemas under `components/schemas`, matched
+    /// by name. This is where changes inside a schema referenced with
+    /// `$ref` are reported, since schema references are compared as
+    /// written.
+    pub schemas: Option<SchemasDiff>,
+    /// Changes to the reusable path items of OpenAPI 3.1 under
+    /// `components/pathItems`, matched by name.
+    pub path_items: Option<PathsDiff>,
+}
+
+impl ComponentsDiff {
+    pub fn has_changes(&self) -> bool {
+        self.examples.is_some() || self.schemas.is_some() || self.path_items.is_some()
+    }
+
+    /// Diffs two `components` sections on their own, such as the ones of a
+    /// shared models file published without any paths.
+    ///
+    /// ```
+    /// use anicca::diff::{ComponentsDiff, DiffOptions};
+    /// use anicca::openapi::Components;
+    ///
+    /// let base: Components = serde_yaml::from_str(
+    ///     "schemas:\n  Pet:\n    type: object\n    properties:\n      name:\n        type: string\n",
+    /// )?;
+    /// let head: Components = serde_yaml::from_str(
+    ///     "schemas:\n  Pet:\n    type: object\n    properties:\n      name:\n        type: integer\n  Cat:\n    type: object\n",
+    /// )?;
+    ///
+    /// let diff = ComponentsDiff::from_components(&base, &head, &DiffOptions::default())?;
+    ///
+    /// let schemas = diff.schemas.expect("schemas should have changed");
+    /// assert_eq!("Cat", schemas.added[0].0);
+    /// assert!(schemas.changed["Pet"].properties_changed.is_some());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_components(
+        base: &Components,
+        head: &Components,
+        options: &DiffOptions,
+    ) -> Result<Self, DiffError> {
+        let mut diff = Self::default();
+
+        if !options.ignore_examples {
+            let examples_diff = ExamplesDiff::from_examples(&base.examples, &head.examples);
+            if examples_diff.has_changes() {
+                diff.examples = Some(examples_diff);
+            }
+        }
+
+        let schemas_diff = SchemasDiff::from_schemas(&base.schemas, &head.schemas, options);
+        if schemas_diff.has_changes() {
+            diff.schemas = Some(schemas_diff);
+        }
+
+        // Path items are matched by name, not by the path they are used at.
+        let path_items_options = DiffOptions {
+            detect_path_renames: false,
+            ignore_path_param_names: false,
+            ..options.clone()
+        };
+        let path_items_diff = PathsDiff::from_paths_with_options(
+            &base.path_items,
+            &head.path_items,
+            &path_items_options,
+        )?;
+        if path_items_diff.has_changes() {
+            diff.path_items = Some(path_items_diff);
+        }
+
+        Ok(diff)
+    }
+}
+
+/// Changes to the reusable examples under `components/examples`, matched
+/// by name.
+#[derive(Debug, Serialize)]
+pub struct ExamplesDiff {
+    pub added: Vec<ExamplePair>,
+    pub removed: Vec<ExamplePair>,
+    pub changed: HashMap<String, ExampleDiff>,
+}
+
+impl ExamplesDiff {
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+
+    pub fn from_examples(
+        base: &BTreeMap<String, ReferenceOr<Example>>,
+        head: &BTreeMap<String, ReferenceOr<Example>>,
+    ) -> Self {
+        let mut added = vec![];
+        let mut removed = vec![];
+        let mut changed = HashMap::new();
+
+        for (name, base_example) in base {
+            match head.get(name) {
+                Some(head_example) => match (base_example, head_example) {
+                    (ReferenceOr::Item(base_example), ReferenceOr::Item(head_example)) => {
+                        let example_diff = ExampleDiff::from_examples(base_example, head_example);
+
+                        if example_diff.has_changes() {
+                            changed.insert(name.clone(), example_diff);
+                        }
+                    }
+                    _ => {
+                        if base_example != head_example {
+                            removed.push((name.clone(), base_example.clone()));
+                            added.push((name.clone(), head_example.clone()));
+                        }
+                    }
+                },
+                None => removed.push((name.clone(), base_example.clone())),
+            }
+        }
+
+        for (name, head_example) in head {
+            if !base.contains_key(name) {
+                added.push((name.clone(), head_example.clone()));
+            }
+        }
+
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExampleDiff {
+    pub summary: Option<OptionalStringDiff>,
+    pub description: Option<OptionalStringDiff>,
+    pub value: Option<OptionalValueDiff>,
+    pub external_value: Option<OptionalStringDiff>,
+}
+
+impl ExampleDiff {
+    pub fn has_changes(&self) -> bool {
+        self.summary.is_some()
+            || self.description.is_some()
+            || self.value.is_some()
+            || self.external_value.is_some()
+    }
+
+    pub fn from_examples(base: &Example, head: &Example) -> Self {
+        Self {
+            summary: OptionalStringDiff::from_strings(&base.summary, &head.summary),
+            description: OptionalStringDiff::from_strings(&base.description, &head.description),
+            value: OptionalValueDiff::from_values(&base.value, &head.value),
+            external_value: OptionalStringDiff::from_strings(
+                &base.external_value,
+                &head.external_value,
+            ),
+        }
+    }
+}
+
+/// Changes to the reusable schemas under `components/schemas`, matched by
+/// name.
+#[derive(Debug, Serialize)]
+pub struct SchemasDiff {
+    pub added: Vec<SchemaPair>,
+    pub removed: Vec<SchemaPair>,
+    pub changed: HashMap<String, SchemaDiff>,
+}
+
+impl SchemasDiff {
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+
+    pub fn from_schemas(
+        base: &BTreeMap<String, ReferenceOr<Schema>>,
+        head: &BTreeMap<String, ReferenceOr<Schema>>,
+        options: &DiffOptions,
+    ) -> Self {
+        let mut added = vec![];
+        let mut removed = vec![];
+        let mut changed = HashMap::new();
+
+        for (name, base_schema) in base {
+            match head.get(name) {
+                Some(head_schema) => {
+                    let schema_diff =
+                        SchemaDiff::from_schemas_with_options(base_schema, head_schema, options);
+
+                    if schema_diff.has_changes() {
+                        changed.insert(name.clone(), schema_diff);
+                    }
+                }
+                None => removed.push((name.clone(), base_schema.clone())),
+            }
+        }
+
+        for (name, head_schema) in head {
+            if !base.contains_key(name) {
+                added.push((name.clone(), head_schema.clone()));
+            }
+        }
+
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn components(value: serde_json::Value) -> Components {
+        let mut components = Components::default();
+        components.examples.insert(
+            String::from("pet"),
+            ReferenceOr::Item(Example {
+                summary: Some(String::from("A pet")),
+                value: Some(value),
+                ..Default::default()
+            }),
+        );
+        components
+    }
+
+    #[test]
+    fn changed_example_value() {
+        let diff = ComponentsDiff::from_components(
+            &components(json!({"name": "Rex"})),
+            &components(json!({"name": "Fido"})),
+            &DiffOptions::default(),
+        )
+        .unwrap();
+
+        let examples = diff.examples.expect("examples should have changed");
+        let example_diff = &examples.changed["pet"];
+        assert!(example_diff.summary.is_none());
+        assert_eq!(
+            Some(json!({"name": "Fido"})),
+            example_diff.value.as_ref().unwrap().to
+        );
+    }
+
+    #[test]
+    fn added_and_removed_examples() {
+        let diff = ComponentsDiff::from_components(
+            &Components::default(),
+            &components(json!("Rex")),
+            &DiffOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(1, diff.examples.unwrap().added.len());
+
+        let diff = ComponentsDiff::from_components(
+            &components(json!("Rex")),
+            &Components::default(),
+            &DiffOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(1, diff.examples.unwrap().removed.len());
+    }
+
+    #[test]
+    fn ignore_examples() {
+        let options = DiffOptions {
+            ignore_examples: true,
+            ..Default::default()
+        };
+
+        let diff = ComponentsDiff::from_components(
+            &components(json!("Rex")),
+            &components(json!("Fido")),
+            &options,
+        )
+        .unwrap();
+
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn changed_path_item() {
+        let components = |summary: &str| -> Components {
+            serde_yaml::from_str(&format!(
+                r#"
+pathItems:
+  Pets:
+    get:
+      summary: {}
+      responses: {{}}
+"#,
+                summary
+            ))
+            .expect("Failed to parse components")
+        };
+
+        let diff = ComponentsDiff::from_components(
+            &components("List pets"),
+            &components("List all pets"),
+            &DiffOptions::default(),
+        )
+        .unwrap();
+
+        let path_items = diff.path_items.expect("path items should have changed");
+        let summary = path_items.changed["Pets"].operations_changed["get"]
+            .summary
+            .as_ref()
+            .expect("summary should have changed");
+        assert_eq!(Some("List all pets"), summary.to.as_deref());
+
+        // OpenAPI 3.0 components have no path items.
+        let diff = ComponentsDiff::from_components(
+            &Components::default(),
+            &Components::default(),
+            &DiffOptions::default(),
+        )
+        .unwrap();
+        assert!(diff.path_items.is_none());
+    }
+}
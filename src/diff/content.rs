@@ -1,7 +1,8 @@
 use super::media_type::MediaTypeDiff;
+use super::options::DiffOptions;
 use crate::openapi::MediaType;
-use std::collections::BTreeMap;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 pub type MediaTypePair = (String, MediaType);
@@ -21,6 +22,7 @@ impl ContentDiff {
     pub fn from_content(
         base: &BTreeMap<String, MediaType>,
         head: &BTreeMap<String, MediaType>,
+        options: &DiffOptions,
     ) -> Self {
         let mut media_types_added = vec![];
         let mut media_types_removed = vec![];
@@ -29,8 +31,11 @@ impl ContentDiff {
         for (media_type, media_type_definition) in base {
             match head.get(media_type) {
                 Some(head_media_type) => {
-                    let media_type_diff =
-                        MediaTypeDiff::from_media_types(&media_type_definition, &head_media_type);
+                    let media_type_diff = MediaTypeDiff::from_media_types(
+                        media_type_definition,
+                        head_media_type,
+                        options,
+                    );
 
                     if media_type_diff.has_changes() {
                         media_types_changed.insert(media_type.clone(), media_type_diff);
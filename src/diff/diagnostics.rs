@@ -0,0 +1,358 @@
+use super::common::escape_pointer;
+use crate::openapi::{MediaType, OpenAPI, Paths, ReferenceOr, Schema};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A problem found in the head description while diffing. Diagnostics
+/// are not changes: they flag inconsistencies a reviewer should look at.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    /// JSON pointer to the offending location in the head description.
+    pub pointer: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// A request example sets a property the schema marks as `readOnly`.
+    ReadOnlyPropertyInRequestExample { property: String },
+    /// A response example contains a property the schema marks as `writeOnly`.
+    WriteOnlyPropertyInResponseExample { property: String },
+    /// A new or changed `operationId` does not match the configured pattern.
+    OperationIdPatternMismatch { operation_id: String },
+}
+
+/// Checks the `operationId` of every head operation that is new or whose
+/// id changed against `DiffOptions::operation_id_pattern`. Ids already
+/// present in base are left alone.
+pub(crate) fn validate_operation_ids(
+    base: &OpenAPI,
+    head: &OpenAPI,
+    pattern: &str,
+) -> Result<Vec<Diagnostic>, regex::Error> {
+    let regex = Regex::new(&format!("^(?:{})$", pattern))?;
+    let mut diagnostics = vec![];
+
+    for (root, base_paths, head_paths) in [
+        ("paths", &base.paths, &head.paths),
+        ("webhooks", &base.webhooks, &head.webhooks),
+    ] {
+        for (path, path_item) in head_paths {
+            let path_item = match path_item {
+                ReferenceOr::Item(path_item) => path_item,
+                ReferenceOr::Reference { reference: _ } => continue,
+            };
+
+            for (method, operation) in path_item.operations() {
+                let operation_id = match &operation.operation_id {
+                    Some(operation_id) => operation_id,
+                    None => continue,
+                };
+
+                if base_operation_id(base_paths, path, method) == Some(operation_id)
+                    || regex.is_match(operation_id)
+                {
+                    continue;
+                }
+
+                diagnostics.push(Diagnostic {
+                    kind: DiagnosticKind::OperationIdPatternMismatch {
+                        operation_id: operation_id.clone(),
+                    },
+                    pointer: format!("/{}/{}/{}/operationId", root, escape_pointer(path), method),
+                    message: format!(
+                        "operationId `{}` does not match the pattern `{}`.",
+                        operation_id, pattern
+                    ),
+                });
+            }
+        }
+    }
+
+    Ok(diagnostics)
+}
+
+fn base_operation_id<'a>(paths: &'a Paths, path: &str, method: &str) -> Option<&'a String> {
+    match paths.get(path)? {
+        ReferenceOr::Item(path_item) => path_item
+            .operations()
+            .find(|(base_method, _)| *base_method == method)
+            .and_then(|(_, operation)| operation.operation_id.as_ref()),
+        ReferenceOr::Reference { reference: _ } => None,
+    }
+}
+
+/// Checks request and response examples of every operation against the
+/// `readOnly`/`writeOnly` flags of their schema.
+pub(crate) fn validate_examples(spec: &OpenAPI) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    for (path, path_item) in &spec.paths {
+        let path_item = match path_item {
+            ReferenceOr::Item(path_item) => path_item,
+            ReferenceOr::Reference { reference: _ } => continue,
+        };
+
+        for (method, operation) in path_item.operations() {
+            let pointer = format!("/paths/{}/{}", escape_pointer(path), method);
+
+            if let Some(ReferenceOr::Item(request_body)) = &operation.request_body {
+                validate_content(
+                    &request_body.content,
+                    &format!("{}/requestBody", pointer),
+                    ExampleContext::Request,
+                    &mut diagnostics,
+                );
+            }
+
+            let responses = operation
+                .responses
+                .default
+                .iter()
+                .map(|response| (String::from("default"), response))
+                .chain(
+                    operation
+                        .responses
+                        .responses
+                        .iter()
+                        .map(|(status_code, response)| (status_code.to_string(), response)),
+                );
+
+            for (status_code, response) in responses {
+                if let ReferenceOr::Item(response) = response {
+                    validate_content(
+                        &response.content,
+                        &format!("{}/responses/{}", pointer, status_code),
+                        ExampleContext::Response,
+                        &mut diagnostics,
+                    );
+                }
+            }
+        }
+    }
+
+    diagnostics
+}
+
+#[derive(Clone, Copy)]
+enum ExampleContext {
+    Request,
+    Response,
+}
+
+fn validate_content(
+    content: &BTreeMap<String, MediaType>,
+    pointer: &str,
+    context: ExampleContext,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (media_type_name, media_type) in content {
+        let schema = match &media_type.schema {
+            Some(ReferenceOr::Item(schema)) => schema,
+            _ => continue,
+        };
+
+        let pointer = format!("{}/content/{}", pointer, escape_pointer(media_type_name));
+
+        if let Some(example) = &media_type.example {
+            let pointer = format!("{}/example", pointer);
+            validate_value(example, schema, "", &pointer, context, diagnostics);
+        }
+
+        for (name, example) in &media_type.examples {
+            if let ReferenceOr::Item(example) = example {
+                if let Some(value) = &example.value {
+                    let pointer = format!("{}/examples/{}/value", pointer, escape_pointer(name));
+                    validate_value(value, schema, "", &pointer, context, diagnostics);
+                }
+            }
+        }
+    }
+}
+
+fn validate_value(
+    value: &serde_json::Value,
+    schema: &Schema,
+    property_path: &str,
+    pointer: &str,
+    context: ExampleContext,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match value {
+        serde_json::Value::Object(object) => {
+            let properties = match &schema.properties {
+                Some(properties) => properties,
+                None => return,
+            };
+
+            for (key, property_value) in object {
+                let property_schema = match properties.get(key) {
+                    Some(property_schema) => property_schema,
+                    None => continue,
+                };
+
+                let property = if property_path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", property_path, key)
+                };
+
+                match context {
+                    ExampleContext::Request if property_schema.read_only == Some(true) => {
+                        diagnostics.push(Diagnostic {
+                            message: format!(
+                                "Request example sets readOnly property `{}`.",
+                                property
+                            ),
+                            kind: DiagnosticKind::ReadOnlyPropertyInRequestExample {
+                                property: property.clone(),
+                            },
+                            pointer: pointer.to_string(),
+                        });
+                    }
+                    ExampleContext::Response if property_schema.write_only == Some(true) => {
+                        diagnostics.push(Diagnostic {
+                            message: format!(
+                                "Response example contains writeOnly property `{}`.",
+                                property
+                            ),
+                            kind: DiagnosticKind::WriteOnlyPropertyInResponseExample {
+                                property: property.clone(),
+                            },
+                            pointer: pointer.to_string(),
+                        });
+                    }
+                    _ => {}
+                }
+
+                validate_value(
+                    property_value,
+                    property_schema,
+                    &property,
+                    pointer,
+                    context,
+                    diagnostics,
+                );
+            }
+        }
+        serde_json::Value::Array(values) => {
+            if let Some(items) = &schema.items {
+                for (index, item) in values.iter().enumerate() {
+                    let property = format!("{}[{}]", property_path, index);
+                    validate_value(item, items, &property, pointer, context, diagnostics);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: "3.0.0"
+info:
+  title: Users
+  version: 1.0.0
+paths:
+  /users:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                id:
+                  type: string
+                  readOnly: true
+                password:
+                  type: string
+                  writeOnly: true
+            example:
+              id: "42"
+              password: hunter2
+      responses:
+        "201":
+          description: Created
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  type: object
+                  properties:
+                    id:
+                      type: string
+                      readOnly: true
+                    password:
+                      type: string
+                      writeOnly: true
+              examples:
+                created:
+                  value:
+                    - id: "42"
+                      password: hunter2
+"#;
+
+    #[test]
+    fn read_only_and_write_only_examples() {
+        let spec: OpenAPI = serde_yaml::from_str(SPEC).expect("Failed to parse spec");
+
+        let diagnostics = validate_examples(&spec);
+
+        assert_eq!(2, diagnostics.len());
+        assert_eq!(
+            DiagnosticKind::ReadOnlyPropertyInRequestExample {
+                property: "id".into()
+            },
+            diagnostics[0].kind
+        );
+        assert_eq!(
+            "/paths/~1users/post/requestBody/content/application~1json/example",
+            diagnostics[0].pointer
+        );
+        assert_eq!(
+            DiagnosticKind::WriteOnlyPropertyInResponseExample {
+                property: "[0].password".into()
+            },
+            diagnostics[1].kind
+        );
+        assert_eq!(
+            "/paths/~1users/post/responses/201/content/application~1json/examples/created/value",
+            diagnostics[1].pointer
+        );
+    }
+
+    #[test]
+    fn operation_id_pattern() {
+        let base: OpenAPI = serde_yaml::from_str(SPEC).expect("Failed to parse spec");
+        let mut head = base.clone();
+        if let Some(ReferenceOr::Item(path_item)) = head.paths.get_mut("/users") {
+            path_item.post.as_mut().unwrap().operation_id = Some("createUser".into());
+            path_item.get = Some(crate::openapi::Operation {
+                operation_id: Some("users.list".into()),
+                ..Default::default()
+            });
+        }
+
+        let diagnostics =
+            validate_operation_ids(&base, &head, r"[a-z]+\.[a-z]+").expect("Failed to validate");
+
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(
+            DiagnosticKind::OperationIdPatternMismatch {
+                operation_id: "createUser".into()
+            },
+            diagnostics[0].kind
+        );
+        assert_eq!("/paths/~1users/post/operationId", diagnostics[0].pointer);
+
+        assert!(validate_operation_ids(&base, &head, "(").is_err());
+    }
+}
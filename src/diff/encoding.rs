@@ -0,0 +1,240 @@
+use super::common::{BooleanDiff, OptionalStringDiff};
+use super::options::DiffOptions;
+use super::schema::SchemaDiff;
+use crate::openapi::{Encoding, Header, ParameterSchemaOrContent, ReferenceOr};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+pub type HeaderPair = (String, ReferenceOr<Header>);
+
+/// Changes to the `encoding` of a media type, matched by the name of the
+/// part or property each entry applies to.
+#[derive(Debug, Serialize)]
+pub struct EncodingsDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: HashMap<String, EncodingDiff>,
+}
+
+impl EncodingsDiff {
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+
+    pub fn from_encodings(
+        base: &BTreeMap<String, Encoding>,
+        head: &BTreeMap<String, Encoding>,
+        options: &DiffOptions,
+    ) -> Self {
+        let mut added = vec![];
+        let mut removed = vec![];
+        let mut changed = HashMap::new();
+
+        for (name, base_encoding) in base {
+            match head.get(name) {
+                Some(head_encoding) => {
+                    let diff = EncodingDiff::from_encodings(base_encoding, head_encoding, options);
+                    if diff.has_changes() {
+                        changed.insert(name.clone(), diff);
+                    }
+                }
+                None => removed.push(name.clone()),
+            }
+        }
+
+        for name in head.keys() {
+            if !base.contains_key(name) {
+                added.push(name.clone());
+            }
+        }
+
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct EncodingDiff {
+    pub content_type: Option<OptionalStringDiff>,
+    /// Changes to the headers of a multipart part.
+    pub headers: Option<HeadersDiff>,
+}
+
+impl EncodingDiff {
+    pub fn has_changes(&self) -> bool {
+        self.content_type.is_some() || self.headers.is_some()
+    }
+
+    pub fn from_encodings(base: &Encoding, head: &Encoding, options: &DiffOptions) -> Self {
+        let headers = HeadersDiff::from_headers(&base.headers, &head.headers, options);
+
+        Self {
+            content_type: OptionalStringDiff::from_strings(&base.content_type, &head.content_type),
+            headers: if headers.has_changes() {
+                Some(headers)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeadersDiff {
+    pub added: Vec<HeaderPair>,
+    pub removed: Vec<HeaderPair>,
+    pub changed: HashMap<String, HeaderDiff>,
+}
+
+impl HeadersDiff {
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+
+    pub fn from_headers(
+        base: &BTreeMap<String, ReferenceOr<Header>>,
+        head: &BTreeMap<String, ReferenceOr<Header>>,
+        options: &DiffOptions,
+    ) -> Self {
+        let mut added = vec![];
+        let mut removed = vec![];
+        let mut changed = HashMap::new();
+
+        for (name, base_header) in base {
+            match head.get(name) {
+                Some(head_header) => {
+                    let diff = HeaderDiff::from_headers(base_header, head_header, options);
+                    if diff.has_changes() {
+                        changed.insert(name.clone(), diff);
+                    }
+                }
+                None => removed.push((name.clone(), base_header.clone())),
+            }
+        }
+
+        for (name, head_header) in head {
+            if !base.contains_key(name) {
+                added.push((name.clone(), head_header.clone()));
+            }
+        }
+
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeaderDiff {
+    /// The component the header refers to, when either side is a
+    /// reference. References are compared by name and not followed.
+    pub reference: Option<OptionalStringDiff>,
+    pub required: Option<BooleanDiff>,
+    pub schema: Option<SchemaDiff>,
+}
+
+impl HeaderDiff {
+    pub fn has_changes(&self) -> bool {
+        self.reference.is_some() || self.required.is_some() || self.schema.is_some()
+    }
+
+    fn reference(header: &ReferenceOr<Header>) -> Option<String> {
+        match header {
+            ReferenceOr::Reference { reference } => Some(reference.clone()),
+            ReferenceOr::Item(_) => None,
+        }
+    }
+
+    pub fn from_headers(
+        base: &ReferenceOr<Header>,
+        head: &ReferenceOr<Header>,
+        options: &DiffOptions,
+    ) -> Self {
+        let (base_header, head_header) = match (base, head) {
+            (ReferenceOr::Item(base), ReferenceOr::Item(head)) => (base, head),
+            _ => {
+                return Self {
+                    reference: OptionalStringDiff::from_strings(
+                        &Self::reference(base),
+                        &Self::reference(head),
+                    ),
+                    required: None,
+                    schema: None,
+                }
+            }
+        };
+
+        let schema = match (&base_header.format, &head_header.format) {
+            (
+                ParameterSchemaOrContent::Schema(base_schema),
+                ParameterSchemaOrContent::Schema(head_schema),
+            ) => Some(SchemaDiff::from_schemas_with_options(
+                base_schema,
+                head_schema,
+                options,
+            ))
+            .filter(SchemaDiff::has_changes),
+            _ => None,
+        };
+
+        Self {
+            reference: None,
+            required: BooleanDiff::from_bools(
+                base_header.required.unwrap_or(false),
+                head_header.required.unwrap_or(false),
+            ),
+            schema,
+        }
+    }
+
+    /// Whether the header is required, which references don't tell.
+    pub(crate) fn is_required(header: &ReferenceOr<Header>) -> bool {
+        match header {
+            ReferenceOr::Item(header) => header.required.unwrap_or(false),
+            ReferenceOr::Reference { .. } => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoding(headers: &str) -> BTreeMap<String, Encoding> {
+        serde_yaml::from_str(&format!(
+            r#"
+avatar:
+  contentType: image/png
+  headers:
+    {}
+"#,
+            headers
+        ))
+        .expect("Failed to parse encoding")
+    }
+
+    #[test]
+    fn part_headers() {
+        let diff = EncodingsDiff::from_encodings(
+            &encoding("{ X-Checksum: { schema: { type: string } }, X-Rate: { schema: { type: integer } } }"),
+            &encoding("{ X-Checksum: { required: true, schema: { type: string } }, X-Trace: { required: true, schema: { type: string } } }"),
+            &DiffOptions::default(),
+        );
+
+        let headers = diff.changed["avatar"]
+            .headers
+            .as_ref()
+            .expect("headers should have changed");
+
+        assert_eq!("X-Trace", headers.added[0].0);
+        assert!(HeaderDiff::is_required(&headers.added[0].1));
+        assert_eq!("X-Rate", headers.removed[0].0);
+        assert!(headers.changed["X-Checksum"].required.as_ref().unwrap().to);
+        assert!(headers.changed["X-Checksum"].schema.is_none());
+    }
+}
@@ -1,4 +1,8 @@
-use serde::Serialize;
+use super::options::DiffOptions;
+use super::DiffError;
+use crate::openapi::OpenAPI;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::{BTreeMap, HashMap};
 
 pub type ExtensionKeyValue = (String, serde_json::Value);
@@ -27,7 +31,7 @@ impl ExtensionsDiff {
             match head.get(extension_key) {
                 Some(head_extension_value) => {
                     let extension_diff =
-                        ExtensionDiff::from_values(&extension_value, &head_extension_value);
+                        ExtensionDiff::from_values(extension_value, head_extension_value);
 
                     if extension_diff.has_changes() {
                         extensions_changed.insert(extension_key.clone(), extension_diff);
@@ -70,3 +74,166 @@ impl ExtensionDiff {
         }
     }
 }
+
+/// Fields whose value maps names chosen by the API, such as property or
+/// component names, to OpenAPI objects. Their keys are never extensions.
+const NAMED_MAPS: &[&str] = &[
+    "properties",
+    "patternProperties",
+    "dependentSchemas",
+    "paths",
+    "webhooks",
+    "schemas",
+    "responses",
+    "parameters",
+    "requestBodies",
+    "headers",
+    "securitySchemes",
+    "links",
+    "callbacks",
+    "pathItems",
+    "content",
+    "encoding",
+    "variables",
+    "mapping",
+];
+
+/// Fields holding literal values rather than OpenAPI objects.
+pub(super) const LITERALS: &[&str] = &[
+    "example",
+    "default",
+    "enum",
+    "const",
+    "value",
+    "dependentRequired",
+    "security",
+    "scopes",
+];
+
+/// Removes the extensions selected by `DiffOptions::strip_extensions` from
+/// a description.
+pub(crate) fn strip_extensions(spec: OpenAPI, options: &DiffOptions) -> Result<OpenAPI, DiffError> {
+    let prefix = match &options.strip_extensions {
+        Some(prefix) => prefix,
+        None => return Ok(spec),
+    };
+
+    let mut document = serde_json::to_value(spec)?;
+    strip_object(
+        &mut document,
+        prefix,
+        options.deprecated_extension.as_deref(),
+    );
+    Ok(OpenAPI::deserialize(document)?)
+}
+
+fn strip_object(value: &mut Value, prefix: &str, keep: Option<&str>) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|key, _| {
+                !(key.starts_with("x-") && key.starts_with(prefix)) || Some(key.as_str()) == keep
+            });
+
+            for (key, field) in object.iter_mut() {
+                if LITERALS.contains(&key.as_str()) {
+                    continue;
+                }
+
+                match field {
+                    // `examples` is a list of literal values in a schema and a
+                    // map of Example objects elsewhere.
+                    Value::Array(_) if key == "examples" => {}
+                    Value::Object(entries)
+                        if key == "examples" || NAMED_MAPS.contains(&key.as_str()) =>
+                    {
+                        for entry in entries.values_mut() {
+                            strip_object(entry, prefix, keep);
+                        }
+                    }
+                    field => strip_object(field, prefix, keep),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                strip_object(item, prefix, keep);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_extensions_with_prefix() {
+        let spec: OpenAPI = serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Pets", "version": "1.0.0", "x-generator": "a" },
+            "paths": {
+                "/pets": {
+                    "x-deprecated": true,
+                    "get": {
+                        "x-codegen-name": "listPets",
+                        "responses": {
+                            "200": {
+                                "description": "Pets",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "object",
+                                            "x-codegen-class": "Pet",
+                                            "properties": { "x-name": { "type": "string" } },
+                                            "example": { "x-name": "Rex" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to parse description");
+
+        let options = DiffOptions {
+            strip_extensions: Some("x-".into()),
+            deprecated_extension: Some("x-deprecated".into()),
+            ..Default::default()
+        };
+        let stripped = serde_json::to_value(strip_extensions(spec, &options).unwrap()).unwrap();
+
+        assert!(stripped.pointer("/info/x-generator").is_none());
+        assert!(stripped.pointer("/paths/~1pets/x-deprecated").is_some());
+        assert!(stripped
+            .pointer("/paths/~1pets/get/x-codegen-name")
+            .is_none());
+
+        let schema = stripped
+            .pointer("/paths/~1pets/get/responses/200/content/application~1json/schema")
+            .unwrap();
+        assert!(schema.get("x-codegen-class").is_none());
+        assert!(schema.pointer("/properties/x-name").is_some());
+        assert!(schema.pointer("/example/x-name").is_some());
+    }
+
+    #[test]
+    fn strips_extensions_only() {
+        let spec: OpenAPI = serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Pets", "version": "1.0.0", "description": "Pets" },
+            "paths": {}
+        }))
+        .expect("Failed to parse description");
+
+        let options = DiffOptions {
+            strip_extensions: Some("de".into()),
+            ..Default::default()
+        };
+        let stripped = serde_json::to_value(strip_extensions(spec, &options).unwrap()).unwrap();
+
+        assert!(stripped.pointer("/info/description").is_some());
+    }
+}
@@ -1,32 +1,71 @@
+use super::common::OptionalValueDiff;
+use super::components::ExamplesDiff;
+use super::encoding::EncodingsDiff;
+use super::options::DiffOptions;
 use super::schema::SchemaDiff;
 use crate::openapi::{MediaType, ReferenceOr, Schema};
 use serde::Serialize;
 
+/// Changes to a media type. Schema and example changes are kept apart, so
+/// an edit to the example payload alone can be told from a change to what
+/// the media type accepts.
 #[derive(Debug, Serialize)]
 pub struct MediaTypeDiff {
     pub schema_changed: Option<SchemaDiff>,
     pub schema_added: Option<ReferenceOr<Schema>>,
     pub schema_removed: Option<ReferenceOr<Schema>>,
+    /// Change to the singular `example`.
+    pub example_changed: Option<OptionalValueDiff>,
+    /// Changes to the named `examples`, matched by name.
+    pub examples_changed: Option<ExamplesDiff>,
+    /// Changes to the `encoding` of the parts of a multipart or form body.
+    pub encoding_changed: Option<EncodingsDiff>,
 }
 
 impl MediaTypeDiff {
     pub fn has_changes(&self) -> bool {
+        self.has_schema_changes() || self.has_example_changes() || self.encoding_changed.is_some()
+    }
+
+    pub fn has_schema_changes(&self) -> bool {
         self.schema_changed.is_some()
             || self.schema_added.is_some()
             || self.schema_removed.is_some()
     }
 
-    pub fn from_media_types(base: &MediaType, head: &MediaType) -> Self {
+    pub fn has_example_changes(&self) -> bool {
+        self.example_changed.is_some() || self.examples_changed.is_some()
+    }
+
+    pub fn from_media_types(base: &MediaType, head: &MediaType, options: &DiffOptions) -> Self {
         let mut diff = Self {
             schema_changed: None,
             schema_removed: None,
             schema_added: None,
+            example_changed: None,
+            examples_changed: None,
+            encoding_changed: None,
         };
 
+        let encoding_diff = EncodingsDiff::from_encodings(&base.encoding, &head.encoding, options);
+        if encoding_diff.has_changes() {
+            diff.encoding_changed = Some(encoding_diff);
+        }
+
+        if !options.ignore_examples {
+            diff.example_changed = OptionalValueDiff::from_values(&base.example, &head.example);
+
+            let examples_diff = ExamplesDiff::from_examples(&base.examples, &head.examples);
+            if examples_diff.has_changes() {
+                diff.examples_changed = Some(examples_diff);
+            }
+        }
+
         match &base.schema {
             Some(base_schema) => match &head.schema {
                 Some(head_schema) => {
-                    let schema_diff = SchemaDiff::from_schemas(&base_schema, &head_schema);
+                    let schema_diff =
+                        SchemaDiff::from_schemas_with_options(base_schema, head_schema, options);
 
                     if schema_diff.has_changes() {
                         diff.schema_changed = Some(schema_diff);
@@ -36,14 +75,46 @@ impl MediaTypeDiff {
                     diff.schema_removed = Some(base_schema.clone());
                 }
             },
-            None => match &head.schema {
-                Some(head_schema) => {
+            None => {
+                if let Some(head_schema) = &head.schema {
                     diff.schema_added = Some(head_schema.clone());
                 }
-                None => {}
-            },
+            }
         }
 
         diff
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn media_type(example: serde_json::Value) -> MediaType {
+        MediaType {
+            schema: Some(ReferenceOr::Item(Schema {
+                schema_type: Some("object".into()),
+                ..Default::default()
+            })),
+            example: Some(example),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn only_example_changed() {
+        let diff = MediaTypeDiff::from_media_types(
+            &media_type(json!({"name": "Rex"})),
+            &media_type(json!({"name": "Fido"})),
+            &DiffOptions::default(),
+        );
+
+        assert!(diff.has_example_changes());
+        assert!(!diff.has_schema_changes());
+        assert_eq!(
+            Some(json!({"name": "Fido"})),
+            diff.example_changed.unwrap().to
+        );
+    }
+}
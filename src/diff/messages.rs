@@ -0,0 +1,102 @@
+use super::changes::Change;
+
+/// Produces the human readable message of a change, so reports can be
+/// worded in another language than English.
+///
+/// Providers usually match on the `reason_code` of the change kind and
+/// build the message from the location of the change and its
+/// `arguments`, falling back to `English` for the kinds they don't
+/// translate.
+pub trait MessageProvider {
+    fn message(&self, change: &Change) -> String;
+}
+
+/// The English messages changes are collected with, filling the template
+/// of a change with its arguments.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct English;
+
+impl MessageProvider for English {
+    fn message(&self, change: &Change) -> String {
+        let mut arguments = change.arguments.iter();
+        let mut pieces = change.template.split("{}");
+        let mut message = String::from(pieces.next().unwrap_or_default());
+
+        for piece in pieces {
+            message.push_str(arguments.next().map_or("", String::as_str));
+            message.push_str(piece);
+        }
+
+        message
+    }
+}
+
+impl Change {
+    /// The message of the change, as worded by `provider`.
+    pub fn localized_message(&self, provider: &dyn MessageProvider) -> String {
+        provider.message(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::{ChangeKind, Confidence};
+
+    struct French;
+
+    impl MessageProvider for French {
+        fn message(&self, change: &Change) -> String {
+            match (change.kind.reason_code(), change.arguments.as_slice()) {
+                ("path_parameter_renamed", [from, to]) => {
+                    format!("Le paramètre `{}` a été renommé en `{}`.", from, to)
+                }
+                _ => English.message(change),
+            }
+        }
+    }
+
+    fn change(kind: ChangeKind, template: &str, arguments: &[&str]) -> Change {
+        let mut change = Change {
+            kind,
+            pointer: String::from("/paths/~1users~1{userId}"),
+            path: Some(String::from("/users/{userId}")),
+            method: None,
+            property: None,
+            context: None,
+            message: String::new(),
+            template: String::from(template),
+            arguments: arguments.iter().map(ToString::to_string).collect(),
+            confidence: Confidence::Certain,
+        };
+        change.message = English.message(&change);
+        change
+    }
+
+    #[test]
+    fn localized_messages() {
+        let renamed = change(
+            ChangeKind::PathParameterRenamed,
+            "Path parameter `{}` was renamed to `{}`.",
+            &["userId", "id"],
+        );
+        assert_eq!(
+            "Path parameter `userId` was renamed to `id`.",
+            renamed.localized_message(&English)
+        );
+        assert_eq!(
+            "Le paramètre `userId` a été renommé en `id`.",
+            renamed.localized_message(&French)
+        );
+
+        let removed = change(
+            ChangeKind::PathRemoved,
+            "Path `{}` was removed.",
+            &["/users/{userId}"],
+        );
+        assert_eq!(
+            "Path `/users/{userId}` was removed.",
+            removed.localized_message(&French)
+        );
+    }
+}
@@ -1,26 +1,53 @@
+pub(crate) mod breaking;
+pub(crate) mod callbacks;
+pub(crate) mod changes;
 pub(crate) mod common;
+pub(crate) mod components;
 pub(crate) mod content;
+pub(crate) mod diagnostics;
+pub(crate) mod encoding;
 pub(crate) mod extensions;
 pub(crate) mod info;
 pub(crate) mod media_type;
+pub(crate) mod messages;
+pub(crate) mod normalize;
 pub(crate) mod operations;
+pub(crate) mod options;
+pub(crate) mod owners;
 pub(crate) mod parameter;
 pub(crate) mod parameters;
 pub(crate) mod path_items;
 pub(crate) mod paths;
+pub(crate) mod references;
 pub(crate) mod request_body;
 pub(crate) mod response;
 pub(crate) mod responses;
 pub(crate) mod schema;
+pub(crate) mod security;
 pub(crate) mod servers;
+pub(crate) mod tags;
 
 use crate::openapi::OpenAPI;
+use breaking::Values;
+pub use breaking::{BreakingChange, BreakingChanges};
+pub use changes::{Change, ChangeContext, ChangeKind, ChangelogSection, Confidence, Severity};
 use common::StringDiff;
+pub use components::ComponentsDiff;
+pub use diagnostics::{Diagnostic, DiagnosticKind};
 use info::InfoDiff;
-use paths::PathsDiff;
-use serde::Serialize;
+pub use messages::{English, MessageProvider};
+pub use normalize::normalize_spec;
+pub use options::{DiffOptions, NormalizeOptions};
+use owners::OperationTags;
+pub use owners::DEFAULT_OWNER;
+pub use paths::{PathOrderDiff, PathsDiff};
+use references::Resolvers;
+use security::{InheritedSecurity, SecurityDiff};
+use serde::{Deserialize, Serialize};
 use servers::ServersDiff;
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use thiserror::Error;
 
 /// DiffError enumerates all possible errors returned by this library.
@@ -34,46 +61,583 @@ pub enum DiffError {
     #[error(transparent)]
     IOError(#[from] std::io::Error),
 
-    /// Represents all cases of `serde_json::Error`.
+    /// Represents all cases of `serde_yaml::Error`.
     #[error(transparent)]
     SerdeError(#[from] serde_yaml::Error),
+
+    /// Represents a description that could not be parsed, at a known
+    /// position such as `openapi.yaml:42:7`.
+    #[error("{}:{}:{}: {}", .path.display(), .line, .column, .message)]
+    ParseError {
+        path: PathBuf,
+        /// 1-based line of the error.
+        line: usize,
+        /// 1-based column of the error.
+        column: usize,
+        message: String,
+    },
+
+    /// Represents all cases of `serde_json::Error`.
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+
+    /// Represents an invalid `DiffOptions::operation_id_pattern`.
+    #[error("Invalid operationId pattern: {0}")]
+    PatternError(#[from] regex::Error),
+
+    /// Represents a `$ref` pointing to no component, see
+    /// `DiffOptions::resolve_references`.
+    #[error("Unresolvable reference: {0}")]
+    UnresolvableReference(String),
+
+    /// Represents `$ref`s pointing back to themselves, listed in the order
+    /// they were followed.
+    #[error("Reference cycle: {0}")]
+    ReferenceCycle(String),
 }
 
 #[derive(Debug, Serialize, Default)]
 pub struct Diff {
     pub version: Option<StringDiff>,
     pub servers: Option<ServersDiff>,
+    /// Changes to the document level `security`, which applies to every
+    /// operation not declaring its own.
+    pub security: Option<SecurityDiff>,
     pub paths: Option<PathsDiff>,
+    /// Set when the relative order of the paths changed, only reported with
+    /// `DiffOptions::report_path_reordering`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paths_reordered: Option<PathOrderDiff>,
+    /// Changes to OpenAPI 3.1 webhooks, diffed like paths keyed by webhook
+    /// name.
+    pub webhooks: Option<PathsDiff>,
     pub info: Option<InfoDiff>,
+    pub components: Option<ComponentsDiff>,
+    /// Whether head has a `components` section base did not have. The
+    /// components it introduces are still listed in `components`.
+    pub components_introduced: bool,
+    /// Whether head no longer has the `components` section of base. The
+    /// components it removes are still listed in `components`.
+    pub components_removed: bool,
+    /// Problems found in the head description, see `DiffOptions`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
+    #[serde(skip)]
+    pub(crate) operation_tags: OperationTags,
+    #[serde(skip)]
+    pub(crate) sources: Option<Sources>,
+}
+
+/// The preprocessed descriptions a diff was computed from, kept so the
+/// diff can be updated against a new head. See `Diff::update`.
+#[derive(Debug)]
+pub(crate) struct Sources {
+    base: Arc<OpenAPI>,
+    head: Arc<OpenAPI>,
+    options: DiffOptions,
+    /// The options the sections were diffed with, which also depend on
+    /// the descriptions.
+    section_options: DiffOptions,
 }
 
 impl Diff {
     pub fn has_changes(&self) -> bool {
         self.version.is_some()
             || self.servers.is_some()
+            || self.security.is_some()
             || self.paths.is_some()
+            || self.paths_reordered.is_some()
+            || self.webhooks.is_some()
             || self.info.is_some()
+            || self.components.is_some()
+            || self.components_introduced
+            || self.components_removed
+    }
+
+    /// Checks the invariants of this diff: sub-diffs are only set when they
+    /// hold changes, no entry is both added and removed or changed, changed
+    /// entries hold changes, and the pointers of its changes are valid JSON
+    /// pointers. Returns a description of the first violation found.
+    ///
+    /// Checked in debug builds for every computed diff.
+    pub fn validate_self(&self) -> Result<(), String> {
+        let unchanged = [
+            (
+                "servers",
+                self.servers.as_ref().is_some_and(|d| !d.has_changes()),
+            ),
+            (
+                "security",
+                self.security.as_ref().is_some_and(|d| !d.has_changes()),
+            ),
+            (
+                "paths",
+                self.paths.as_ref().is_some_and(|d| !d.has_changes()),
+            ),
+            (
+                "webhooks",
+                self.webhooks.as_ref().is_some_and(|d| !d.has_changes()),
+            ),
+            ("info", self.info.as_ref().is_some_and(|d| !d.has_changes())),
+            (
+                "components",
+                self.components.as_ref().is_some_and(|d| !d.has_changes()),
+            ),
+        ];
+        if let Some((section, _)) = unchanged.iter().find(|(_, unchanged)| *unchanged) {
+            return Err(format!("The {} diff is set without changes", section));
+        }
+
+        if let Some(paths) = &self.paths {
+            paths
+                .validate()
+                .map_err(|error| format!("paths: {}", error))?;
+        }
+
+        if let Some(webhooks) = &self.webhooks {
+            webhooks
+                .validate()
+                .map_err(|error| format!("webhooks: {}", error))?;
+        }
+
+        if let Some(path_items) = self
+            .components
+            .as_ref()
+            .and_then(|components| components.path_items.as_ref())
+        {
+            path_items
+                .validate()
+                .map_err(|error| format!("components/pathItems: {}", error))?;
+        }
+
+        match self
+            .changes()
+            .find(|change| !is_json_pointer(&change.pointer))
+        {
+            Some(change) => Err(format!("Invalid pointer `{}`", change.pointer)),
+            None => Ok(()),
+        }
+    }
+
+    /// Diffs two descriptions already parsed as JSON values, such as
+    /// descriptions fetched at runtime.
+    pub fn from_values(
+        base: &serde_json::Value,
+        head: &serde_json::Value,
+        options: &DiffOptions,
+    ) -> Result<Self, DiffError> {
+        let base_openapi = OpenAPI::deserialize(base)?;
+        let head_openapi = OpenAPI::deserialize(head)?;
+        diff_with_options(base_openapi, head_openapi, options)
+    }
+
+    /// Every operation added to the description, as sorted `(path, method)`
+    /// tuples. See `PathsDiff::operations_added`.
+    pub fn operations_added(&self) -> Vec<(String, String)> {
+        self.paths
+            .as_ref()
+            .map(|paths| paths.operations_added())
+            .unwrap_or_default()
+    }
+
+    /// Every operation removed from the description, as sorted
+    /// `(path, method)` tuples. See `PathsDiff::operations_removed`.
+    pub fn operations_removed(&self) -> Vec<(String, String)> {
+        self.paths
+            .as_ref()
+            .map(|paths| paths.operations_removed())
+            .unwrap_or_default()
+    }
+
+    /// Every change in this diff as a flat list, in a deterministic order.
+    pub fn changes(&self) -> impl Iterator<Item = Change> {
+        changes::collect(self).into_iter()
+    }
+
+    /// Whether any change in this diff can break existing clients, e.g. to
+    /// fail a CI check. See `Change::is_breaking`.
+    pub fn has_breaking_changes(&self) -> bool {
+        self.changes().any(|change| change.is_breaking())
+    }
+
+    /// Every breaking change in this diff, in the same deterministic order as
+    /// `changes`, with its reason code and the values found at its pointer
+    /// before and after the change. Changes are classified with
+    /// `Change::is_breaking`, and changes skipped through `DiffOptions` are
+    /// never yielded. Yields nothing when the diff has no breaking changes.
+    pub fn breaking_changes_iter(&self) -> impl Iterator<Item = BreakingChange> {
+        let changes: Vec<_> = self.changes().filter(Change::is_breaking).collect();
+
+        let values = if changes.is_empty() {
+            Values::default()
+        } else {
+            self.values()
+        };
+
+        changes
+            .into_iter()
+            .map(move |change| values.breaking_change(change))
+    }
+
+    /// The descriptions this diff was computed from, to look up the values
+    /// of its changes. Empty when the diff was not computed from complete
+    /// descriptions.
+    pub(crate) fn values(&self) -> Values {
+        match &self.sources {
+            Some(sources) => Values::from_specs(&sources.base, &sources.head),
+            None => Values::default(),
+        }
+    }
+
+    /// Every change bucketed into Keep a Changelog sections, e.g. to
+    /// generate a CHANGELOG entry. Changes keep their deterministic order
+    /// within a section, and sections without changes are left out. See
+    /// `ChangeKind::changelog_section` for the mapping.
+    pub fn changelog(&self) -> HashMap<ChangelogSection, Vec<Change>> {
+        let mut sections: HashMap<ChangelogSection, Vec<Change>> = HashMap::new();
+
+        for change in self.changes() {
+            sections
+                .entry(change.kind.changelog_section())
+                .or_default()
+                .push(change);
+        }
+
+        sections
+    }
+
+    /// The operations affected by breaking changes, as
+    /// `(path, method, reason code)` tuples sorted by path, method and reason
+    /// code, with one tuple per reason. Changes to a whole path, such as a
+    /// removed path, affect every operation of the path. Breaking changes
+    /// outside of any path, such as a removed server, affect the whole
+    /// document and are listed first, with an empty path and method.
+    pub fn breaking_operations(&self) -> Vec<(String, String, &'static str)> {
+        let mut operations = BTreeSet::new();
+
+        for change in self.changes().filter(Change::is_breaking) {
+            let reason_code = change.kind.reason_code();
+
+            match (&change.path, &change.method) {
+                (Some(path), Some(method)) => {
+                    operations.insert((path.clone(), method.clone(), reason_code));
+                }
+                (Some(path), None) => {
+                    for method in self.operation_tags.methods_of(&change) {
+                        operations.insert((path.clone(), method.to_string(), reason_code));
+                    }
+                }
+                (None, _) => {
+                    operations.insert((String::new(), String::new(), reason_code));
+                }
+            }
+        }
+
+        operations.into_iter().collect()
+    }
+
+    /// Breaking changes grouped by owner, given a `tag → owner` mapping,
+    /// e.g. to notify the team maintaining each client SDK. Changes are
+    /// routed through the tags of their operation; changes outside of any
+    /// operation with a mapped tag go to `DEFAULT_OWNER`.
+    pub fn group_by_owner(
+        &self,
+        mapping: &HashMap<String, String>,
+    ) -> HashMap<String, Vec<Change>> {
+        self.operation_tags.group_by_owner(self.changes(), mapping)
+    }
+
+    /// Recomputes this diff against a new head description, such as a file
+    /// saved again in a watch tool. Only the path items and webhooks that
+    /// differ from the previous head are diffed again, the changes of the
+    /// others are reused. The result is the same as a full diff of the base
+    /// description against `head`, with the same options.
+    ///
+    /// Path items are compared with the previous head directly rather than
+    /// through content hashes, so a hash collision cannot hide a change.
+    /// None are reused when head changes what every path item is diffed
+    /// with, such as its OpenAPI version, document security or components.
+    ///
+    /// `paths_reordered` is dropped: the order of the paths is only known
+    /// when diffing files, see `diff_files_with_options`.
+    pub fn update(self, head: OpenAPI) -> Result<Diff, DiffError> {
+        let sources = self.sources.as_ref().ok_or_else(|| {
+            DiffError::UnsupportedFeature(String::from(
+                "Cannot update a diff that was not computed from two descriptions",
+            ))
+        })?;
+
+        let base = sources.base.clone();
+        let options = sources.options.clone();
+        let head = preprocess(head, &options)?;
+
+        compare(base, head, &options, Some(self))
     }
 }
 
 pub fn diff_files(base: PathBuf, head: PathBuf) -> Result<Diff, DiffError> {
-    let base_contents = std::fs::read_to_string(base)?;
-    let head_contents = std::fs::read_to_string(head)?;
-    let base_openapi: OpenAPI = serde_yaml::from_str(&base_contents)?;
-    let head_openapi: OpenAPI = serde_yaml::from_str(&head_contents)?;
-    diff(base_openapi, head_openapi)
+    diff_files_with_options(base, head, &DiffOptions::default())
+}
+
+pub fn diff_files_with_options(
+    base: PathBuf,
+    head: PathBuf,
+    options: &DiffOptions,
+) -> Result<Diff, DiffError> {
+    let mut base_openapi = parse_file(&base)?;
+    let mut head_openapi = parse_file(&head)?;
+
+    if options.resolve_references {
+        base_openapi = references::bundle(base_openapi, &base)?;
+        head_openapi = references::bundle(head_openapi, &head)?;
+    }
+    let mut diff = diff_with_options(base_openapi, head_openapi, options)?;
+
+    if options.report_path_reordering {
+        diff.paths_reordered = PathOrderDiff::from_orders(&path_order(&base)?, &path_order(&head)?);
+    }
+
+    Ok(diff)
+}
+
+/// The paths of a description file, in declaration order.
+fn path_order(path: &Path) -> Result<Vec<String>, DiffError> {
+    let contents = read_description(path)?;
+    let document: serde_yaml::Value = serde_yaml::from_str(&contents)?;
+
+    Ok(document
+        .get("paths")
+        .and_then(serde_yaml::Value::as_mapping)
+        .map(|paths| {
+            paths
+                .iter()
+                .filter_map(|(path, _)| path.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Reads and parses a description, in YAML or JSON. Parse errors at a known
+/// position are returned as `DiffError::ParseError`.
+///
+/// With the `gzip` feature, gzipped descriptions such as `openapi.json.gz`
+/// are decompressed first. They are recognized by their magic bytes rather
+/// than their extension.
+pub fn parse_file(path: &Path) -> Result<OpenAPI, DiffError> {
+    let contents = read_description(path)?;
+
+    serde_yaml::from_str(&contents).map_err(|error| match error.location() {
+        Some(location) => {
+            // The position is reported as part of the error span instead.
+            let suffix = format!(" at line {} column {}", location.line(), location.column());
+            let message = error.to_string();
+
+            DiffError::ParseError {
+                path: path.to_path_buf(),
+                line: location.line(),
+                column: location.column(),
+                message: message
+                    .strip_suffix(&suffix)
+                    .unwrap_or(&message)
+                    .to_string(),
+            }
+        }
+        None => DiffError::SerdeError(error),
+    })
+}
+
+/// The first bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Reads a description file, decompressing it if it is gzipped.
+fn read_description(path: &Path) -> Result<String, DiffError> {
+    let bytes = std::fs::read(path)?;
+
+    if bytes.starts_with(&GZIP_MAGIC) {
+        return gunzip(&bytes);
+    }
+
+    String::from_utf8(bytes)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error).into())
+}
+
+#[cfg(feature = "gzip")]
+fn gunzip(bytes: &[u8]) -> Result<String, DiffError> {
+    use std::io::Read;
+
+    let mut contents = String::new();
+    flate2::read::GzDecoder::new(bytes).read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn gunzip(_bytes: &[u8]) -> Result<String, DiffError> {
+    Err(DiffError::UnsupportedFeature(String::from(
+        "gzipped descriptions require the `gzip` feature",
+    )))
 }
 
 pub fn diff(base: OpenAPI, head: OpenAPI) -> Result<Diff, DiffError> {
-    let mut diff = Diff::default();
+    diff_with_options(base, head, &DiffOptions::default())
+}
+
+pub fn diff_with_options(
+    base: OpenAPI,
+    head: OpenAPI,
+    options: &DiffOptions,
+) -> Result<Diff, DiffError> {
+    let base = preprocess(base, options)?;
+    let head = preprocess(head, options)?;
+
+    compare(Arc::new(base), head, options, None)
+}
+
+/// Normalizes a description before it is compared.
+fn preprocess(mut spec: OpenAPI, options: &DiffOptions) -> Result<OpenAPI, DiffError> {
+    if options.strip_extensions.is_some() {
+        spec = extensions::strip_extensions(spec, options)?;
+    }
+
+    if options.resolve_references {
+        spec = references::resolve_references(spec)?;
+    }
+
+    if !options.exclude_tags.is_empty() {
+        spec = tags::exclude_tags(spec, &options.exclude_tags);
+    }
+
+    normalize::normalize_spec(&mut spec, &options.normalize);
+
+    Ok(spec)
+}
+
+/// Diffs two preprocessed descriptions, reusing the path item changes of
+/// `previous` when given. See `Diff::update`.
+fn compare(
+    base: Arc<OpenAPI>,
+    head: OpenAPI,
+    options: &DiffOptions,
+    previous: Option<Diff>,
+) -> Result<Diff, DiffError> {
+    let (previous_sources, previous_paths, previous_webhooks) = match previous {
+        Some(Diff {
+            sources: Some(sources),
+            paths,
+            webhooks,
+            ..
+        }) => (
+            Some(sources),
+            paths.unwrap_or_default(),
+            webhooks.unwrap_or_default(),
+        ),
+        _ => (None, PathsDiff::default(), PathsDiff::default()),
+    };
+
+    let mut diff = Diff {
+        version: StringDiff::from_strings(base.openapi.clone(), head.openapi.clone()),
+        operation_tags: OperationTags::from_specs(&base, &head),
+        ..Default::default()
+    };
+
+    let section_options = DiffOptions {
+        json_schema: Some(
+            options
+                .json_schema
+                .unwrap_or_else(|| is_json_schema(&base, &head)),
+        ),
+        security: InheritedSecurity::from_specs(&base, &head),
+        resolvers: Resolvers::from_specs(&base, &head),
+        ..options.clone()
+    };
 
-    diff.version = StringDiff::from_strings(base.openapi, head.openapi);
+    // Path items were diffed with the previous section options, which
+    // depend on head, such as its version, security and components, so
+    // their changes can't be reused once those changed.
+    let previous_head = previous_sources
+        .filter(|previous| previous.section_options == section_options)
+        .map(|previous| previous.head);
 
-    let paths_diff = PathsDiff::from_paths(&base.paths, &head.paths)?;
+    // Identical descriptions have no changes, which is much cheaper to tell
+    // than diffing them section by section.
+    if !(options.short_circuit_identical && *base == head) {
+        compare_sections(
+            &mut diff,
+            &base,
+            &head,
+            &section_options,
+            previous_head.as_deref(),
+            previous_paths,
+            previous_webhooks,
+        )?;
+    }
+
+    if options.validate_examples {
+        diff.diagnostics = diagnostics::validate_examples(&head);
+    }
+
+    if let Some(pattern) = &options.operation_id_pattern {
+        diff.diagnostics
+            .extend(diagnostics::validate_operation_ids(&base, &head, pattern)?);
+    }
+
+    diff.sources = Some(Sources {
+        base,
+        head: Arc::new(head),
+        options: options.clone(),
+        section_options,
+    });
+
+    debug_assert_eq!(Ok(()), diff.validate_self());
+
+    Ok(diff)
+}
+
+/// Whether either description is OpenAPI 3.1 or later, whose schemas are
+/// JSON Schema. See `DiffOptions::json_schema`.
+fn is_json_schema(base: &OpenAPI, head: &OpenAPI) -> bool {
+    [base, head]
+        .iter()
+        .any(|spec| !spec.openapi.starts_with("3.0"))
+}
+
+/// Diffs the sections of two descriptions into `diff`.
+fn compare_sections(
+    diff: &mut Diff,
+    base: &OpenAPI,
+    head: &OpenAPI,
+    options: &DiffOptions,
+    previous_head: Option<&OpenAPI>,
+    previous_paths: PathsDiff,
+    previous_webhooks: PathsDiff,
+) -> Result<(), DiffError> {
+    let paths_diff = match &previous_head {
+        Some(previous_head) => PathsDiff::from_paths_reusing(
+            &base.paths,
+            &head.paths,
+            options,
+            &previous_head.paths,
+            previous_paths,
+        )?,
+        None => PathsDiff::from_paths_with_options(&base.paths, &head.paths, options)?,
+    };
     if paths_diff.has_changes() {
         diff.paths = Some(paths_diff);
     }
 
+    let webhooks_diff = match &previous_head {
+        Some(previous_head) => PathsDiff::from_paths_reusing(
+            &base.webhooks,
+            &head.webhooks,
+            options,
+            &previous_head.webhooks,
+            previous_webhooks,
+        )?,
+        None => PathsDiff::from_paths_with_options(&base.webhooks, &head.webhooks, options)?,
+    };
+    if webhooks_diff.has_changes() {
+        diff.webhooks = Some(webhooks_diff);
+    }
+
     let info_diff = InfoDiff::from_info(&base.info, &head.info);
     if info_diff.has_changes() {
         diff.info = Some(info_diff);
@@ -84,12 +648,36 @@ pub fn diff(base: OpenAPI, head: OpenAPI) -> Result<Diff, DiffError> {
         diff.servers = Some(servers_diff);
     }
 
-    Ok(diff)
+    diff.security = SecurityDiff::from_requirements(&base.security, &head.security);
+
+    let components_diff = ComponentsDiff::from_components(
+        &base.components.clone().unwrap_or_default(),
+        &head.components.clone().unwrap_or_default(),
+        options,
+    )?;
+    diff.components_introduced = base.components.is_none() && head.components.is_some();
+    diff.components_removed = base.components.is_some() && head.components.is_none();
+    if components_diff.has_changes() {
+        diff.components = Some(components_diff);
+    }
+
+    Ok(())
+}
+
+/// Whether `pointer` is a JSON pointer as per RFC 6901: empty, or reference
+/// tokens each prefixed by `/` in which `~` only escapes `~` and `/`.
+fn is_json_pointer(pointer: &str) -> bool {
+    (pointer.is_empty() || pointer.starts_with('/'))
+        && pointer
+            .split('~')
+            .skip(1)
+            .all(|escaped| escaped.starts_with('0') || escaped.starts_with('1'))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::openapi::{PathItem, ReferenceOr};
 
     #[test]
     fn from_json_files() {
@@ -115,7 +703,30 @@ mod tests {
         )
         .expect("Failed to diff JSON");
 
-        assert_eq!(false, diff.has_changes());
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn short_circuit_identical() {
+        let options = DiffOptions {
+            short_circuit_identical: true,
+            ..Default::default()
+        };
+        let base = PathBuf::from("fixtures/pet-store.yaml");
+        let head = PathBuf::from("fixtures/pet-store-changed.yaml");
+
+        let diff =
+            diff_files_with_options(base.clone(), base.clone(), &options).expect("Failed to diff");
+        assert!(!diff.has_changes());
+
+        let changes = |options: &DiffOptions| -> Vec<String> {
+            diff_files_with_options(base.clone(), head.clone(), options)
+                .expect("Failed to diff")
+                .changes()
+                .map(|change| change.message)
+                .collect()
+        };
+        assert_eq!(changes(&DiffOptions::default()), changes(&options));
     }
 
     #[test]
@@ -147,4 +758,610 @@ mod tests {
         assert_eq!("3.0.0", version_change.from);
         assert_eq!("4.0.0", version_change.to);
     }
+
+    #[test]
+    fn from_values() {
+        let base: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string("fixtures/pet-store.json").expect("Failed to read base"),
+        )
+        .expect("Failed to parse base");
+        let head: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string("fixtures/pet-store-changed.json")
+                .expect("Failed to read head"),
+        )
+        .expect("Failed to parse head");
+
+        let diff = Diff::from_values(&base, &head, &DiffOptions::default())
+            .expect("Failed to diff values");
+
+        assert!(diff.has_changes());
+        assert!(diff.paths.is_some());
+    }
+
+    #[test]
+    fn from_invalid_values() {
+        let base = serde_json::json!({ "openapi": "3.0.0" });
+
+        let result = Diff::from_values(&base, &base, &DiffOptions::default());
+
+        assert!(matches!(result, Err(DiffError::JsonError(_))));
+    }
+
+    #[test]
+    fn webhook_request_schema_changed() {
+        let base: OpenAPI = serde_yaml::from_str(
+            r#"
+openapi: "3.1.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths: {}
+webhooks:
+  newPet:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                name:
+                  type: string
+      responses:
+        "200":
+          description: Received
+"#,
+        )
+        .expect("Failed to parse base");
+        let mut head = base.clone();
+        if let ReferenceOr::Item(path_item) = head.webhooks.get_mut("newPet").unwrap() {
+            if let Some(ReferenceOr::Item(request_body)) =
+                &mut path_item.post.as_mut().unwrap().request_body
+            {
+                let schema = request_body
+                    .content
+                    .get_mut("application/json")
+                    .unwrap()
+                    .schema
+                    .as_mut()
+                    .unwrap();
+                if let ReferenceOr::Item(schema) = schema {
+                    schema.properties.as_mut().unwrap().insert(
+                        "tag".into(),
+                        crate::openapi::Schema {
+                            schema_type: Some("string".into()),
+                            ..Default::default()
+                        },
+                    );
+                }
+            }
+        }
+
+        let diff = diff(base, head).expect("Failed to diff");
+
+        assert!(diff.paths.is_none());
+        let webhooks = diff
+            .webhooks
+            .as_ref()
+            .expect("webhooks should have changed");
+        assert!(webhooks.changed["newPet"].operations_changed["post"]
+            .request_body
+            .is_some());
+
+        let changes: Vec<_> = diff.changes().collect();
+        assert_eq!(1, changes.len());
+        assert_eq!(ChangeKind::PropertyAdded, changes[0].kind);
+        assert_eq!(
+            "/webhooks/newPet/post/requestBody/content/application~1json/schema/properties/tag",
+            changes[0].pointer
+        );
+        // Receivers handle the payload like a response, so a new property
+        // deserves attention without breaking them.
+        assert!(!changes[0].is_breaking());
+        assert_eq!(Severity::Warning, changes[0].severity());
+    }
+
+    /// A description whose `POST /payments` request body has `schema`.
+    fn request_schema_document(version: &str, schema: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "openapi": version,
+            "info": { "title": "Payments", "version": "1.0.0" },
+            "paths": {
+                "/payments": {
+                    "post": {
+                        "requestBody": {
+                            "content": { "application/json": { "schema": schema } }
+                        },
+                        "responses": {}
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn request_schema_changes() {
+        let base = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "method": { "type": "string" },
+                "iban": { "type": "string" },
+                "payers": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "address": {
+                                "type": "object",
+                                "properties": { "zip": { "type": "string" } }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        let mut head = base.clone();
+        head["if"] = serde_json::json!({ "properties": { "method": { "enum": ["sepa"] } } });
+        head["then"] = serde_json::json!({ "required": ["iban"] });
+        head["properties"]["iban"]["contentEncoding"] = serde_json::json!("base64");
+        head["properties"]["payers"]["items"]["properties"]["address"]["properties"]["zip"]
+            ["type"] = serde_json::json!("integer");
+
+        let diff = Diff::from_values(
+            &request_schema_document("3.1.0", base.clone()),
+            &request_schema_document("3.1.0", head.clone()),
+            &DiffOptions::default(),
+        )
+        .expect("Failed to diff");
+
+        let changes: Vec<_> = diff.changes().collect();
+        assert_eq!(
+            vec![
+                ChangeKind::ConditionalAdded,
+                ChangeKind::ConditionalAdded,
+                ChangeKind::SchemaContentEncodingChanged,
+                ChangeKind::SchemaTypeChanged,
+            ],
+            changes
+                .iter()
+                .map(|change| change.kind.clone())
+                .collect::<Vec<_>>()
+        );
+        assert!(changes.iter().all(|change| change.is_breaking()));
+        assert!(changes[0].pointer.ends_with("/schema/if"));
+        assert!(changes[1].pointer.ends_with("/schema/then"));
+        assert_eq!(Some("iban"), changes[2].property.as_deref());
+        assert_eq!(
+            "Schema content encoding was added: `base64`.",
+            changes[2].message
+        );
+        assert_eq!(Some("payers[].address.zip"), changes[3].property.as_deref());
+
+        // OpenAPI 3.0 schemas do not define `contentEncoding`.
+        let diff = Diff::from_values(
+            &request_schema_document("3.0.3", base),
+            &request_schema_document("3.0.3", head),
+            &DiffOptions::default(),
+        )
+        .expect("Failed to diff");
+        assert!(diff
+            .changes()
+            .all(|change| change.kind != ChangeKind::SchemaContentEncodingChanged));
+    }
+
+    #[test]
+    fn group_by_owner() {
+        let base: OpenAPI = serde_yaml::from_str(
+            r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      tags: [pets]
+      responses: {}
+    delete:
+      tags: [pets, admin]
+      responses: {}
+  /stores:
+    get:
+      responses: {}
+"#,
+        )
+        .expect("Failed to parse base");
+        let mut head = base.clone();
+        if let Some(ReferenceOr::Item(path_item)) = head.paths.get_mut("/pets") {
+            path_item.delete = None;
+        }
+        head.paths.remove("/stores");
+
+        let diff = diff(base, head).expect("Failed to diff");
+
+        let mut mapping = HashMap::new();
+        mapping.insert(String::from("pets"), String::from("pets-sdk"));
+        mapping.insert(String::from("admin"), String::from("admin-sdk"));
+        let groups = diff.group_by_owner(&mapping);
+
+        assert_eq!(3, groups.len());
+        assert_eq!("/paths/~1pets/delete", groups["pets-sdk"][0].pointer);
+        assert_eq!("/paths/~1pets/delete", groups["admin-sdk"][0].pointer);
+        assert_eq!("/paths/~1stores", groups[DEFAULT_OWNER][0].pointer);
+    }
+
+    #[test]
+    fn has_breaking_changes() {
+        let spec = |required: bool| -> OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: limit
+          in: query
+          required: {}
+          schema:
+            type: integer
+      responses: {{}}
+"#,
+                required
+            ))
+            .expect("Failed to parse description")
+        };
+
+        let mut head = spec(false);
+        head.paths.insert(
+            String::from("/stores"),
+            ReferenceOr::Item(PathItem::default()),
+        );
+        let added = diff(spec(false), head.clone()).expect("Failed to diff");
+        assert!(added.has_changes());
+        assert!(!added.has_breaking_changes());
+
+        let required = diff(spec(false), spec(true)).expect("Failed to diff");
+        assert!(required.has_breaking_changes());
+
+        head.paths.remove("/pets");
+        let removed = diff(spec(false), head).expect("Failed to diff");
+        assert!(removed.has_breaking_changes());
+    }
+
+    #[test]
+    fn validate_self() {
+        let mut diff = diff_files(
+            PathBuf::from("fixtures/pet-store.yaml"),
+            PathBuf::from("fixtures/pet-store-changed.yaml"),
+        )
+        .expect("Failed to diff");
+        assert_eq!(Ok(()), diff.validate_self());
+
+        let paths = diff.paths.as_mut().unwrap();
+        paths
+            .changed
+            .insert(String::from("/stores"), path_items::PathItemDiff::default());
+        assert_eq!(
+            Err(String::from(
+                "paths: Path `/stores` is changed without changes"
+            )),
+            diff.validate_self()
+        );
+
+        let diff = Diff {
+            paths: Some(PathsDiff::default()),
+            ..Default::default()
+        };
+        assert!(diff.validate_self().is_err());
+
+        assert!(is_json_pointer("/paths/~1pets/get"));
+        assert!(is_json_pointer(""));
+        assert!(!is_json_pointer("paths"));
+        assert!(!is_json_pointer("/paths/~2pets"));
+    }
+
+    #[test]
+    fn path_reordering() {
+        let base = PathBuf::from("fixtures/pet-store.yaml");
+        let head = PathBuf::from("fixtures/pet-store-reordered.yaml");
+
+        let diff = diff_files(base.clone(), head.clone()).expect("Failed to diff YAML");
+        assert!(!diff.has_changes());
+
+        let options = DiffOptions {
+            report_path_reordering: true,
+            ..DiffOptions::default()
+        };
+        let diff = diff_files_with_options(base, head, &options).expect("Failed to diff YAML");
+
+        let changes: Vec<_> = diff.changes().collect();
+        assert_eq!(1, changes.len());
+        assert_eq!(ChangeKind::PathsReordered, changes[0].kind);
+        assert!(!changes[0].is_breaking());
+        assert_eq!(
+            vec![String::from("/pets/{petId}"), String::from("/pets")],
+            diff.paths_reordered.expect("Paths should be reordered").to
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn gzipped_file() {
+        let diff = diff_files(
+            PathBuf::from("fixtures/pet-store.json.gz"),
+            PathBuf::from("fixtures/pet-store.json"),
+        )
+        .expect("Failed to diff gzipped file");
+
+        assert!(!diff.has_changes());
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn gzipped_file_without_feature() {
+        let error =
+            parse_file(Path::new("fixtures/pet-store.json.gz")).expect_err("Parsed a gzipped file");
+
+        assert!(matches!(error, DiffError::UnsupportedFeature(_)));
+    }
+
+    #[test]
+    fn parse_error_span() {
+        let error = diff_files(
+            PathBuf::from("fixtures/pet-store.yaml"),
+            PathBuf::from("fixtures/invalid.yaml"),
+        )
+        .expect_err("Parsed an invalid description");
+
+        match &error {
+            DiffError::ParseError { line, column, .. } => assert_eq!((6, 11), (*line, *column)),
+            error => panic!("Unexpected error: {:?}", error),
+        }
+        assert_eq!(
+            "fixtures/invalid.yaml:6:11: info.license.name: invalid type: sequence, expected a string",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn components_introduced() {
+        let base: OpenAPI = serde_yaml::from_str(
+            r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths: {}
+"#,
+        )
+        .expect("Failed to parse base");
+        let mut head = base.clone();
+        head.components = Some(
+            serde_yaml::from_str("examples:\n  pet:\n    value:\n      name: Rex\n")
+                .expect("Failed to parse components"),
+        );
+
+        let introduced = diff(base.clone(), head.clone()).expect("Failed to diff");
+
+        assert!(introduced.components_introduced);
+        assert!(!introduced.components_removed);
+        assert_eq!(
+            vec![ChangeKind::ComponentsAdded, ChangeKind::ExampleAdded],
+            introduced
+                .changes()
+                .map(|change| change.kind)
+                .collect::<Vec<_>>()
+        );
+
+        let removed = diff(head, base).expect("Failed to diff");
+        assert!(removed.components_removed);
+        assert_eq!(
+            Some(ChangeKind::ComponentsRemoved),
+            removed.changes().next().map(|change| change.kind)
+        );
+    }
+
+    #[test]
+    fn changelog() {
+        let base: OpenAPI = serde_yaml::from_str(
+            r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      summary: List pets
+      responses: {}
+    delete:
+      responses: {}
+"#,
+        )
+        .expect("Failed to parse base");
+        let mut head = base.clone();
+        if let Some(ReferenceOr::Item(path_item)) = head.paths.get_mut("/pets") {
+            path_item.delete = None;
+            let get = path_item.get.as_mut().unwrap();
+            get.summary = Some(String::from("List all pets"));
+            get.deprecated = Some(true);
+        }
+        head.paths.insert(
+            String::from("/stores"),
+            ReferenceOr::Item(Default::default()),
+        );
+
+        let diff = diff(base, head).expect("Failed to diff");
+        let changelog = diff.changelog();
+
+        let kinds = |section| -> Vec<ChangeKind> {
+            changelog[&section]
+                .iter()
+                .map(|change| change.kind.clone())
+                .collect()
+        };
+        assert_eq!(vec![ChangeKind::PathAdded], kinds(ChangelogSection::Added));
+        assert_eq!(
+            vec![ChangeKind::OperationRemoved],
+            kinds(ChangelogSection::Removed)
+        );
+        assert_eq!(
+            vec![ChangeKind::OperationDeprecated],
+            kinds(ChangelogSection::Deprecated)
+        );
+        assert_eq!(
+            vec![ChangeKind::OperationSummaryChanged],
+            kinds(ChangelogSection::Changed)
+        );
+    }
+
+    #[test]
+    fn breaking_operations() {
+        let base: OpenAPI = serde_yaml::from_str(
+            r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+servers:
+  - url: https://pets.example.com
+paths:
+  /pets:
+    get:
+      responses: {}
+    delete:
+      responses: {}
+  /stores:
+    get:
+      responses: {}
+    post:
+      responses: {}
+"#,
+        )
+        .expect("Failed to parse base");
+        let mut head = base.clone();
+        head.info.title = String::from("Pet store");
+        if let Some(ReferenceOr::Item(path_item)) = head.paths.get_mut("/pets") {
+            path_item.delete = None;
+        }
+        head.paths.remove("/stores");
+        head.servers.clear();
+
+        let diff = diff(base, head).expect("Failed to diff");
+
+        assert_eq!(
+            vec![
+                (String::new(), String::new(), "server_removed"),
+                (
+                    String::from("/pets"),
+                    String::from("delete"),
+                    "operation_removed"
+                ),
+                (String::from("/stores"), String::from("get"), "path_removed"),
+                (
+                    String::from("/stores"),
+                    String::from("post"),
+                    "path_removed"
+                ),
+            ],
+            diff.breaking_operations()
+        );
+    }
+
+    #[test]
+    fn update_matches_full_diff() {
+        let read = |path| -> OpenAPI {
+            let contents = std::fs::read_to_string(path).expect("Failed to read fixture");
+            serde_yaml::from_str(&contents).expect("Failed to parse fixture")
+        };
+        let base = read("fixtures/pet-store.yaml");
+        let head = read("fixtures/pet-store-changed.yaml");
+
+        let mut new_head = head.clone();
+        new_head.paths.remove("/pets");
+        new_head.paths.insert(
+            "/stores".into(),
+            ReferenceOr::Item(crate::openapi::PathItem::default()),
+        );
+
+        let previous = diff(base.clone(), head).expect("Failed to diff");
+        let updated = previous.update(new_head.clone()).expect("Failed to update");
+        let full = diff(base, new_head.clone()).expect("Failed to diff");
+
+        assert_eq!(
+            full.changes().collect::<Vec<_>>(),
+            updated.changes().collect::<Vec<_>>()
+        );
+
+        let updated_again = updated.update(new_head).expect("Failed to update");
+        assert_eq!(
+            full.changes().collect::<Vec<_>>(),
+            updated_again.changes().collect::<Vec<_>>()
+        );
+
+        assert!(Diff::default().update(OpenAPI::default()).is_err());
+    }
+
+    #[test]
+    fn update_with_new_version() {
+        let document = |version: &str, schema: serde_json::Value| -> OpenAPI {
+            serde_json::from_value(request_schema_document(version, schema))
+                .expect("Failed to parse description")
+        };
+        let encoded = serde_json::json!({ "type": "string", "contentEncoding": "base64" });
+        let base = document("3.0.3", serde_json::json!({ "type": "string" }));
+        let head = document("3.0.3", encoded.clone());
+        let new_head = document("3.1.0", encoded);
+
+        // OpenAPI 3.0 schemas have no `contentEncoding`, so the path item
+        // changes once head is OpenAPI 3.1 even though it is the same.
+        let previous = diff(base.clone(), head).expect("Failed to diff");
+        let updated = previous.update(new_head.clone()).expect("Failed to update");
+        let full = diff(base, new_head).expect("Failed to diff");
+
+        assert!(updated
+            .changes()
+            .any(|change| change.kind == ChangeKind::SchemaContentEncodingChanged));
+        assert_eq!(
+            full.changes().collect::<Vec<_>>(),
+            updated.changes().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn breaking_changes_iter() {
+        let diff = diff_files(
+            PathBuf::from("fixtures/pet-store.yaml"),
+            PathBuf::from("fixtures/pet-store-changed.yaml"),
+        )
+        .expect("Failed to diff YAML");
+
+        let breaking: Vec<_> = diff.breaking_changes_iter().collect();
+
+        assert!(!breaking.is_empty());
+        assert_eq!(
+            diff.changes()
+                .filter(Change::is_breaking)
+                .collect::<Vec<_>>(),
+            breaking
+                .iter()
+                .map(|breaking| breaking.change.clone())
+                .collect::<Vec<_>>()
+        );
+        for breaking in &breaking {
+            assert_eq!(breaking.change.kind.reason_code(), breaking.reason_code);
+            assert!(breaking.before.is_some() || breaking.after.is_some());
+        }
+
+        let clean = diff_files(
+            PathBuf::from("fixtures/pet-store.yaml"),
+            PathBuf::from("fixtures/pet-store.yaml"),
+        )
+        .expect("Failed to diff YAML");
+        assert_eq!(0, clean.breaking_changes_iter().count());
+    }
 }
@@ -0,0 +1,379 @@
+use super::options::NormalizeOptions;
+use crate::openapi::{
+    AdditionalProperties, Components, Header, MediaType, OpenAPI, Operation, Parameter,
+    ParameterSchemaOrContent, PathItem, ReferenceOr, RequestBody, Response, Schema, SchemaType,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Rewrites the schemas of a description so equivalent OpenAPI 3.0 and 3.1
+/// representations compare equal, such as `nullable: true` and a type list
+/// with `"null"`. Descriptions are normalized before they are diffed,
+/// which keeps the sub-diffs free of representation concerns.
+///
+/// ```
+/// use anicca::diff::{normalize_spec, NormalizeOptions};
+/// use anicca::openapi::OpenAPI;
+///
+/// let mut spec: OpenAPI = serde_yaml::from_str(
+///     r#"
+/// openapi: "3.1.0"
+/// info: { title: Pets, version: 1.0.0 }
+/// paths: {}
+/// components:
+///   schemas:
+///     Name: { type: [string, "null"] }
+/// "#,
+/// )?;
+///
+/// normalize_spec(&mut spec, &NormalizeOptions::default());
+///
+/// let schemas = &spec.components.unwrap().schemas;
+/// let name = match &schemas["Name"] {
+///     anicca::openapi::ReferenceOr::Item(name) => name,
+///     _ => unreachable!(),
+/// };
+/// assert_eq!(Some("string".into()), name.schema_type);
+/// assert_eq!(Some(true), name.nullable);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn normalize_spec(spec: &mut OpenAPI, options: &NormalizeOptions) {
+    for path_item in spec.paths.values_mut().chain(spec.webhooks.values_mut()) {
+        if let ReferenceOr::Item(path_item) = path_item {
+            normalize_path_item(path_item, options);
+        }
+    }
+
+    if let Some(components) = &mut spec.components {
+        normalize_components(components, options);
+    }
+}
+
+fn normalize_components(components: &mut Components, options: &NormalizeOptions) {
+    for schema in components.schemas.values_mut() {
+        normalize_reference(schema, options);
+    }
+
+    for parameter in components.parameters.values_mut() {
+        if let ReferenceOr::Item(parameter) = parameter {
+            normalize_parameter(parameter, options);
+        }
+    }
+
+    for request_body in components.request_bodies.values_mut() {
+        if let ReferenceOr::Item(request_body) = request_body {
+            normalize_request_body(request_body, options);
+        }
+    }
+
+    for response in components.responses.values_mut() {
+        if let ReferenceOr::Item(response) = response {
+            normalize_response(response, options);
+        }
+    }
+
+    for header in components.headers.values_mut() {
+        if let ReferenceOr::Item(header) = header {
+            normalize_header(header, options);
+        }
+    }
+
+    for callback in components.callbacks.values_mut() {
+        if let ReferenceOr::Item(callback) = callback {
+            for path_item in callback.values_mut() {
+                normalize_path_item(path_item, options);
+            }
+        }
+    }
+
+    for path_item in components.path_items.values_mut() {
+        if let ReferenceOr::Item(path_item) = path_item {
+            normalize_path_item(path_item, options);
+        }
+    }
+}
+
+fn normalize_path_item(path_item: &mut PathItem, options: &NormalizeOptions) {
+    for parameter in &mut path_item.parameters {
+        if let ReferenceOr::Item(parameter) = parameter {
+            normalize_parameter(parameter, options);
+        }
+    }
+
+    for (_, operation) in path_item.operations_mut() {
+        normalize_operation(operation, options);
+    }
+}
+
+fn normalize_operation(operation: &mut Operation, options: &NormalizeOptions) {
+    for parameter in &mut operation.parameters {
+        if let ReferenceOr::Item(parameter) = parameter {
+            normalize_parameter(parameter, options);
+        }
+    }
+
+    if let Some(ReferenceOr::Item(request_body)) = &mut operation.request_body {
+        normalize_request_body(request_body, options);
+    }
+
+    let responses = &mut operation.responses;
+    for response in responses
+        .responses
+        .values_mut()
+        .chain(responses.default.iter_mut())
+    {
+        if let ReferenceOr::Item(response) = response {
+            normalize_response(response, options);
+        }
+    }
+
+    for callback in operation.callbacks.values_mut() {
+        if let ReferenceOr::Item(callback) = callback {
+            for path_item in callback.values_mut() {
+                normalize_path_item(path_item, options);
+            }
+        }
+    }
+}
+
+fn normalize_parameter(parameter: &mut Parameter, options: &NormalizeOptions) {
+    let parameter_data = match parameter {
+        Parameter::Query { parameter_data, .. }
+        | Parameter::Header { parameter_data, .. }
+        | Parameter::Path { parameter_data, .. }
+        | Parameter::Cookie { parameter_data, .. } => parameter_data,
+    };
+
+    normalize_format(&mut parameter_data.format, options);
+}
+
+fn normalize_request_body(request_body: &mut RequestBody, options: &NormalizeOptions) {
+    normalize_content(&mut request_body.content, options);
+}
+
+fn normalize_response(response: &mut Response, options: &NormalizeOptions) {
+    for header in response.headers.values_mut() {
+        if let ReferenceOr::Item(header) = header {
+            normalize_header(header, options);
+        }
+    }
+
+    normalize_content(&mut response.content, options);
+}
+
+fn normalize_header(header: &mut Header, options: &NormalizeOptions) {
+    normalize_format(&mut header.format, options);
+}
+
+fn normalize_format(format: &mut ParameterSchemaOrContent, options: &NormalizeOptions) {
+    match format {
+        ParameterSchemaOrContent::Schema(schema) => normalize_reference(schema, options),
+        ParameterSchemaOrContent::Content(content) => normalize_content(content, options),
+    }
+}
+
+fn normalize_content(content: &mut BTreeMap<String, MediaType>, options: &NormalizeOptions) {
+    for media_type in content.values_mut() {
+        if let Some(schema) = &mut media_type.schema {
+            normalize_reference(schema, options);
+        }
+
+        for encoding in media_type.encoding.values_mut() {
+            for header in encoding.headers.values_mut() {
+                if let ReferenceOr::Item(header) = header {
+                    normalize_header(header, options);
+                }
+            }
+        }
+    }
+}
+
+fn normalize_reference(schema: &mut ReferenceOr<Schema>, options: &NormalizeOptions) {
+    if let ReferenceOr::Item(schema) = schema {
+        normalize_schema(schema, options);
+    }
+}
+
+/// Normalizes a schema along with its subschemas.
+fn normalize_schema(schema: &mut Schema, options: &NormalizeOptions) {
+    if let Some(SchemaType::List(types)) = &schema.schema_type {
+        let nullable = types.contains("null");
+        let mut types = types.iter().filter(|schema_type| *schema_type != "null");
+
+        match (types.next(), types.next()) {
+            (Some(schema_type), None) if nullable && options.nullable => {
+                schema.schema_type = Some(schema_type.as_str().into());
+                schema.nullable = Some(true);
+            }
+            (Some(schema_type), None) if !nullable && options.type_lists => {
+                schema.schema_type = Some(schema_type.as_str().into());
+            }
+            _ => {}
+        }
+    }
+
+    if options.exclusive_bounds {
+        normalize_exclusive_bound(schema, "exclusiveMinimum", "minimum");
+        normalize_exclusive_bound(schema, "exclusiveMaximum", "maximum");
+    }
+
+    if options.examples && schema.examples.is_none() {
+        if let Some(example) = schema.example.take() {
+            schema.examples = Some(vec![example]);
+        }
+    }
+
+    if let Some(items) = &mut schema.items {
+        normalize_schema(items, options);
+    }
+
+    for property in schema.properties.iter_mut().flat_map(BTreeMap::values_mut) {
+        normalize_schema(property, options);
+    }
+
+    if let Some(AdditionalProperties::Schema(additional_properties)) =
+        &mut schema.additional_properties
+    {
+        normalize_reference(additional_properties, options);
+    }
+
+    for subschema in schema
+        .all_of
+        .iter_mut()
+        .chain(schema.one_of.iter_mut())
+        .chain(schema.any_of.iter_mut())
+        .flatten()
+    {
+        normalize_reference(subschema, options);
+    }
+
+    for subschema in [
+        &mut schema.not,
+        &mut schema.if_schema,
+        &mut schema.then_schema,
+        &mut schema.else_schema,
+        &mut schema.property_names,
+    ]
+    .iter_mut()
+    .filter_map(|subschema| subschema.as_deref_mut())
+    {
+        normalize_reference(subschema, options);
+    }
+
+    for subschema in schema
+        .dependent_schemas
+        .iter_mut()
+        .flat_map(BTreeMap::values_mut)
+    {
+        normalize_reference(subschema, options);
+    }
+}
+
+/// Rewrites a 3.0 boolean exclusive bound, such as `minimum: 1` with
+/// `exclusiveMinimum: true`, as the 3.1 `exclusiveMinimum: 1`. Only
+/// `minimum` is a field of `Schema`, the other keywords are kept among its
+/// extensions.
+fn normalize_exclusive_bound(schema: &mut Schema, exclusive: &str, bound: &str) {
+    let is_exclusive = match schema.extensions.get(exclusive) {
+        Some(Value::Bool(is_exclusive)) => *is_exclusive,
+        _ => return,
+    };
+    schema.extensions.remove(exclusive);
+
+    if !is_exclusive {
+        return;
+    }
+
+    let bound = if bound == "minimum" {
+        schema.minimum.take()
+    } else {
+        schema.extensions.remove(bound)
+    };
+
+    if let Some(bound) = bound {
+        schema.extensions.insert(exclusive.to_string(), bound);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::{diff_with_options, DiffOptions, NormalizeOptions};
+    use crate::openapi::OpenAPI;
+
+    fn spec(version: &str, schema: &str) -> OpenAPI {
+        serde_yaml::from_str(&format!(
+            r#"
+openapi: "{}"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            {}
+      responses: {{}}
+"#,
+            version, schema
+        ))
+        .expect("Failed to parse description")
+    }
+
+    #[test]
+    fn equivalent_versions() {
+        let base = spec(
+            "3.0.0",
+            "{ type: integer, nullable: true, minimum: 1, exclusiveMinimum: true, example: 10 }",
+        );
+        let head = spec(
+            "3.1.0",
+            r#"{ type: [integer, "null"], exclusiveMinimum: 1, examples: [10] }"#,
+        );
+
+        let diff = diff_with_options(base.clone(), head.clone(), &DiffOptions::default())
+            .expect("Failed to diff");
+        assert!(diff.paths.is_none());
+
+        let options = DiffOptions {
+            normalize: NormalizeOptions::none(),
+            ..Default::default()
+        };
+        let diff = diff_with_options(base, head, &options).expect("Failed to diff");
+        assert!(diff.paths.is_some());
+    }
+
+    #[test]
+    fn type_lists() {
+        let diff = diff_with_options(
+            spec("3.1.0", "{ type: string }"),
+            spec("3.1.0", "{ type: [string] }"),
+            &DiffOptions::default(),
+        )
+        .expect("Failed to diff");
+        assert!(!diff.has_changes());
+
+        // Multiple types are kept as a list, compared as a set.
+        let diff = diff_with_options(
+            spec("3.1.0", "{ type: string }"),
+            spec("3.1.0", r#"{ type: [string, integer, "null"] }"#),
+            &DiffOptions::default(),
+        )
+        .expect("Failed to diff");
+        let change = diff.changes().next().expect("type should have changed");
+        assert_eq!(
+            "Schema type changed from `string` to `[integer, null, string]`.",
+            change.message
+        );
+
+        let diff = diff_with_options(
+            spec("3.1.0", "{ type: [integer, string] }"),
+            spec("3.1.0", "{ type: [string, integer] }"),
+            &DiffOptions::default(),
+        )
+        .expect("Failed to diff");
+        assert!(!diff.has_changes());
+    }
+}
@@ -1,47 +1,152 @@
-use super::common::{OptionalStringDiff, StringListDiff};
+use super::callbacks::CallbacksDiff;
+use super::common::{validate_entries, BooleanDiff, OptionalStringDiff, StringListDiff};
+use super::options::DiffOptions;
+use super::parameter::ParameterDiff;
 use super::parameters::ParametersDiff;
+use super::paths::PathsDiff;
 use super::request_body::RequestBodyDiff;
+use super::response::ResponseDiff;
 use super::responses::ResponsesDiff;
-use crate::openapi::Operation;
+use super::security::SecurityDiff;
+use crate::openapi::{Operation, Parameter, ReferenceOr};
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
 pub struct OperationDiff {
     pub tags: StringListDiff,
+    /// The first tag of the operation, which documentation tools commonly
+    /// use to group operations.
+    pub primary_tag: Option<OptionalStringDiff>,
     pub summary: Option<OptionalStringDiff>,
     pub description: Option<OptionalStringDiff>,
     pub operation_id: Option<OptionalStringDiff>,
+    pub deprecated: Option<BooleanDiff>,
+    pub security: Option<SecurityDiff>,
     pub parameters: ParametersDiff,
     pub request_body: Option<RequestBodyDiff>,
-    pub responses: ResponsesDiff,
+    pub responses: Option<ResponsesDiff>,
+    pub callbacks: Option<CallbacksDiff>,
 }
 
 impl OperationDiff {
     pub fn has_changes(&self) -> bool {
         self.tags.has_changes()
+            || self.primary_tag.is_some()
             || self.summary.is_some()
             || self.description.is_some()
             || self.operation_id.is_some()
+            || self.deprecated.is_some()
+            || self.security.is_some()
             || self.parameters.has_changes()
             || self.request_body.is_some()
-            || self.responses.has_changes()
+            || self.responses.is_some()
+            || self.callbacks.is_some()
     }
 
-    pub fn from_operations(base: &Operation, head: &Operation) -> Self {
+    /// Checks the invariants of the changed parameters, responses and
+    /// callbacks. See `Diff::validate_self`.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        let names = |parameters: &[ReferenceOr<Parameter>]| -> Vec<String> {
+            parameters
+                .iter()
+                .filter_map(|parameter| match parameter {
+                    ReferenceOr::Item(parameter) => Some(ParametersDiff::param_name(parameter)),
+                    ReferenceOr::Reference { .. } => None,
+                })
+                .collect()
+        };
+        let (added, removed) = (
+            names(&self.parameters.added),
+            names(&self.parameters.removed),
+        );
+        validate_entries(
+            "Parameter",
+            added.iter().collect(),
+            removed.iter().collect(),
+            &self.parameters.changed,
+            ParameterDiff::has_changes,
+        )?;
+
+        if self
+            .request_body
+            .as_ref()
+            .is_some_and(|request_body| !request_body.has_changes())
+        {
+            return Err(String::from("Request body is changed without changes"));
+        }
+
+        if let Some(responses) = &self.responses {
+            if !responses.has_changes() {
+                return Err(String::from("Responses are changed without changes"));
+            }
+
+            validate_entries(
+                "Response",
+                responses.added.iter().map(|(status, _)| status).collect(),
+                responses.removed.iter().map(|(status, _)| status).collect(),
+                &responses.changed,
+                ResponseDiff::has_changes,
+            )?;
+
+            if responses
+                .default_changed
+                .as_ref()
+                .is_some_and(|default| !default.has_changes())
+            {
+                return Err(String::from("Default response is changed without changes"));
+            }
+        }
+
+        if let Some(callbacks) = &self.callbacks {
+            validate_entries(
+                "Callback",
+                callbacks.added.iter().map(|(name, _)| name).collect(),
+                callbacks.removed.iter().map(|(name, _)| name).collect(),
+                &callbacks.changed,
+                PathsDiff::has_changes,
+            )?;
+
+            for (name, paths_diff) in &callbacks.changed {
+                paths_diff
+                    .validate()
+                    .map_err(|error| format!("callback {}: {}", name, error))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn from_operations(base: &Operation, head: &Operation, options: &DiffOptions) -> Self {
         let tags_diff = StringListDiff::from_lists(&base.tags, &head.tags);
 
+        let primary_tag_diff = OptionalStringDiff::from_strings(
+            &base.tags.first().cloned(),
+            &head.tags.first().cloned(),
+        );
+
         let summary_diff = OptionalStringDiff::from_strings(&base.summary, &head.summary);
 
-        let description_diff =
-            OptionalStringDiff::from_strings(&base.description, &head.description);
+        let description_diff = if options.ignore_descriptions {
+            None
+        } else {
+            OptionalStringDiff::from_strings(&base.description, &head.description)
+        };
 
         let operation_id_diff =
             OptionalStringDiff::from_strings(&base.operation_id, &head.operation_id);
 
-        let parameters = ParametersDiff::from_params(&base.parameters, &head.parameters);
+        let deprecated_diff = BooleanDiff::from_bools(
+            base.deprecated.unwrap_or(false),
+            head.deprecated.unwrap_or(false),
+        );
+
+        let security_diff =
+            SecurityDiff::from_operations(&base.security, &head.security, &options.security);
+
+        let parameters = ParametersDiff::from_params(&base.parameters, &head.parameters, options);
 
         let request_body_diff =
-            RequestBodyDiff::from_request_bodies(&base.request_body, &head.request_body);
+            RequestBodyDiff::from_request_bodies(&base.request_body, &head.request_body, options);
 
         let request_body = if request_body_diff.has_changes() {
             Some(request_body_diff)
@@ -49,16 +154,34 @@ impl OperationDiff {
             None
         };
 
-        let responses = ResponsesDiff::from_responses(&base.responses, &head.responses);
+        let responses_diff =
+            ResponsesDiff::from_responses(&base.responses, &head.responses, options);
+        let responses = if responses_diff.has_changes() {
+            Some(responses_diff)
+        } else {
+            None
+        };
+
+        let callbacks_diff =
+            CallbacksDiff::from_callbacks(&base.callbacks, &head.callbacks, options);
+        let callbacks = if callbacks_diff.has_changes() {
+            Some(callbacks_diff)
+        } else {
+            None
+        };
 
         Self {
             tags: tags_diff,
+            primary_tag: primary_tag_diff,
             summary: summary_diff,
             description: description_diff,
             operation_id: operation_id_diff,
+            deprecated: deprecated_diff,
+            security: security_diff,
             parameters,
             request_body,
             responses,
+            callbacks,
         }
     }
 }
@@ -66,6 +189,7 @@ impl OperationDiff {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::openapi::StatusCode;
 
     #[test]
     fn operation_id_changed_from_none_to_some() {
@@ -73,7 +197,11 @@ mod tests {
         let mut head_operation = Operation::default();
         head_operation.operation_id = Some("cats/create".into());
 
-        let diff = OperationDiff::from_operations(&base_operation, &head_operation);
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
         let op_id_diff = diff.operation_id.unwrap();
 
         assert_eq!(op_id_diff.from, None);
@@ -87,7 +215,11 @@ mod tests {
         let mut head_operation = Operation::default();
         head_operation.operation_id = Some("cats/create".into());
 
-        let diff = OperationDiff::from_operations(&base_operation, &head_operation);
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
         let op_id_diff = diff.operation_id.unwrap();
 
         assert_eq!(op_id_diff.from, Some("cats-create".into()));
@@ -100,7 +232,11 @@ mod tests {
         let mut head_operation = Operation::default();
         head_operation.summary = Some("Creates a feline.".into());
 
-        let diff = OperationDiff::from_operations(&base_operation, &head_operation);
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
         let summary_diff = diff.summary.unwrap();
 
         assert_eq!(summary_diff.from, None);
@@ -114,7 +250,11 @@ mod tests {
         let mut head_operation = Operation::default();
         head_operation.summary = Some("Creates a feline.".into());
 
-        let diff = OperationDiff::from_operations(&base_operation, &head_operation);
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
         let summary_diff = diff.summary.unwrap();
 
         assert_eq!(summary_diff.from, Some("Creates a cat.".into()));
@@ -127,7 +267,11 @@ mod tests {
         let mut head_operation = Operation::default();
         head_operation.description = Some("Creates a feline.".into());
 
-        let diff = OperationDiff::from_operations(&base_operation, &head_operation);
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
         let description_diff = diff.description.unwrap();
 
         assert_eq!(description_diff.from, None);
@@ -141,7 +285,11 @@ mod tests {
         let mut head_operation = Operation::default();
         head_operation.description = Some("Creates a feline.".into());
 
-        let diff = OperationDiff::from_operations(&base_operation, &head_operation);
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
         let description_diff = diff.description.unwrap();
 
         assert_eq!(description_diff.from, Some("Creates a cat.".into()));
@@ -158,9 +306,310 @@ mod tests {
         head_operation.tags.push("Cats".into());
         head_operation.tags.push("Fish".into());
 
-        let diff = OperationDiff::from_operations(&base_operation, &head_operation);
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
 
         assert_eq!(vec!["Fish"], diff.tags.added);
         assert_eq!(vec!["Dogs"], diff.tags.removed);
+        assert!(diff.primary_tag.is_none());
+    }
+
+    #[test]
+    fn operation_primary_tag_changed() {
+        let mut base_operation = Operation::default();
+        base_operation.tags.push("Cats".into());
+        base_operation.tags.push("Dogs".into());
+
+        let mut head_operation = Operation::default();
+        head_operation.tags.push("Dogs".into());
+        head_operation.tags.push("Cats".into());
+
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
+        let primary_tag_diff = diff.primary_tag.unwrap();
+
+        assert!(!diff.tags.has_changes());
+        assert_eq!(primary_tag_diff.from, Some("Cats".into()));
+        assert_eq!(primary_tag_diff.to, Some("Dogs".into()));
+    }
+
+    #[test]
+    fn parameter_became_required() {
+        let base_operation: Operation = serde_yaml::from_str(
+            r#"
+parameters:
+  - name: limit
+    in: query
+    required: false
+    schema:
+      type: integer
+responses: {}
+"#,
+        )
+        .expect("Failed to parse operation");
+        let head_operation: Operation = serde_yaml::from_str(
+            r#"
+parameters:
+  - name: limit
+    in: query
+    required: true
+    schema:
+      type: integer
+responses: {}
+"#,
+        )
+        .expect("Failed to parse operation");
+
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
+
+        assert!(diff.parameters.has_changes());
+        assert!(diff.has_changes());
+    }
+
+    #[test]
+    fn parameter_removed() {
+        let base_operation: Operation = serde_yaml::from_str(
+            r#"
+parameters:
+  - name: limit
+    in: query
+    required: true
+    schema:
+      type: integer
+responses: {}
+"#,
+        )
+        .expect("Failed to parse operation");
+
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &Operation::default(),
+            &DiffOptions::default(),
+        );
+
+        assert_eq!(1, diff.parameters.removed.len());
+        assert!(diff.has_changes());
+    }
+
+    #[test]
+    fn request_body_content_type_added() {
+        let base_operation: Operation = serde_yaml::from_str(
+            r#"
+requestBody:
+  content:
+    application/json:
+      schema:
+        type: object
+responses: {}
+"#,
+        )
+        .expect("Failed to parse operation");
+        let head_operation: Operation = serde_yaml::from_str(
+            r#"
+requestBody:
+  content:
+    application/json:
+      schema:
+        type: object
+    application/xml:
+      schema:
+        type: object
+responses: {}
+"#,
+        )
+        .expect("Failed to parse operation");
+
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
+
+        assert!(diff.request_body.is_some());
+        assert!(diff.has_changes());
+    }
+
+    fn operation(yaml: &str) -> Operation {
+        serde_yaml::from_str(yaml).expect("Failed to parse operation")
+    }
+
+    #[test]
+    fn response_added() {
+        let base_operation = operation(
+            r#"
+responses:
+  "200":
+    description: A pet.
+"#,
+        );
+        let head_operation = operation(
+            r#"
+responses:
+  "200":
+    description: A pet.
+  "404":
+    description: No such pet.
+"#,
+        );
+
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
+        let responses = diff.responses.expect("responses should have changed");
+
+        assert_eq!(StatusCode::Code(404), responses.added[0].0);
+        assert!(responses.removed.is_empty());
+        assert!(responses.changed.is_empty());
+    }
+
+    #[test]
+    fn response_removed() {
+        let base_operation = operation(
+            r#"
+responses:
+  "200":
+    description: A pet.
+  "404":
+    description: No such pet.
+"#,
+        );
+        let head_operation = operation(
+            r#"
+responses:
+  "200":
+    description: A pet.
+"#,
+        );
+
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
+        let responses = diff.responses.expect("responses should have changed");
+
+        assert!(responses.added.is_empty());
+        assert_eq!(StatusCode::Code(404), responses.removed[0].0);
+    }
+
+    #[test]
+    fn response_content_changed() {
+        let base_operation = operation(
+            r#"
+responses:
+  "200":
+    description: A pet.
+    content:
+      application/json:
+        schema:
+          type: object
+"#,
+        );
+        let head_operation = operation(
+            r#"
+responses:
+  "200":
+    description: A pet.
+    content:
+      application/json:
+        schema:
+          type: string
+"#,
+        );
+
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
+        let responses = diff.responses.expect("responses should have changed");
+
+        assert!(responses.changed[&StatusCode::Code(200)].content.is_some());
+        assert!(OperationDiff::from_operations(
+            &base_operation,
+            &base_operation,
+            &DiffOptions::default()
+        )
+        .responses
+        .is_none());
+    }
+
+    #[test]
+    fn response_replaced_and_error_added() {
+        let base_operation = operation(
+            r#"
+responses:
+  "200":
+    description: The deleted pet.
+"#,
+        );
+        let head_operation = operation(
+            r#"
+responses:
+  "204":
+    description: The pet was deleted.
+  "429":
+    description: Too many requests.
+"#,
+        );
+
+        let diff = OperationDiff::from_operations(
+            &base_operation,
+            &head_operation,
+            &DiffOptions::default(),
+        );
+
+        let json = serde_json::to_value(&diff).expect("Failed to serialize diff");
+        assert_eq!("200", json["responses"]["removed"][0][0]);
+
+        let responses = diff.responses.expect("responses should have changed");
+        let mut added: Vec<_> = responses.added.iter().map(|(code, _)| code).collect();
+        added.sort();
+        assert_eq!(vec![&StatusCode::Code(204), &StatusCode::Code(429)], added);
+        assert_eq!(vec![&StatusCode::Code(429)], responses.error_codes_added());
+    }
+
+    #[test]
+    fn deprecated_transitions() {
+        let transitions = [
+            (None, Some(false), None),
+            (None, Some(true), Some(true)),
+            (Some(true), None, Some(false)),
+            (Some(false), Some(true), Some(true)),
+        ];
+
+        for (from, to, expected) in transitions {
+            let mut base_operation = Operation::default();
+            base_operation.deprecated = from;
+            let mut head_operation = Operation::default();
+            head_operation.deprecated = to;
+
+            let diff = OperationDiff::from_operations(
+                &base_operation,
+                &head_operation,
+                &DiffOptions::default(),
+            );
+
+            assert_eq!(
+                expected,
+                diff.deprecated.as_ref().map(|deprecated| deprecated.to),
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+            assert_eq!(expected.is_some(), diff.has_changes());
+        }
     }
 }
@@ -0,0 +1,152 @@
+use super::references::Resolvers;
+use super::security::InheritedSecurity;
+
+/// Options controlling how two OpenAPI descriptions are diffed.
+///
+/// The default options produce a plain structural diff of normalized
+/// descriptions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiffOptions {
+    /// Checks that head examples are consistent with `readOnly` and
+    /// `writeOnly` schemas: response examples must not contain `writeOnly`
+    /// properties and request examples must not contain `readOnly` ones.
+    /// Inconsistencies are reported as diagnostics alongside the diff.
+    pub validate_examples: bool,
+    /// Skips example changes, such as edits to reusable examples under
+    /// `components/examples` or to the examples of a media type or schema.
+    pub ignore_examples: bool,
+    /// Extension marking a whole path item as deprecated, such as
+    /// `x-deprecated: true`. All operations of such a path item are then
+    /// effectively deprecated, and are reported as newly deprecated when
+    /// the extension is added. Path-level deprecation is ignored when unset.
+    pub deprecated_extension: Option<String>,
+    /// Skips changes to the descriptions of operations and responses,
+    /// which only affect documentation.
+    pub ignore_descriptions: bool,
+    /// Removes every specification extension whose name starts with this
+    /// prefix from both descriptions before diffing, such as `x-` for all
+    /// extensions or `x-speakeasy-` for the ones of a single generator.
+    /// Unlike a diff option, stripped extensions are never compared.
+    ///
+    /// Only fields of OpenAPI objects are stripped: names chosen by the
+    /// API, such as schema properties or component names, and literal
+    /// values, such as examples, defaults and enums, are kept as is. The
+    /// `deprecated_extension` is kept as well, and so are fields that are
+    /// not extensions, so a `de` prefix leaves `description` alone.
+    pub strip_extensions: Option<String>,
+    /// Regular expression every new or changed `operationId` in head must
+    /// match in full, such as `[a-z]+\.[a-z]+` for `resource.action` ids.
+    /// Violations are reported as diagnostics alongside the diff.
+    pub operation_id_pattern: Option<String>,
+    /// Reports a change when the relative order of the paths shared by base
+    /// and head changed, for teams whose documentation follows declaration
+    /// order. Path order has no meaning otherwise and is ignored by default.
+    ///
+    /// Parsed descriptions do not keep declaration order, so reordering is
+    /// only reported when diffing files, see `diff_files_with_options`.
+    pub report_path_reordering: bool,
+    /// Compares string enum values ignoring case, for enums matched
+    /// case-insensitively at runtime, so `ACTIVE` becoming `active` is not
+    /// reported as a value removed and another added.
+    pub enum_case_insensitive: bool,
+    /// Replaces local `$ref`s to path items, parameters, request bodies,
+    /// responses, callbacks and multipart part headers with the components
+    /// they point to before diffing, so an inline definition and a
+    /// reference to an identical component are not reported as a change.
+    /// Schema references are left as written. Chains of references are
+    /// followed; a reference to a missing component or a cycle fails the
+    /// diff.
+    ///
+    /// References are made canonical first, see `Resolver::canonical`, so
+    /// `./models.yaml#/...` and `models.yaml#/...` are the same target.
+    /// When diffing files, the components other files are referenced for
+    /// are copied into the description as bundlers do, so a bundled and
+    /// an unbundled description compare equal.
+    pub resolve_references: bool,
+    /// Reports a removed path and an added path as a rename when they hold
+    /// the same path item, or operations sharing an `operationId`. Only
+    /// one-to-one matches are paired; see `PathsDiff::renamed`.
+    pub detect_path_renames: bool,
+    /// Matches paths regardless of the names of their template parameters,
+    /// so `/users/{userId}` and `/users/{id}` are compared as the same path
+    /// and only the renamed path parameters are reported. Exact matches
+    /// take precedence, and ambiguous matches are left as added and
+    /// removed paths.
+    pub ignore_path_param_names: bool,
+    /// Treats a request body as required when the schema of any of its
+    /// media types has required properties, even though the body's
+    /// `required` flag is false: such a body can only be omitted by
+    /// clients if servers ignore the schema. Transitions in this effective
+    /// value are reported as the body becoming required or optional.
+    /// Schemas referenced from `components/schemas` are looked up; other
+    /// references, such as ones into other files, count as having no
+    /// required properties.
+    pub infer_request_body_required: bool,
+    /// Drops the operations carrying any of these tags from both
+    /// descriptions before they are compared, such as `internal` ones kept
+    /// out of public reports. An operation is dropped even when its other
+    /// tags are not excluded, and a path item left without operations is
+    /// dropped too.
+    pub exclude_tags: Vec<String>,
+    /// Skips diffing descriptions that are structurally identical once
+    /// parsed, which is much faster on large descriptions that did not
+    /// change. The diff is the same either way.
+    pub short_circuit_identical: bool,
+    /// Rewrites equivalent OpenAPI 3.0 and 3.1 representations of schemas
+    /// into a single form before descriptions are compared. See
+    /// `normalize_spec`.
+    pub normalize: NormalizeOptions,
+    /// Whether schemas are JSON Schema, as of OpenAPI 3.1, rather than the
+    /// OpenAPI 3.0 subset. Keywords only JSON Schema defines, namely
+    /// `if`/`then`/`else`, `propertyNames`, `dependentRequired`,
+    /// `dependentSchemas`, `contentEncoding`, `contentMediaType`,
+    /// `$dynamicRef` and `$dynamicAnchor`, are only diffed for JSON Schema.
+    /// When unset, descriptions are diffed as JSON Schema unless both are
+    /// OpenAPI 3.0.
+    pub json_schema: Option<bool>,
+    /// The document level `security` of the descriptions, set while they
+    /// are compared.
+    pub(crate) security: InheritedSecurity,
+    /// The components of the descriptions, set while they are compared.
+    pub(crate) resolvers: Resolvers,
+}
+
+/// The normalizations `normalize_spec` applies, all of them by default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizeOptions {
+    /// Rewrites a 3.1 list of types with `"null"`, such as
+    /// `[string, "null"]`, as the type with the 3.0 `nullable: true`.
+    pub nullable: bool,
+    /// Rewrites a 3.1 list of a single type, such as `[string]`, as that
+    /// type.
+    pub type_lists: bool,
+    /// Rewrites the 3.0 boolean `exclusiveMinimum` and `exclusiveMaximum`,
+    /// which qualify `minimum` and `maximum`, as the 3.1 numeric bounds.
+    pub exclusive_bounds: bool,
+    /// Rewrites the 3.0 singular `example` of a schema as a 3.1 `examples`
+    /// list.
+    pub examples: bool,
+}
+
+impl NormalizeOptions {
+    /// Applies no normalization, so schemas are compared as written.
+    pub fn none() -> Self {
+        Self {
+            nullable: false,
+            type_lists: false,
+            exclusive_bounds: false,
+            examples: false,
+        }
+    }
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self {
+            nullable: true,
+            type_lists: true,
+            exclusive_bounds: true,
+            examples: true,
+        }
+    }
+}
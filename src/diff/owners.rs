@@ -0,0 +1,116 @@
+use super::changes::Change;
+use super::common::escape_pointer;
+use crate::openapi::{OpenAPI, ReferenceOr};
+use std::collections::{BTreeSet, HashMap};
+
+/// Owner of the changes that do not belong to an operation with a mapped
+/// tag, such as info changes or changes to untagged operations.
+pub const DEFAULT_OWNER: &str = "default";
+
+/// The tags of every operation, keyed by the JSON pointer of the operation
+/// such as `/paths/~1pets/get`. Tags of head operations take precedence,
+/// so removed operations keep the tags they had in base.
+#[derive(Debug, Default)]
+pub(crate) struct OperationTags {
+    tags: HashMap<String, Vec<String>>,
+}
+
+impl OperationTags {
+    pub(crate) fn from_specs(base: &OpenAPI, head: &OpenAPI) -> Self {
+        let mut operation_tags = Self::default();
+
+        for spec in [base, head] {
+            for (root, paths) in [("paths", &spec.paths), ("webhooks", &spec.webhooks)] {
+                for (path, path_item) in paths {
+                    if let ReferenceOr::Item(path_item) = path_item {
+                        for (method, operation) in path_item.operations() {
+                            operation_tags.tags.insert(
+                                format!("/{}/{}/{}", root, escape_pointer(path), method),
+                                operation.tags.clone(),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        operation_tags
+    }
+
+    /// The tags of the operation a change belongs to. Changes to a whole
+    /// path, such as a removed path, get the tags of all its operations.
+    fn tags_of(&self, change: &Change) -> BTreeSet<&String> {
+        let segments: Vec<_> = change.pointer.splitn(5, '/').collect();
+
+        match segments.as_slice() {
+            ["", root, path, method, ..] => {
+                let pointer = format!("/{}/{}/{}", root, path, method);
+                self.tags
+                    .get(&pointer)
+                    .map(|tags| tags.iter().collect())
+                    .unwrap_or_default()
+            }
+            ["", root, path] => {
+                let prefix = format!("/{}/{}/", root, path);
+                self.tags
+                    .iter()
+                    .filter(|(pointer, _)| pointer.starts_with(&prefix))
+                    .flat_map(|(_, tags)| tags)
+                    .collect()
+            }
+            _ => BTreeSet::new(),
+        }
+    }
+
+    /// The methods of the operations of the path a change belongs to, for
+    /// changes to a whole path such as a removed path.
+    pub(crate) fn methods_of(&self, change: &Change) -> BTreeSet<&str> {
+        let segments: Vec<_> = change.pointer.splitn(4, '/').collect();
+
+        match segments.as_slice() {
+            ["", root, path, ..] => {
+                let prefix = format!("/{}/{}/", root, path);
+                self.tags
+                    .keys()
+                    .filter_map(|pointer| pointer.strip_prefix(&prefix))
+                    .collect()
+            }
+            _ => BTreeSet::new(),
+        }
+    }
+
+    /// Groups breaking changes by the owners of the tags of their operation.
+    /// A change is listed under every owner of one of its tags, and under
+    /// `DEFAULT_OWNER` when none of its tags has an owner.
+    pub(crate) fn group_by_owner<'a>(
+        &self,
+        changes: impl Iterator<Item = Change>,
+        mapping: &'a HashMap<String, String>,
+    ) -> HashMap<String, Vec<Change>> {
+        let mut groups: HashMap<String, Vec<Change>> = HashMap::new();
+
+        for change in changes.filter(Change::is_breaking) {
+            let owners: BTreeSet<&'a String> = self
+                .tags_of(&change)
+                .into_iter()
+                .filter_map(|tag| mapping.get(tag))
+                .collect();
+
+            if owners.is_empty() {
+                groups
+                    .entry(String::from(DEFAULT_OWNER))
+                    .or_default()
+                    .push(change);
+            } else {
+                for owner in owners {
+                    groups
+                        .entry(owner.clone())
+                        .or_default()
+                        .push(change.clone());
+                }
+            }
+        }
+
+        groups
+    }
+}
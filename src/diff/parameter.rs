@@ -1,52 +1,98 @@
 use super::common::{BooleanDiff, StringDiff};
+use super::content::ContentDiff;
+use super::options::DiffOptions;
 use super::schema::SchemaDiff;
 use crate::openapi::{Parameter, ParameterData, ParameterSchemaOrContent};
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
 pub struct ParameterDiff {
-    required: Option<BooleanDiff>,
+    pub required: Option<BooleanDiff>,
     #[serde(rename = "in")]
-    in_change: Option<StringDiff>,
-    schema: Option<SchemaDiff>,
+    pub in_change: Option<StringDiff>,
+    pub deprecated: Option<BooleanDiff>,
+    pub schema: Option<SchemaDiff>,
+    /// Changes to the media types of a parameter described with `content`
+    /// rather than `schema`.
+    pub content: Option<ContentDiff>,
+    /// Set when a parameter switched between `schema` and `content`.
+    pub format_changed: Option<StringDiff>,
 }
 
 impl ParameterDiff {
     pub fn has_changes(&self) -> bool {
-        self.required.is_some() || self.in_change.is_some() || self.schema.is_some()
+        self.required.is_some()
+            || self.in_change.is_some()
+            || self.deprecated.is_some()
+            || self.schema.is_some()
+            || self.content.is_some()
+            || self.format_changed.is_some()
     }
 
-    pub fn from_params(base: &Parameter, head: &Parameter) -> Self {
+    pub fn from_params(base: &Parameter, head: &Parameter, options: &DiffOptions) -> Self {
         let base_parameter_data = Self::parameter_data(base);
         let head_parameter_data = Self::parameter_data(head);
 
-        let schema_diff =
-            if let ParameterSchemaOrContent::Schema(base_schema) = &base_parameter_data.format {
-                if let ParameterSchemaOrContent::Schema(head_schema) = &head_parameter_data.format {
-                    let diff = SchemaDiff::from_schemas(&base_schema, &head_schema);
-
-                    if diff.has_changes() {
-                        Some(diff)
-                    } else {
-                        None
-                    }
-                } else {
-                    panic!("Parameter content is not supported yet")
+        let mut schema = None;
+        let mut content = None;
+        let mut format_changed = None;
+
+        match (&base_parameter_data.format, &head_parameter_data.format) {
+            (
+                ParameterSchemaOrContent::Schema(base_schema),
+                ParameterSchemaOrContent::Schema(head_schema),
+            ) => {
+                let diff = SchemaDiff::from_schemas_with_options(base_schema, head_schema, options);
+                if diff.has_changes() {
+                    schema = Some(diff);
+                }
+            }
+            (
+                ParameterSchemaOrContent::Content(base_content),
+                ParameterSchemaOrContent::Content(head_content),
+            ) => {
+                let diff = ContentDiff::from_content(base_content, head_content, options);
+                if diff.has_changes() {
+                    content = Some(diff);
                 }
+            }
+            (base_format, head_format) => {
+                format_changed = StringDiff::from_strings(
+                    Self::format_name(base_format),
+                    Self::format_name(head_format),
+                );
+            }
+        }
+
+        // Path parameters are always required, so their `required` flag
+        // carries no information worth reporting.
+        let required =
+            if Self::parameter_type(base) == "path" || Self::parameter_type(head) == "path" {
+                None
             } else {
-                panic!("Parameter content is not supported yet")
+                BooleanDiff::from_bools(base_parameter_data.required, head_parameter_data.required)
             };
 
         Self {
-            required: BooleanDiff::from_bools(
-                base_parameter_data.required,
-                head_parameter_data.required,
+            required,
+            deprecated: BooleanDiff::from_bools(
+                base_parameter_data.deprecated.unwrap_or(false),
+                head_parameter_data.deprecated.unwrap_or(false),
             ),
             in_change: StringDiff::from_strings(
                 Self::parameter_type(base),
                 Self::parameter_type(head),
             ),
-            schema: schema_diff,
+            schema,
+            content,
+            format_changed,
+        }
+    }
+
+    fn format_name(format: &ParameterSchemaOrContent) -> String {
+        match format {
+            ParameterSchemaOrContent::Schema(_) => String::from("schema"),
+            ParameterSchemaOrContent::Content(_) => String::from("content"),
         }
     }
 
@@ -96,3 +142,235 @@ impl ParameterDiff {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::{Change, ChangeKind};
+    use crate::openapi::{OpenAPI, PathStyle, QueryStyle, ReferenceOr, Schema};
+    use std::collections::BTreeMap;
+
+    fn parameter_data(required: bool) -> ParameterData {
+        ParameterData {
+            name: String::from("id"),
+            description: None,
+            deprecated: None,
+            example: None,
+            examples: BTreeMap::default(),
+            extensions: BTreeMap::default(),
+            format: ParameterSchemaOrContent::Schema(ReferenceOr::Item(Schema::default())),
+            required,
+            explode: None,
+        }
+    }
+
+    fn query_parameter(required: bool) -> Parameter {
+        Parameter::Query {
+            parameter_data: parameter_data(required),
+            allow_empty_value: None,
+            allow_reserved: None,
+            style: QueryStyle::Form,
+        }
+    }
+
+    #[test]
+    fn query_parameter_became_required() {
+        let diff = ParameterDiff::from_params(
+            &query_parameter(false),
+            &query_parameter(true),
+            &DiffOptions::default(),
+        );
+
+        let required = diff.required.expect("required should have changed");
+        assert!(!required.from);
+        assert!(required.to);
+    }
+
+    #[test]
+    fn query_parameter_became_optional() {
+        let diff = ParameterDiff::from_params(
+            &query_parameter(true),
+            &query_parameter(false),
+            &DiffOptions::default(),
+        );
+
+        let required = diff.required.expect("required should have changed");
+        assert!(required.from);
+        assert!(!required.to);
+    }
+
+    #[test]
+    fn path_parameter_required_is_ignored() {
+        let base = Parameter::Path {
+            parameter_data: parameter_data(false),
+            style: PathStyle::Simple,
+        };
+        let head = Parameter::Path {
+            parameter_data: parameter_data(true),
+            style: PathStyle::Simple,
+        };
+
+        let diff = ParameterDiff::from_params(&base, &head, &DiffOptions::default());
+
+        assert!(diff.required.is_none());
+        assert!(!diff.has_changes());
+    }
+
+    /// The changes between two `GET /pets/{id}` operations whose `id`
+    /// parameter, located in `location`, goes from `required: from` to
+    /// `required: to`.
+    fn required_changes(location: &str, from: bool, to: bool) -> Vec<Change> {
+        let spec = |required: bool| -> OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets/{{id}}:
+    get:
+      parameters:
+        - name: id
+          in: {}
+          required: {}
+          schema:
+            type: string
+      responses: {{}}
+"#,
+                location, required
+            ))
+            .expect("Failed to parse description")
+        };
+
+        crate::diff::diff(spec(from), spec(to))
+            .expect("Failed to diff")
+            .changes()
+            .collect()
+    }
+
+    #[test]
+    fn required_transitions() {
+        let changes = required_changes("query", false, true);
+        assert_eq!(1, changes.len());
+        assert_eq!(ChangeKind::ParameterBecameRequired, changes[0].kind);
+        assert!(changes[0].is_breaking());
+
+        let changes = required_changes("query", true, false);
+        assert_eq!(1, changes.len());
+        assert_eq!(ChangeKind::ParameterBecameOptional, changes[0].kind);
+        assert!(!changes[0].is_breaking());
+
+        assert!(required_changes("path", false, true).is_empty());
+        assert!(required_changes("path", true, false).is_empty());
+    }
+
+    fn parameter(yaml: &str) -> Parameter {
+        serde_yaml::from_str(yaml).expect("Failed to parse parameter")
+    }
+
+    #[test]
+    fn schema_type_changed() {
+        let base = parameter(
+            r#"
+name: id
+in: query
+schema:
+  type: string
+"#,
+        );
+        let head = parameter(
+            r#"
+name: id
+in: query
+schema:
+  type: integer
+"#,
+        );
+
+        let diff = ParameterDiff::from_params(&base, &head, &DiffOptions::default());
+
+        let schema = diff.schema.expect("schema should have changed");
+        assert!(schema.type_changed.is_some());
+    }
+
+    #[test]
+    fn content_changed() {
+        let base = parameter(
+            r#"
+name: filter
+in: query
+content:
+  application/json:
+    schema:
+      type: object
+      properties:
+        color:
+          type: string
+"#,
+        );
+        let head = parameter(
+            r#"
+name: filter
+in: query
+content:
+  application/json:
+    schema:
+      type: object
+      required: [color]
+      properties:
+        color:
+          type: string
+"#,
+        );
+
+        let diff = ParameterDiff::from_params(&base, &head, &DiffOptions::default());
+
+        let content = diff.content.expect("content should have changed");
+        assert!(content.changed.contains_key("application/json"));
+        assert!(diff.format_changed.is_none());
+
+        let diff =
+            ParameterDiff::from_params(&query_parameter(false), &base, &DiffOptions::default());
+        let format = diff.format_changed.expect("format should have changed");
+        assert_eq!("schema", format.from);
+        assert_eq!("content", format.to);
+    }
+
+    #[test]
+    fn deprecated_transitions() {
+        let transitions = [
+            (None, Some(false), None),
+            (None, Some(true), Some(true)),
+            (Some(true), None, Some(false)),
+            (Some(false), Some(true), Some(true)),
+        ];
+
+        for (from, to, expected) in transitions {
+            let parameter = |deprecated| Parameter::Query {
+                parameter_data: ParameterData {
+                    deprecated,
+                    ..parameter_data(false)
+                },
+                allow_empty_value: None,
+                allow_reserved: None,
+                style: QueryStyle::Form,
+            };
+
+            let diff = ParameterDiff::from_params(
+                &parameter(from),
+                &parameter(to),
+                &DiffOptions::default(),
+            );
+
+            assert_eq!(
+                expected,
+                diff.deprecated.as_ref().map(|deprecated| deprecated.to),
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+            assert_eq!(expected.is_some(), diff.has_changes());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::options::DiffOptions;
 use super::parameter::ParameterDiff;
 use crate::openapi::{Parameter, ReferenceOr};
 use serde::Serialize;
@@ -8,6 +9,10 @@ pub struct ParametersDiff {
     pub added: Vec<ReferenceOr<Parameter>>,
     pub removed: Vec<ReferenceOr<Parameter>>,
     pub changed: HashMap<String, ParameterDiff>,
+    /// Whether the parameters that added references point to are required,
+    /// by reference. References to components missing from head are left
+    /// out.
+    pub added_references_required: HashMap<String, bool>,
 }
 
 impl ParametersDiff {
@@ -15,6 +20,11 @@ impl ParametersDiff {
         !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
     }
 
+    /// The number of parameters added, removed or changed.
+    pub fn change_count(&self) -> usize {
+        self.added.len() + self.removed.len() + self.changed.len()
+    }
+
     pub fn param_name(param: &Parameter) -> String {
         match param {
             Parameter::Query {
@@ -41,6 +51,7 @@ impl ParametersDiff {
     pub fn from_params(
         base: &Vec<ReferenceOr<Parameter>>,
         head: &Vec<ReferenceOr<Parameter>>,
+        options: &DiffOptions,
     ) -> Self {
         let mut added = vec![];
         let mut removed = vec![];
@@ -54,10 +65,10 @@ impl ParametersDiff {
                         ReferenceOr::Item(_) => false,
                     });
 
+                    // References are compared by name and not followed, see
+                    // `DiffOptions::resolve_references`.
                     match ref_match {
-                        Some(_param) => {
-                            panic!("Comparing changed parameter refs is not implemented yet");
-                        }
+                        Some(_param) => {}
                         None => removed.push(ref_or_param.clone()),
                     }
                 }
@@ -70,7 +81,7 @@ impl ParametersDiff {
                     match param_match {
                         Some(head_param) => {
                             if let ReferenceOr::Item(head_param) = head_param {
-                                let diff = ParameterDiff::from_params(param, head_param);
+                                let diff = ParameterDiff::from_params(param, head_param, options);
 
                                 if diff.has_changes() {
                                     changed.insert(Self::param_name(param), diff);
@@ -110,10 +121,25 @@ impl ParametersDiff {
             }
         }
 
+        let added_references_required = added
+            .iter()
+            .filter_map(|parameter| match parameter {
+                ReferenceOr::Reference { reference } => {
+                    let parameter = options.resolvers.head.resolve(parameter).ok()?;
+                    Some((
+                        reference.clone(),
+                        ParameterDiff::parameter_data(parameter).required,
+                    ))
+                }
+                ReferenceOr::Item(_) => None,
+            })
+            .collect();
+
         Self {
             added,
             removed,
             changed,
+            added_references_required,
         }
     }
 }
@@ -122,6 +148,7 @@ impl ParametersDiff {
 mod tests {
     use super::*;
 
+    use crate::diff::{ChangeKind, Confidence};
     use crate::openapi::{Operation, ParameterData, ParameterSchemaOrContent, QueryStyle, Schema};
     use std::collections::BTreeMap;
 
@@ -148,26 +175,24 @@ mod tests {
 
         head_operation.parameters.push(ReferenceOr::Item(my_param));
 
-        let diff =
-            ParametersDiff::from_params(&base_operation.parameters, &head_operation.parameters);
+        let diff = ParametersDiff::from_params(
+            &base_operation.parameters,
+            &head_operation.parameters,
+            &DiffOptions::default(),
+        );
 
         assert_eq!(diff.added.len(), 1);
         assert_eq!(diff.removed.len(), 0);
 
         match diff.added.first().unwrap() {
-            ReferenceOr::Item(p) => match p {
-                Parameter::Query {
-                    parameter_data,
-                    allow_reserved: _,
-                    style: _,
-                    allow_empty_value: _,
-                } => {
-                    assert_eq!(parameter_data.name, "myParam")
-                }
-                _ => {
-                    panic!("Unexpected parameter type")
-                }
-            },
+            ReferenceOr::Item(Parameter::Query {
+                parameter_data,
+                allow_reserved: _,
+                style: _,
+                allow_empty_value: _,
+            }) => {
+                assert_eq!(parameter_data.name, "myParam")
+            }
             _ => {
                 panic!("Unexpected parameter type")
             }
@@ -198,29 +223,132 @@ mod tests {
 
         base_operation.parameters.push(ReferenceOr::Item(my_param));
 
-        let diff =
-            ParametersDiff::from_params(&base_operation.parameters, &head_operation.parameters);
+        let diff = ParametersDiff::from_params(
+            &base_operation.parameters,
+            &head_operation.parameters,
+            &DiffOptions::default(),
+        );
 
         assert_eq!(diff.added.len(), 0);
         assert_eq!(diff.removed.len(), 1);
 
         match diff.removed.first().unwrap() {
-            ReferenceOr::Item(p) => match p {
-                Parameter::Query {
-                    parameter_data,
-                    allow_reserved: _,
-                    style: _,
-                    allow_empty_value: _,
-                } => {
-                    assert_eq!(parameter_data.name, "myParam")
-                }
-                _ => {
-                    panic!("Unexpected parameter type")
-                }
-            },
+            ReferenceOr::Item(Parameter::Query {
+                parameter_data,
+                allow_reserved: _,
+                style: _,
+                allow_empty_value: _,
+            }) => {
+                assert_eq!(parameter_data.name, "myParam")
+            }
             _ => {
                 panic!("Unexpected parameter type")
             }
         }
     }
+
+    #[test]
+    fn added_removed_and_changed_parameters() {
+        let query_param = |name: &str, required: bool| {
+            ReferenceOr::Item(Parameter::Query {
+                parameter_data: ParameterData {
+                    name: String::from(name),
+                    description: None,
+                    deprecated: None,
+                    example: None,
+                    examples: BTreeMap::default(),
+                    extensions: BTreeMap::default(),
+                    format: ParameterSchemaOrContent::Schema(ReferenceOr::Item(Schema::default())),
+                    required,
+                    explode: None,
+                },
+                allow_empty_value: None,
+                allow_reserved: None,
+                style: QueryStyle::Form,
+            })
+        };
+
+        let base = vec![
+            query_param("limit", false),
+            query_param("offset", false),
+            query_param("sort", false),
+        ];
+        let head = vec![
+            query_param("limit", true),
+            query_param("sort", false),
+            query_param("cursor", false),
+        ];
+
+        let diff = ParametersDiff::from_params(&base, &head, &DiffOptions::default());
+
+        assert!(diff.has_changes());
+        assert_eq!(1, diff.added.len());
+        assert_eq!(1, diff.removed.len());
+        assert_eq!(1, diff.changed.len());
+        assert_eq!(3, diff.change_count());
+
+        let diff = ParametersDiff::from_params(&base, &base, &DiffOptions::default());
+
+        assert!(!diff.has_changes());
+        assert_eq!(0, diff.change_count());
+    }
+
+    #[test]
+    fn added_references() {
+        let spec = |parameters: &str| -> crate::openapi::OpenAPI {
+            serde_yaml::from_str(&format!(
+                r##"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      parameters: {}
+      responses: {{}}
+components:
+  parameters:
+    Limit:
+      name: limit
+      in: query
+      schema:
+        type: integer
+    Tenant:
+      name: X-Tenant
+      in: header
+      required: true
+      schema:
+        type: string
+"##,
+                parameters
+            ))
+            .expect("Failed to parse description")
+        };
+        let added = |reference: &str| {
+            let head = spec(&format!(
+                r##"[{{ $ref: "#/components/parameters/{}" }}]"##,
+                reference
+            ));
+            let diff = crate::diff::diff(spec("[]"), head).expect("Failed to diff");
+            diff.changes()
+                .next()
+                .expect("parameter should have been added")
+        };
+
+        let optional = added("Limit");
+        assert_eq!(
+            ChangeKind::ParameterAdded { required: false },
+            optional.kind
+        );
+        assert_eq!(Confidence::Certain, optional.confidence);
+
+        let required = added("Tenant");
+        assert_eq!(ChangeKind::ParameterAdded { required: true }, required.kind);
+        assert!(required.is_breaking());
+
+        let missing = added("Missing");
+        assert!(missing.is_breaking());
+        assert_eq!(Confidence::Heuristic, missing.confidence);
+    }
 }
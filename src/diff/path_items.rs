@@ -1,6 +1,10 @@
+use super::common::{validate_entries, BooleanDiff, StringDiff};
 use super::operations::OperationDiff;
+use super::options::DiffOptions;
+use super::paths::template_parameters;
+use super::servers::ServersDiff;
 use super::DiffError;
-use crate::openapi::{Operation, PathItem, ReferenceOr};
+use crate::openapi::{Operation, Parameter, PathItem, ReferenceOr};
 use serde::Serialize;
 use std::collections::HashMap;
 
@@ -13,6 +17,13 @@ pub struct PathItemDiff {
     pub operations_added: Vec<OperationMethod>,
     pub operations_removed: Vec<OperationMethod>,
     pub operations_changed: HashMap<String, OperationDiff>,
+    /// Changes to the servers the path item overrides the description's
+    /// servers with, matched by URL.
+    pub servers: Option<ServersDiff>,
+    /// Template parameters renamed between base and head, e.g. from
+    /// `/users/{userId}` to `/users/{id}`. Only set with
+    /// `DiffOptions::ignore_path_param_names`.
+    pub path_parameters_renamed: Vec<StringDiff>,
 }
 
 impl PathItemDiff {
@@ -20,17 +31,62 @@ impl PathItemDiff {
         !self.operations_removed.is_empty()
             || !self.operations_added.is_empty()
             || !self.operations_changed.is_empty()
+            || self.servers.is_some()
+            || !self.path_parameters_renamed.is_empty()
     }
 
-    /// Diffs two sets of OpenAPI paths
+    /// Checks the invariants of the changed operations. See
+    /// `Diff::validate_self`.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        validate_entries(
+            "Operation",
+            self.operations_added
+                .iter()
+                .map(|(method, _)| method)
+                .collect(),
+            self.operations_removed
+                .iter()
+                .map(|(method, _)| method)
+                .collect(),
+            &self.operations_changed,
+            OperationDiff::has_changes,
+        )?;
+
+        if self
+            .servers
+            .as_ref()
+            .is_some_and(|servers| !servers.has_changes())
+        {
+            return Err(String::from("Servers are changed without changes"));
+        }
+
+        for (method, operation_diff) in &self.operations_changed {
+            operation_diff
+                .validate()
+                .map_err(|error| format!("{}: {}", method, error))?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether a path item is marked deprecated through the extension
+    /// configured in `DiffOptions::deprecated_extension`. OpenAPI itself
+    /// has no `deprecated` field on path items.
+    fn is_deprecated(path_item: &PathItem, options: &DiffOptions) -> bool {
+        match &options.deprecated_extension {
+            Some(extension) => {
+                path_item.extensions.get(extension) == Some(&serde_json::Value::Bool(true))
+            }
+            None => false,
+        }
+    }
+
+    /// Diffs two OpenAPI path items
     pub fn from_path_items(
         base: &ReferenceOr<PathItem>,
         head: &ReferenceOr<PathItem>,
+        options: &DiffOptions,
     ) -> Result<Self, DiffError> {
-        let mut operations_added = vec![];
-        let mut operations_removed = vec![];
-        let mut operations_changed: HashMap<String, OperationDiff> = HashMap::default();
-
         let base_path_item = match &base {
             ReferenceOr::Item(pi) => pi,
             ReferenceOr::Reference { reference } => {
@@ -51,114 +107,133 @@ impl PathItemDiff {
             }
         };
 
-        match &base_path_item.get {
-            Some(op) => match &head_path_item.get {
-                Some(head_op) => {
-                    let diff = OperationDiff::from_operations(op, head_op);
+        Ok(Self::from_items(base_path_item, head_path_item, options))
+    }
 
-                    if diff.has_changes() {
-                        operations_changed.insert(String::from("get"), diff);
-                    }
-                }
-                None => {
-                    // Removed
-                    operations_removed.push((String::from("get"), op.clone()));
-                }
-            },
-            None => match &head_path_item.get {
-                Some(head_op) => {
-                    // Added
-                    operations_added.push((String::from("get"), head_op.clone()));
-                }
-                None => {}
-            },
-        }
+    /// Diffs two path items whose paths only differ by the names of their
+    /// template parameters, such as `/users/{userId}` and `/users/{id}`.
+    /// The path parameters of head are matched to the ones of base by
+    /// position. See `DiffOptions::ignore_path_param_names`.
+    pub(crate) fn from_templated_path_items(
+        base_path: &str,
+        base: &ReferenceOr<PathItem>,
+        head_path: &str,
+        head: &ReferenceOr<PathItem>,
+        options: &DiffOptions,
+    ) -> Result<Self, DiffError> {
+        let renamed: Vec<_> = template_parameters(base_path)
+            .zip(template_parameters(head_path))
+            .filter_map(|(from, to)| StringDiff::from_strings(from.into(), to.into()))
+            .collect();
+
+        let head = match head {
+            ReferenceOr::Item(head) => {
+                ReferenceOr::Item(Self::rename_path_parameters(head, &renamed))
+            }
+            reference => reference.clone(),
+        };
 
-        match &base_path_item.post {
-            Some(op) => match &head_path_item.post {
-                Some(head_op) => {
-                    let diff = OperationDiff::from_operations(op, head_op);
+        let mut diff = Self::from_path_items(base, &head, options)?;
+        diff.path_parameters_renamed = renamed;
+        Ok(diff)
+    }
 
-                    if diff.has_changes() {
-                        operations_changed.insert(String::from("post"), diff);
+    /// Gives the path parameters of a head path item their base names.
+    fn rename_path_parameters(path_item: &PathItem, renamed: &[StringDiff]) -> PathItem {
+        let mut path_item = path_item.clone();
+
+        let rename = |parameters: &mut Vec<ReferenceOr<Parameter>>| {
+            for parameter in parameters {
+                if let ReferenceOr::Item(Parameter::Path { parameter_data, .. }) = parameter {
+                    if let Some(rename) = renamed
+                        .iter()
+                        .find(|rename| rename.to == parameter_data.name)
+                    {
+                        parameter_data.name = rename.from.clone();
                     }
                 }
-                None => {
-                    // Removed
-                    operations_removed.push((String::from("post"), op.clone()));
-                }
-            },
-            None => match &head_path_item.post {
-                Some(head_op) => {
-                    // Added
-                    operations_added.push((String::from("post"), head_op.clone()));
-                }
-                None => {}
-            },
+            }
+        };
+
+        rename(&mut path_item.parameters);
+        for (_, operation) in path_item.operations_mut() {
+            rename(&mut operation.parameters);
         }
 
-        match &base_path_item.put {
-            Some(op) => match &head_path_item.put {
-                Some(head_op) => {
-                    let diff = OperationDiff::from_operations(op, head_op);
+        path_item
+    }
 
-                    if diff.has_changes() {
-                        operations_changed.insert(String::from("put"), diff);
-                    }
-                }
-                None => {
-                    // Removed
-                    operations_removed.push((String::from("put"), op.clone()));
-                }
-            },
-            None => match &head_path_item.put {
-                Some(head_op) => {
-                    // Added
-                    operations_added.push((String::from("put"), head_op.clone()));
-                }
-                None => {}
-            },
-        }
+    /// Diffs two inline path items, such as the ones of a callback.
+    pub(crate) fn from_items(
+        base_path_item: &PathItem,
+        head_path_item: &PathItem,
+        options: &DiffOptions,
+    ) -> Self {
+        let mut operations_added = vec![];
+        let mut operations_removed = vec![];
+        let mut operations_changed: HashMap<String, OperationDiff> = HashMap::default();
 
-        match &base_path_item.patch {
-            Some(op) => match &head_path_item.patch {
-                Some(head_op) => {
-                    let diff = OperationDiff::from_operations(op, head_op);
+        let base_path_deprecated = Self::is_deprecated(base_path_item, options);
+        let head_path_deprecated = Self::is_deprecated(head_path_item, options);
+
+        let operations = [
+            ("get", &base_path_item.get, &head_path_item.get),
+            ("post", &base_path_item.post, &head_path_item.post),
+            ("put", &base_path_item.put, &head_path_item.put),
+            ("delete", &base_path_item.delete, &head_path_item.delete),
+            ("options", &base_path_item.options, &head_path_item.options),
+            ("head", &base_path_item.head, &head_path_item.head),
+            ("patch", &base_path_item.patch, &head_path_item.patch),
+            ("trace", &base_path_item.trace, &head_path_item.trace),
+        ];
+
+        for (method, base_op, head_op) in operations {
+            match (base_op, head_op) {
+                (Some(op), Some(head_op)) => {
+                    let mut diff = OperationDiff::from_operations(op, head_op, options);
+
+                    // A deprecated path item deprecates all of its operations,
+                    // so deprecation is compared on the effective flags.
+                    if base_path_deprecated || head_path_deprecated {
+                        diff.deprecated = BooleanDiff::from_bools(
+                            base_path_deprecated || op.deprecated.unwrap_or(false),
+                            head_path_deprecated || head_op.deprecated.unwrap_or(false),
+                        );
+                    }
 
                     if diff.has_changes() {
-                        operations_changed.insert(String::from("patch"), diff);
+                        operations_changed.insert(String::from(method), diff);
                     }
                 }
-                None => {
-                    // Removed
-                    operations_removed.push((String::from("patch"), op.clone()));
-                }
-            },
-            None => match &head_path_item.patch {
-                Some(head_op) => {
-                    // Added
-                    operations_added.push((String::from("patch"), head_op.clone()));
+                (Some(op), None) => operations_removed.push((String::from(method), op.clone())),
+                (None, Some(head_op)) => {
+                    operations_added.push((String::from(method), head_op.clone()))
                 }
-                None => {}
-            },
+                (None, None) => {}
+            }
         }
 
+        let servers_diff =
+            ServersDiff::from_servers(&base_path_item.servers, &head_path_item.servers);
 
-
-
-
-
-        Ok(Self {
+        Self {
             operations_added,
             operations_removed,
             operations_changed,
-        })
+            servers: if servers_diff.has_changes() {
+                Some(servers_diff)
+            } else {
+                None
+            },
+            path_parameters_renamed: vec![],
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::openapi::Server;
 
     #[test]
     fn operations_removed() {
@@ -171,7 +246,8 @@ mod tests {
 
         let head = ReferenceOr::Item(PathItem::default());
 
-        let diff = PathItemDiff::from_path_items(&base, &head).expect("Failed to diff paths");
+        let diff = PathItemDiff::from_path_items(&base, &head, &DiffOptions::default())
+            .expect("Failed to diff paths");
 
         assert_eq!(diff.operations_removed.len(), 1);
         let removed_op = diff.operations_removed.first().unwrap();
@@ -190,7 +266,8 @@ mod tests {
         let base = ReferenceOr::Item(PathItem::default());
         let head = ReferenceOr::Item(path_item);
 
-        let diff = PathItemDiff::from_path_items(&base, &head).expect("Failed to diff paths");
+        let diff = PathItemDiff::from_path_items(&base, &head, &DiffOptions::default())
+            .expect("Failed to diff paths");
 
         assert_eq!(diff.operations_removed.len(), 0);
         assert_eq!(diff.operations_added.len(), 1);
@@ -199,4 +276,91 @@ mod tests {
         assert_eq!(added_op.0, "get");
         assert_eq!(added_op.1.operation_id, Some("cats/get".into()));
     }
+
+    #[test]
+    fn deprecated_path_item_cascades_to_operations() {
+        let mut base_path_item = PathItem::default();
+        base_path_item.get = Some(Operation::default());
+        let mut head_path_item = base_path_item.clone();
+        head_path_item
+            .extensions
+            .insert("x-deprecated".into(), serde_json::Value::Bool(true));
+
+        let base = ReferenceOr::Item(base_path_item);
+        let head = ReferenceOr::Item(head_path_item);
+
+        let diff = PathItemDiff::from_path_items(&base, &head, &DiffOptions::default())
+            .expect("Failed to diff paths");
+        assert!(!diff.has_change());
+
+        let options = DiffOptions {
+            deprecated_extension: Some("x-deprecated".into()),
+            ..Default::default()
+        };
+        let diff =
+            PathItemDiff::from_path_items(&base, &head, &options).expect("Failed to diff paths");
+
+        let deprecated = diff.operations_changed["get"].deprecated.as_ref().unwrap();
+        assert!(!deprecated.from);
+        assert!(deprecated.to);
+    }
+
+    #[test]
+    fn operation_already_deprecated() {
+        let mut operation = Operation::default();
+        operation.deprecated = Some(true);
+        let mut base_path_item = PathItem::default();
+        base_path_item.get = Some(operation);
+        let mut head_path_item = base_path_item.clone();
+        head_path_item
+            .extensions
+            .insert("x-deprecated".into(), serde_json::Value::Bool(true));
+
+        let options = DiffOptions {
+            deprecated_extension: Some("x-deprecated".into()),
+            ..Default::default()
+        };
+        let diff = PathItemDiff::from_path_items(
+            &ReferenceOr::Item(base_path_item),
+            &ReferenceOr::Item(head_path_item),
+            &options,
+        )
+        .expect("Failed to diff paths");
+
+        assert!(!diff.has_change());
+    }
+
+    #[test]
+    fn servers_changed() {
+        let server = |url: &str, description: &str| Server {
+            url: url.into(),
+            description: Some(description.into()),
+            ..Default::default()
+        };
+        let mut base_path_item = PathItem::default();
+        base_path_item.servers = vec![
+            server("https://eu.pets.example", "Europe"),
+            server("https://us.pets.example", "US"),
+        ];
+        let mut head_path_item = PathItem::default();
+        head_path_item.servers = vec![
+            server("https://eu.pets.example", "European Union"),
+            server("https://asia.pets.example", "Asia"),
+        ];
+
+        let diff = PathItemDiff::from_path_items(
+            &ReferenceOr::Item(base_path_item),
+            &ReferenceOr::Item(head_path_item),
+            &DiffOptions::default(),
+        )
+        .expect("Failed to diff paths");
+
+        assert!(diff.has_change());
+        let servers = diff.servers.expect("servers should have changed");
+        assert_eq!("https://asia.pets.example", servers.added[0].url);
+        assert_eq!("https://us.pets.example", servers.removed[0].url);
+        assert!(servers.changed["https://eu.pets.example"]
+            .description
+            .is_some());
+    }
 }
@@ -1,59 +1,460 @@
+use super::common::validate_entries;
+use super::options::DiffOptions;
 use super::path_items::{PathItemDiff, PathItemPair};
 use super::DiffError;
-use crate::openapi::Paths;
+use crate::openapi::{PathItem, Paths, ReferenceOr, StatusCode};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// An operation whose primary (first) tag changed between base and head.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TagMove {
+    /// The operation, formatted as `METHOD /path`.
+    pub operation: String,
+    pub from_tag: Option<String>,
+    pub to_tag: Option<String>,
+}
+
+/// The declaration order of the paths shared by base and head, when their
+/// relative order changed. See `DiffOptions::report_path_reordering`.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct PathOrderDiff {
+    pub from: Vec<String>,
+    pub to: Vec<String>,
+}
+
+impl PathOrderDiff {
+    /// Compares the declaration order of two lists of paths, ignoring the
+    /// paths only one of them declares.
+    pub fn from_orders(base: &[String], head: &[String]) -> Option<Self> {
+        let from: Vec<_> = base
+            .iter()
+            .filter(|path| head.contains(path))
+            .cloned()
+            .collect();
+        let to: Vec<_> = head
+            .iter()
+            .filter(|path| base.contains(path))
+            .cloned()
+            .collect();
+
+        if from == to {
+            None
+        } else {
+            Some(Self { from, to })
+        }
+    }
+}
+
+/// A path removed from base and added to head under another name. See
+/// `DiffOptions::detect_path_renames`.
+#[derive(Debug, Serialize)]
+pub struct PathRename {
+    pub from: String,
+    pub to: String,
+    /// The changes between the path items, which are only paired up when
+    /// equal or when their operations share an `operationId`.
+    pub changed: Option<PathItemDiff>,
+}
 
 #[derive(Debug, Default, Serialize)]
 pub struct PathsDiff {
     pub added: Vec<PathItemPair>,
     pub removed: Vec<PathItemPair>,
     pub changed: HashMap<String, PathItemDiff>,
+    /// Paths removed from base and added to head under another name. Only
+    /// detected with `DiffOptions::detect_path_renames`, these paths are
+    /// then not listed as added or removed.
+    pub renamed: Vec<PathRename>,
 }
 
 impl PathsDiff {
     pub fn has_changes(&self) -> bool {
-        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+        !self.added.is_empty()
+            || !self.removed.is_empty()
+            || !self.changed.is_empty()
+            || !self.renamed.is_empty()
+    }
+
+    /// Checks the invariants of the changed paths and of everything nested
+    /// in them. See `Diff::validate_self`.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        validate_entries(
+            "Path",
+            self.added.iter().map(|(path, _)| path).collect(),
+            self.removed.iter().map(|(path, _)| path).collect(),
+            &self.changed,
+            PathItemDiff::has_change,
+        )?;
+
+        for rename in &self.renamed {
+            let listed = |paths: &[PathItemPair], path: &String| {
+                paths.iter().any(|(listed, _)| listed == path)
+            };
+            if listed(&self.removed, &rename.from) || listed(&self.added, &rename.to) {
+                return Err(format!(
+                    "Path `{}` renamed to `{}` is also added or removed",
+                    rename.from, rename.to
+                ));
+            }
+
+            if let Some(path_item_diff) = &rename.changed {
+                if !path_item_diff.has_change() {
+                    return Err(format!(
+                        "Path `{}` renamed to `{}` is listed as changed but has no change",
+                        rename.from, rename.to
+                    ));
+                }
+                path_item_diff
+                    .validate()
+                    .map_err(|error| format!("{}: {}", rename.to, error))?;
+            }
+        }
+
+        for (path, path_item_diff) in &self.changed {
+            path_item_diff
+                .validate()
+                .map_err(|error| format!("{}: {}", path, error))?;
+        }
+
+        Ok(())
+    }
+
+    /// Lists the error status codes newly documented on existing operations,
+    /// as `(path, method, status code)` tuples sorted by path and method.
+    ///
+    /// See `ResponsesDiff::error_codes_added`.
+    pub fn error_codes_added(&self) -> Vec<(String, String, StatusCode)> {
+        let mut result = vec![];
+
+        for (path, path_item_diff) in &self.changed {
+            for (method, operation_diff) in &path_item_diff.operations_changed {
+                if let Some(responses) = &operation_diff.responses {
+                    for status_code in responses.error_codes_added() {
+                        result.push((path.clone(), method.clone(), status_code.clone()));
+                    }
+                }
+            }
+        }
+
+        result.sort();
+        result
     }
 
-    /// Diffs two sets of OpenAPI paths
-    pub(crate) fn from_paths(base: &Paths, head: &Paths) -> Result<Self, DiffError> {
+    /// Lists every operation added to the description as `(path, method)`
+    /// tuples sorted by path and method, including all operations of added
+    /// paths.
+    pub fn operations_added(&self) -> Vec<(String, String)> {
+        let mut result = Self::path_operations(&self.added);
+
+        for (path, path_item_diff) in &self.changed {
+            for (method, _) in &path_item_diff.operations_added {
+                result.push((path.clone(), method.clone()));
+            }
+        }
+
+        result.sort();
+        result
+    }
+
+    /// Lists every operation removed from the description as
+    /// `(path, method)` tuples sorted by path and method, including all
+    /// operations of removed paths.
+    pub fn operations_removed(&self) -> Vec<(String, String)> {
+        let mut result = Self::path_operations(&self.removed);
+
+        for (path, path_item_diff) in &self.changed {
+            for (method, _) in &path_item_diff.operations_removed {
+                result.push((path.clone(), method.clone()));
+            }
+        }
+
+        result.sort();
+        result
+    }
+
+    fn path_operations(path_items: &[PathItemPair]) -> Vec<(String, String)> {
+        let mut result = vec![];
+
+        for (path, path_item) in path_items {
+            if let ReferenceOr::Item(path_item) = path_item {
+                for (method, _) in path_item.operations() {
+                    result.push((path.clone(), method.to_string()));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Lists the existing operations that moved to another primary tag,
+    /// sorted by path and method.
+    pub fn tag_moves(&self) -> Vec<TagMove> {
+        let mut moves = vec![];
+
+        for (path, path_item_diff) in &self.changed {
+            for (method, operation_diff) in &path_item_diff.operations_changed {
+                if let Some(primary_tag) = &operation_diff.primary_tag {
+                    moves.push((
+                        path.clone(),
+                        method.clone(),
+                        primary_tag.from.clone(),
+                        primary_tag.to.clone(),
+                    ));
+                }
+            }
+        }
+
+        moves.sort();
+        moves
+            .into_iter()
+            .map(|(path, method, from_tag, to_tag)| TagMove {
+                operation: format!("{} {}", method.to_uppercase(), path),
+                from_tag,
+                to_tag,
+            })
+            .collect()
+    }
+
+    /// Diffs two sets of OpenAPI paths with the default options.
+    pub fn from_paths(base: &Paths, head: &Paths) -> Result<Self, DiffError> {
+        Self::from_paths_with_options(base, head, &DiffOptions::default())
+    }
+
+    /// Diffs two sets of OpenAPI paths, applying `options` to every path
+    /// item and operation.
+    pub fn from_paths_with_options(
+        base: &Paths,
+        head: &Paths,
+        options: &DiffOptions,
+    ) -> Result<Self, DiffError> {
+        Self::from_paths_reusing(base, head, options, &Paths::default(), Self::default())
+    }
+
+    /// Like `from_paths_with_options`, given `previous`, the diff of `base` against
+    /// `previous_head`. Path items equal in `previous_head` and `head` are
+    /// not diffed again, their previous changes are reused instead.
+    pub(crate) fn from_paths_reusing(
+        base: &Paths,
+        head: &Paths,
+        options: &DiffOptions,
+        previous_head: &Paths,
+        mut previous: PathsDiff,
+    ) -> Result<Self, DiffError> {
         let mut paths_added = vec![];
         let mut paths_removed = vec![];
         let mut paths_changed: HashMap<String, PathItemDiff> = HashMap::new();
+        let mut templated_matches = HashSet::new();
 
         for (path, path_item) in base {
             match head.get(path) {
                 Some(head_path_item) => {
-                    let path_item_diff = PathItemDiff::from_path_items(path_item, head_path_item)?;
+                    let unchanged = previous_head.get(path) == Some(head_path_item);
+
+                    let path_item_diff = if unchanged {
+                        previous.changed.remove(path)
+                    } else {
+                        Some(PathItemDiff::from_path_items(
+                            path_item,
+                            head_path_item,
+                            options,
+                        )?)
+                    };
 
-                    if path_item_diff.has_change() {
-                        paths_changed.insert(path.clone(), path_item_diff);
+                    if let Some(path_item_diff) = path_item_diff {
+                        if path_item_diff.has_change() {
+                            paths_changed.insert(path.clone(), path_item_diff);
+                        }
                     }
                 }
-                None => paths_removed.push((path.clone(), path_item.clone())),
+                None => match Self::templated_match(path, base, head, options) {
+                    Some(head_path) => {
+                        let path_item_diff = PathItemDiff::from_templated_path_items(
+                            path,
+                            path_item,
+                            head_path,
+                            &head[head_path],
+                            options,
+                        )?;
+                        if path_item_diff.has_change() {
+                            paths_changed.insert(path.clone(), path_item_diff);
+                        }
+                        templated_matches.insert(head_path);
+                    }
+                    None => paths_removed.push((path.clone(), path_item.clone())),
+                },
             }
         }
 
         for (path, path_item) in head {
-            match base.get(path) {
-                Some(_) => {}
-                None => paths_added.push((path.clone(), path_item.clone())),
+            if !base.contains_key(path) && !templated_matches.contains(path) {
+                paths_added.push((path.clone(), path_item.clone()));
             }
         }
 
-        Ok(PathsDiff {
+        let mut diff = PathsDiff {
             added: paths_added,
             removed: paths_removed,
             changed: paths_changed,
-        })
+            renamed: vec![],
+        };
+
+        if options.detect_path_renames {
+            diff.detect_renames(options)?;
+        }
+
+        Ok(diff)
+    }
+
+    /// The head path a base path without an exact match in head matches
+    /// once the names of their template parameters are ignored, when
+    /// neither has any other candidate. See
+    /// `DiffOptions::ignore_path_param_names`.
+    fn templated_match<'a>(
+        path: &str,
+        base: &Paths,
+        head: &'a Paths,
+        options: &DiffOptions,
+    ) -> Option<&'a String> {
+        if !options.ignore_path_param_names {
+            return None;
+        }
+
+        let template = normalize_template(path);
+        let unmatched_base = base
+            .keys()
+            .filter(|base_path| !head.contains_key(*base_path))
+            .filter(|base_path| normalize_template(base_path) == template)
+            .count();
+        let mut candidates = head
+            .keys()
+            .filter(|head_path| !base.contains_key(*head_path))
+            .filter(|head_path| normalize_template(head_path) == template);
+
+        match (unmatched_base, candidates.next(), candidates.next()) {
+            (1, Some(head_path), None) => Some(head_path),
+            _ => None,
+        }
+    }
+
+    /// Moves the removed and added paths that only match each other to
+    /// `renamed`, along with the changes between their path items. Paths
+    /// match when they hold the same path item, or when their operations
+    /// share an `operationId`.
+    fn detect_renames(&mut self, options: &DiffOptions) -> Result<(), DiffError> {
+        let matches = |removed: &ReferenceOr<PathItem>, added: &ReferenceOr<PathItem>| {
+            if removed == added {
+                return true;
+            }
+
+            let removed_ids = Self::operation_ids(removed);
+            Self::operation_ids(added)
+                .iter()
+                .any(|id| removed_ids.contains(id))
+        };
+
+        let mut pairs = vec![];
+        for (removed_index, (_, removed_item)) in self.removed.iter().enumerate() {
+            let candidates: Vec<_> = (0..self.added.len())
+                .filter(|&added_index| matches(removed_item, &self.added[added_index].1))
+                .collect();
+
+            if let [added_index] = candidates[..] {
+                let rivals = self
+                    .removed
+                    .iter()
+                    .filter(|(_, item)| matches(item, &self.added[added_index].1))
+                    .count();
+
+                if rivals == 1 {
+                    pairs.push((removed_index, added_index));
+                }
+            }
+        }
+
+        for &(removed_index, added_index) in &pairs {
+            let (from, removed_item) = &self.removed[removed_index];
+            let (to, added_item) = &self.added[added_index];
+
+            let changed = if removed_item == added_item {
+                None
+            } else {
+                Some(PathItemDiff::from_path_items(
+                    removed_item,
+                    added_item,
+                    options,
+                )?)
+                .filter(PathItemDiff::has_change)
+            };
+
+            self.renamed.push(PathRename {
+                from: from.clone(),
+                to: to.clone(),
+                changed,
+            });
+        }
+
+        let (removed, added): (HashSet<_>, HashSet<_>) = pairs.into_iter().unzip();
+        self.removed = std::mem::take(&mut self.removed)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, path)| (!removed.contains(&index)).then_some(path))
+            .collect();
+        self.added = std::mem::take(&mut self.added)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, path)| (!added.contains(&index)).then_some(path))
+            .collect();
+
+        Ok(())
+    }
+
+    fn operation_ids(path_item: &ReferenceOr<PathItem>) -> Vec<&String> {
+        match path_item {
+            ReferenceOr::Item(path_item) => path_item
+                .operations()
+                .filter_map(|(_, operation)| operation.operation_id.as_ref())
+                .collect(),
+            ReferenceOr::Reference { .. } => vec![],
+        }
+    }
+}
+
+/// The names of the template parameters of a path, in order, such as
+/// `userId` for `/users/{userId}`.
+pub(crate) fn template_parameters(path: &str) -> impl Iterator<Item = &str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+}
+
+/// A path with the names of its template parameters left out, such as
+/// `/users/{}` for `/users/{userId}`.
+fn normalize_template(path: &str) -> String {
+    let mut parts = path.split('{');
+    let mut normalized = parts.next().unwrap_or_default().to_string();
+
+    for part in parts {
+        match part.split_once('}') {
+            Some((_, rest)) => {
+                normalized.push_str("{}");
+                normalized.push_str(rest);
+            }
+            None => {
+                normalized.push('{');
+                normalized.push_str(part);
+            }
+        }
     }
+
+    normalized
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::openapi::{PathItem, ReferenceOr};
+    use crate::diff::ChangeKind;
+    use crate::openapi::{Operation, PathItem, Response};
 
     #[test]
     fn path_added() {
@@ -79,4 +480,300 @@ mod tests {
         assert_eq!(diff.removed.len(), 1);
         assert_eq!(diff.removed.first().unwrap().0, "/cats");
     }
+
+    #[test]
+    fn path_parameter_renamed() {
+        let paths = |template: &str| -> Paths {
+            let path_item: PathItem = serde_yaml::from_str(&format!(
+                r#"
+get:
+  parameters:
+    - {{ name: {0}, in: path, required: true, schema: {{ type: string }} }}
+  responses: {{}}
+"#,
+                template
+            ))
+            .expect("Failed to parse path item");
+            let mut paths = Paths::default();
+            paths.insert(
+                format!("/users/{{{}}}", template),
+                ReferenceOr::Item(path_item),
+            );
+            paths
+        };
+        let options = DiffOptions {
+            ignore_path_param_names: true,
+            ..Default::default()
+        };
+
+        let diff = PathsDiff::from_paths_with_options(&paths("userId"), &paths("id"), &options)
+            .expect("Failed to diff paths");
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        let path_item_diff = &diff.changed["/users/{userId}"];
+        assert_eq!("userId", path_item_diff.path_parameters_renamed[0].from);
+        assert_eq!("id", path_item_diff.path_parameters_renamed[0].to);
+        assert!(path_item_diff.operations_changed.is_empty());
+
+        let diff =
+            PathsDiff::from_paths(&paths("userId"), &paths("id")).expect("Failed to diff paths");
+        assert_eq!("/users/{id}", diff.added[0].0);
+        assert_eq!("/users/{userId}", diff.removed[0].0);
+    }
+
+    fn path_item(summary: &str) -> ReferenceOr<PathItem> {
+        let mut path_item = PathItem::default();
+        path_item.get = Some(Operation {
+            summary: Some(summary.into()),
+            ..Default::default()
+        });
+        ReferenceOr::Item(path_item)
+    }
+
+    fn renames(base: &[(&str, &str)], head: &[(&str, &str)]) -> PathsDiff {
+        let paths = |paths: &[(&str, &str)]| -> Paths {
+            paths
+                .iter()
+                .map(|(path, summary)| (path.to_string(), path_item(summary)))
+                .collect()
+        };
+        let options = DiffOptions {
+            detect_path_renames: true,
+            ..Default::default()
+        };
+
+        PathsDiff::from_paths_with_options(&paths(base), &paths(head), &options)
+            .expect("Failed to diff paths")
+    }
+
+    #[test]
+    fn path_renamed() {
+        let diff = renames(&[("/cats", "List cats")], &[("/felines", "List cats")]);
+
+        assert_eq!(1, diff.renamed.len());
+        assert_eq!("/cats", diff.renamed[0].from);
+        assert_eq!("/felines", diff.renamed[0].to);
+        assert!(diff.renamed[0].changed.is_none());
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+
+        let mut base = Paths::default();
+        base.insert("/cats".into(), path_item("List cats"));
+        let mut head = Paths::default();
+        head.insert("/felines".into(), path_item("List cats"));
+        let diff = PathsDiff::from_paths(&base, &head).expect("Failed to diff paths");
+        assert!(diff.renamed.is_empty());
+        assert_eq!(1, diff.added.len());
+    }
+
+    #[test]
+    fn path_renamed_and_changed() {
+        let diff = renames(&[("/cats", "List cats")], &[("/felines", "List felines")]);
+
+        assert!(diff.renamed.is_empty());
+        assert_eq!("/felines", diff.added[0].0);
+        assert_eq!("/cats", diff.removed[0].0);
+    }
+
+    #[test]
+    fn path_renamed_by_operation_id() {
+        let mut base = Paths::default();
+        let mut head = Paths::default();
+        for (paths, path, summary) in [
+            (&mut base, "/cats", "List cats"),
+            (&mut head, "/felines", "List felines"),
+        ] {
+            let mut path_item = path_item(summary);
+            if let ReferenceOr::Item(path_item) = &mut path_item {
+                path_item.get.as_mut().unwrap().operation_id = Some("listCats".into());
+            }
+            paths.insert(path.into(), path_item);
+        }
+        let options = DiffOptions {
+            detect_path_renames: true,
+            ..Default::default()
+        };
+
+        let diff =
+            PathsDiff::from_paths_with_options(&base, &head, &options).expect("Failed to diff");
+
+        assert_eq!(1, diff.renamed.len());
+        assert_eq!("/cats", diff.renamed[0].from);
+        assert_eq!("/felines", diff.renamed[0].to);
+        let changed = diff.renamed[0]
+            .changed
+            .as_ref()
+            .expect("path item should have changed");
+        assert!(changed.operations_changed["get"].summary.is_some());
+
+        let changes = crate::diff::changes::collect_paths(&diff);
+        assert_eq!(
+            vec![ChangeKind::PathRenamed, ChangeKind::OperationSummaryChanged],
+            changes
+                .into_iter()
+                .map(|change| change.kind)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn ambiguous_renames() {
+        let diff = renames(
+            &[("/cats", "List cats"), ("/kittens", "List cats")],
+            &[("/felines", "List cats")],
+        );
+
+        assert!(diff.renamed.is_empty());
+        assert_eq!(1, diff.added.len());
+        assert_eq!(2, diff.removed.len());
+    }
+
+    #[test]
+    fn error_codes_added() {
+        let mut operation = Operation::default();
+        operation.responses.responses.insert(
+            StatusCode::Code(200),
+            ReferenceOr::Item(Response::default()),
+        );
+
+        let mut base_path_item = PathItem::default();
+        base_path_item.get = Some(operation.clone());
+
+        operation.responses.responses.insert(
+            StatusCode::Code(429),
+            ReferenceOr::Item(Response::default()),
+        );
+
+        let mut head_path_item = PathItem::default();
+        head_path_item.get = Some(operation);
+
+        let mut base = Paths::default();
+        base.insert("/cats".into(), ReferenceOr::Item(base_path_item));
+        let mut head = Paths::default();
+        head.insert("/cats".into(), ReferenceOr::Item(head_path_item));
+
+        let diff = PathsDiff::from_paths(&base, &head).expect("Failed to diff paths");
+
+        assert_eq!(
+            vec![("/cats".into(), "get".into(), StatusCode::Code(429))],
+            diff.error_codes_added()
+        );
+    }
+
+    #[test]
+    fn tag_moves() {
+        let mut base_operation = Operation::default();
+        base_operation.tags.push("Cats".into());
+        let mut head_operation = Operation::default();
+        head_operation.tags.push("Pets".into());
+        head_operation.tags.push("Cats".into());
+
+        let mut base_path_item = PathItem::default();
+        base_path_item.get = Some(base_operation);
+        let mut head_path_item = PathItem::default();
+        head_path_item.get = Some(head_operation);
+
+        let mut base = Paths::default();
+        base.insert("/cats".into(), ReferenceOr::Item(base_path_item));
+        let mut head = Paths::default();
+        head.insert("/cats".into(), ReferenceOr::Item(head_path_item));
+
+        let diff = PathsDiff::from_paths(&base, &head).expect("Failed to diff paths");
+
+        assert_eq!(
+            vec![TagMove {
+                operation: "GET /cats".into(),
+                from_tag: Some("Cats".into()),
+                to_tag: Some("Pets".into()),
+            }],
+            diff.tag_moves()
+        );
+    }
+
+    #[test]
+    fn operations_added_and_removed() {
+        let mut cats = PathItem::default();
+        cats.get = Some(Operation::default());
+        cats.delete = Some(Operation::default());
+        let mut dogs = PathItem::default();
+        dogs.get = Some(Operation::default());
+
+        let mut base = Paths::default();
+        base.insert("/cats".into(), ReferenceOr::Item(cats.clone()));
+        base.insert("/dogs".into(), ReferenceOr::Item(dogs.clone()));
+
+        cats.delete = None;
+        cats.post = Some(Operation::default());
+        let mut head = Paths::default();
+        head.insert("/birds".into(), ReferenceOr::Item(dogs));
+        head.insert("/cats".into(), ReferenceOr::Item(cats));
+
+        let diff = PathsDiff::from_paths(&base, &head).expect("Failed to diff paths");
+
+        assert_eq!(
+            vec![
+                ("/birds".into(), "get".into()),
+                ("/cats".into(), "post".into())
+            ],
+            diff.operations_added()
+        );
+        assert_eq!(
+            vec![
+                ("/cats".into(), "delete".into()),
+                ("/dogs".into(), "get".into())
+            ],
+            diff.operations_removed()
+        );
+    }
+
+    #[test]
+    fn options_reach_operations() {
+        let mut cats = PathItem::default();
+        cats.get = Some(Operation {
+            description: Some("Lists cats".into()),
+            ..Operation::default()
+        });
+
+        let mut base = Paths::default();
+        base.insert("/cats".into(), ReferenceOr::Item(cats.clone()));
+
+        cats.get.as_mut().unwrap().description = Some("Lists all cats".into());
+        let mut head = Paths::default();
+        head.insert("/cats".into(), ReferenceOr::Item(cats));
+
+        let diff = PathsDiff::from_paths(&base, &head).expect("Failed to diff paths");
+        assert!(diff.has_changes());
+
+        let options = DiffOptions {
+            ignore_descriptions: true,
+            ..DiffOptions::default()
+        };
+        let diff = PathsDiff::from_paths_with_options(&base, &head, &options)
+            .expect("Failed to diff paths");
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn path_order() {
+        let order =
+            |paths: &[&str]| -> Vec<String> { paths.iter().map(|path| path.to_string()).collect() };
+
+        assert_eq!(
+            None,
+            PathOrderDiff::from_orders(
+                &order(&["/cats", "/dogs"]),
+                &order(&["/birds", "/cats", "/dogs"])
+            )
+        );
+        assert_eq!(
+            Some(PathOrderDiff {
+                from: order(&["/cats", "/dogs"]),
+                to: order(&["/dogs", "/cats"]),
+            }),
+            PathOrderDiff::from_orders(
+                &order(&["/cats", "/birds", "/dogs"]),
+                &order(&["/dogs", "/cats"])
+            )
+        );
+    }
 }
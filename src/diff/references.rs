@@ -0,0 +1,370 @@
+use super::extensions::LITERALS;
+use super::{read_description, DiffError};
+use crate::openapi::{MediaType, OpenAPI, Operation, PathItem, ReferenceOr};
+use crate::resolver::{Component, Resolver};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+
+/// The components of the compared descriptions, set on `DiffOptions` while
+/// they are compared so sub-diffs can look up what references point to.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Resolvers {
+    pub base: Arc<Resolver>,
+    pub head: Arc<Resolver>,
+}
+
+impl Resolvers {
+    pub fn from_specs(base: &OpenAPI, head: &OpenAPI) -> Self {
+        Self {
+            base: Arc::new(Resolver::new(base)),
+            head: Arc::new(Resolver::new(head)),
+        }
+    }
+}
+
+/// Replaces the references to path items, and to the parameters, request
+/// bodies, responses, callbacks and multipart part headers of every
+/// operation, with the components they point to, so an inline definition
+/// and a reference to the same definition compare equal. See
+/// `DiffOptions::resolve_references`.
+pub(crate) fn resolve_references(spec: OpenAPI) -> Result<OpenAPI, DiffError> {
+    let mut document = serde_json::to_value(spec)?;
+    visit_references(&mut document, &mut |reference| {
+        *reference = Resolver::canonical(reference);
+    });
+    let mut spec = OpenAPI::deserialize(document)?;
+
+    let resolver = Resolver::new(&spec);
+
+    for path_item in spec.paths.values_mut().chain(spec.webhooks.values_mut()) {
+        resolve(path_item, &resolver)?;
+
+        if let ReferenceOr::Item(path_item) = path_item {
+            resolve_path_item(path_item, &resolver)?;
+        }
+    }
+
+    Ok(spec)
+}
+
+/// Copies the components that references into other files point to, such
+/// as `models.yaml#/components/schemas/Pet`, into the components of the
+/// description read from `path`, and makes the references local, as
+/// bundlers do. A bundled and an unbundled description then compare equal.
+/// References to other parts of a file are only made canonical.
+pub(crate) fn bundle(spec: OpenAPI, path: &Path) -> Result<OpenAPI, DiffError> {
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut document = serde_json::to_value(spec)?;
+    let mut pending = localize_references(&mut document, "");
+    let mut files: HashMap<String, Value> = HashMap::new();
+    let mut bundled: Vec<String> = vec![];
+
+    while let Some((file, pointer)) = pending.pop() {
+        let target = format!("{}#{}", file, pointer);
+        if bundled.contains(&target) {
+            continue;
+        }
+
+        if !files.contains_key(&file) {
+            let contents = read_description(&directory.join(&file))?;
+            files.insert(file.clone(), serde_yaml::from_str(&contents)?);
+        }
+
+        let mut component = files[&file]
+            .pointer(&pointer)
+            .cloned()
+            .ok_or_else(|| DiffError::UnresolvableReference(target.clone()))?;
+        pending.extend(localize_references(&mut component, &file));
+
+        match document.pointer(&pointer) {
+            Some(existing) if *existing != component => {
+                return Err(DiffError::UnsupportedFeature(format!(
+                    "bundling `{}` over a different component",
+                    target
+                )))
+            }
+            Some(_) => {}
+            None => insert(&mut document, &pointer, component),
+        }
+
+        bundled.push(target);
+    }
+
+    Ok(OpenAPI::deserialize(document)?)
+}
+
+/// Makes the references of a part of `file`, `""` for the description
+/// itself, relative to the description, and the references to components
+/// local. Returns the components to copy, as files and pointers.
+fn localize_references(value: &mut Value, file: &str) -> Vec<(String, String)> {
+    let mut components = vec![];
+
+    visit_references(value, &mut |reference| {
+        // Remote references are kept as written.
+        if reference.contains("://") {
+            return;
+        }
+
+        let (target, pointer) = match reference.find('#') {
+            Some(index) => (&reference[..index], &reference[index + 1..]),
+            None => (reference.as_str(), ""),
+        };
+
+        let target = match (target, file.rfind('/')) {
+            ("", _) => file.to_string(),
+            (target, _) if target.starts_with('/') => target.to_string(),
+            (target, Some(index)) => format!("{}/{}", &file[..index], target),
+            (target, None) => target.to_string(),
+        };
+        let target = Resolver::canonical(&target);
+
+        if target.is_empty() {
+            *reference = format!("#{}", pointer);
+        } else if is_component(pointer) {
+            components.push((target, pointer.to_string()));
+            *reference = format!("#{}", pointer);
+        } else {
+            *reference = Resolver::canonical(&format!("{}#{}", target, pointer));
+        }
+    });
+
+    components
+}
+
+/// Whether a pointer names a component, such as
+/// `/components/schemas/Pet`.
+fn is_component(pointer: &str) -> bool {
+    let segments: Vec<&str> = pointer.split('/').collect();
+    matches!(segments.as_slice(), ["", "components", _, _])
+}
+
+/// Inserts a component at its pointer, creating `components` and its
+/// section when needed.
+fn insert(document: &mut Value, pointer: &str, component: Value) {
+    let mut value = document;
+    let segments: Vec<String> = pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect();
+
+    for segment in segments {
+        if !value.is_object() {
+            *value = Value::Object(Default::default());
+        }
+        value = value
+            .as_object_mut()
+            .expect("Value was made an object")
+            .entry(segment)
+            .or_insert(Value::Null);
+    }
+
+    *value = component;
+}
+
+/// Calls `visit` with every `$ref` of a part of a description, skipping
+/// literal values such as examples.
+fn visit_references(value: &mut Value, visit: &mut impl FnMut(&mut String)) {
+    match value {
+        Value::Object(object) => {
+            for (key, field) in object.iter_mut() {
+                match field {
+                    Value::String(reference) if key == "$ref" => visit(reference),
+                    _ if LITERALS.contains(&key.as_str()) => {}
+                    Value::Array(_) if key == "examples" => {}
+                    field => visit_references(field, visit),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                visit_references(item, visit);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve_path_item(path_item: &mut PathItem, resolver: &Resolver) -> Result<(), DiffError> {
+    for parameter in &mut path_item.parameters {
+        resolve(parameter, resolver)?;
+    }
+
+    for (_, operation) in path_item.operations_mut() {
+        resolve_operation(operation, resolver)?;
+    }
+
+    Ok(())
+}
+
+fn resolve_operation(operation: &mut Operation, resolver: &Resolver) -> Result<(), DiffError> {
+    for parameter in &mut operation.parameters {
+        resolve(parameter, resolver)?;
+    }
+
+    if let Some(request_body) = &mut operation.request_body {
+        resolve(request_body, resolver)?;
+
+        if let ReferenceOr::Item(request_body) = request_body {
+            resolve_content(&mut request_body.content, resolver)?;
+        }
+    }
+
+    let responses = &mut operation.responses;
+    for response in responses
+        .responses
+        .values_mut()
+        .chain(responses.default.iter_mut())
+    {
+        resolve(response, resolver)?;
+
+        if let ReferenceOr::Item(response) = response {
+            resolve_content(&mut response.content, resolver)?;
+        }
+    }
+
+    for callback in operation.callbacks.values_mut() {
+        resolve(callback, resolver)?;
+
+        if let ReferenceOr::Item(callback) = callback {
+            for path_item in callback.values_mut() {
+                resolve_path_item(path_item, resolver)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves the headers of the multipart parts of each media type.
+fn resolve_content(
+    content: &mut BTreeMap<String, MediaType>,
+    resolver: &Resolver,
+) -> Result<(), DiffError> {
+    for media_type in content.values_mut() {
+        for encoding in media_type.encoding.values_mut() {
+            for header in encoding.headers.values_mut() {
+                resolve(header, resolver)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces a reference with the component it points to.
+fn resolve<T: Component + Clone>(
+    item: &mut ReferenceOr<T>,
+    resolver: &Resolver,
+) -> Result<(), DiffError> {
+    if let ReferenceOr::Reference { .. } = item {
+        *item = ReferenceOr::Item(resolver.resolve(item)?.clone());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::{diff_files, diff_files_with_options, diff_with_options, DiffOptions};
+    use std::path::PathBuf;
+
+    fn spec(parameter: &str) -> OpenAPI {
+        serde_yaml::from_str(&format!(
+            r##"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      parameters:
+        - {}
+      responses: {{}}
+components:
+  parameters:
+    PageSize:
+      $ref: "#/components/parameters/Limit"
+    Limit:
+      name: limit
+      in: query
+      schema:
+        type: integer
+    Loop:
+      $ref: "#/components/parameters/Loop"
+"##,
+            parameter
+        ))
+        .expect("Failed to parse description")
+    }
+
+    #[test]
+    fn inline_and_referenced_parameters() {
+        let options = DiffOptions {
+            resolve_references: true,
+            ..Default::default()
+        };
+
+        let diff = diff_with_options(
+            spec("{ name: limit, in: query, schema: { type: integer } }"),
+            spec(r##"$ref: "#/components/parameters/PageSize""##),
+            &options,
+        )
+        .expect("Failed to diff");
+
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn referenced_components_file() {
+        let base = PathBuf::from("fixtures/pet-store.yaml");
+        let refs = PathBuf::from("fixtures/pet-store-refs.yaml");
+
+        let options = DiffOptions {
+            resolve_references: true,
+            ..Default::default()
+        };
+        let diff =
+            diff_files_with_options(base.clone(), refs.clone(), &options).expect("Failed to diff");
+        assert!(diff.paths.is_none());
+
+        // Unresolved references are compared by name.
+        let diff = diff_files(base, refs.clone()).expect("Failed to diff");
+        assert!(diff.paths.is_some());
+        let diff = diff_files(refs.clone(), refs).expect("Failed to diff");
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn bundled_and_unbundled_descriptions() {
+        let unbundled = PathBuf::from("fixtures/bundle/openapi.yaml");
+        let bundled = PathBuf::from("fixtures/bundle/bundled.yaml");
+
+        let options = DiffOptions {
+            resolve_references: true,
+            ..Default::default()
+        };
+        let diff = diff_files_with_options(unbundled.clone(), bundled.clone(), &options)
+            .expect("Failed to diff");
+        assert!(!diff.has_changes());
+
+        let diff = diff_files(unbundled, bundled).expect("Failed to diff");
+        assert!(diff.has_changes());
+    }
+
+    #[test]
+    fn unresolved_and_cyclic_references() {
+        let error = resolve_references(spec(r##"$ref: "#/components/parameters/Missing""##))
+            .expect_err("Resolved a missing parameter");
+        assert!(matches!(error, DiffError::UnresolvableReference(_)));
+
+        let error = resolve_references(spec(r##"$ref: "#/components/parameters/Loop""##))
+            .expect_err("Resolved a cyclic parameter");
+        assert!(matches!(error, DiffError::ReferenceCycle(_)));
+    }
+}
@@ -1,48 +1,101 @@
+use super::common::{BooleanDiff, OptionalStringDiff};
 use super::content::ContentDiff;
+use super::options::DiffOptions;
 use crate::openapi::{ReferenceOr, RequestBody};
+use crate::resolver::Resolver;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
 pub struct RequestBodyDiff {
     pub added: Option<ReferenceOr<RequestBody>>,
     pub removed: Option<ReferenceOr<RequestBody>>,
+    /// Whether clients have to send the added body. A referenced body
+    /// counts as required, since its component is not looked up.
+    pub added_required: bool,
+    /// The component the body refers to, when either side is a reference.
+    /// References are compared by name and not followed.
+    pub reference: Option<OptionalStringDiff>,
+    /// Changes to whether the body is required, which is inferred from its
+    /// schemas with `DiffOptions::infer_request_body_required`.
+    pub required: Option<BooleanDiff>,
     pub content_changed: Option<ContentDiff>,
 }
 
 impl RequestBodyDiff {
     pub fn has_changes(&self) -> bool {
-        self.added.is_some() || self.removed.is_some() || self.content_changed.is_some()
+        self.added.is_some()
+            || self.removed.is_some()
+            || self.reference.is_some()
+            || self.required.is_some()
+            || self.content_changed.is_some()
+    }
+
+    /// Whether clients have to send the body. With
+    /// `DiffOptions::infer_request_body_required`, a body is also required
+    /// when the schema of any of its media types has required properties.
+    /// Referenced schemas are looked up with `resolver`.
+    fn is_required(request_body: &RequestBody, resolver: &Resolver, options: &DiffOptions) -> bool {
+        request_body.required.unwrap_or(false)
+            || (options.infer_request_body_required
+                && request_body.content.values().any(|media_type| {
+                    media_type
+                        .schema
+                        .as_ref()
+                        .and_then(|schema| resolver.resolve(schema).ok())
+                        .and_then(|schema| schema.required.as_ref())
+                        .is_some_and(|required| !required.is_empty())
+                }))
+    }
+
+    fn reference(request_body: &ReferenceOr<RequestBody>) -> Option<String> {
+        match request_body {
+            ReferenceOr::Reference { reference } => Some(reference.clone()),
+            ReferenceOr::Item(_) => None,
+        }
     }
 
     pub fn from_request_bodies(
         base: &Option<ReferenceOr<RequestBody>>,
         head: &Option<ReferenceOr<RequestBody>>,
+        options: &DiffOptions,
     ) -> Self {
         match base {
             Some(base) => match head {
                 Some(head) => {
-                    let derefed_base = match &base {
-                        ReferenceOr::Item(bc) => bc,
-                        ReferenceOr::Reference { reference: _ } => {
-                            panic!("$ref not supported yet");
-                        }
-                    };
-
-                    let derefed_head = match &head {
-                        ReferenceOr::Item(pi) => pi,
-                        ReferenceOr::Reference { reference: _ } => {
-                            panic!("$ref not supported yet");
+                    let (derefed_base, derefed_head) = match (base, head) {
+                        (ReferenceOr::Item(base), ReferenceOr::Item(head)) => (base, head),
+                        _ => {
+                            return Self {
+                                added: None,
+                                removed: None,
+                                added_required: false,
+                                reference: OptionalStringDiff::from_strings(
+                                    &Self::reference(base),
+                                    &Self::reference(head),
+                                ),
+                                required: None,
+                                content_changed: None,
+                            }
                         }
                     };
 
                     let mut diff = Self {
                         added: None,
                         removed: None,
+                        added_required: false,
+                        reference: None,
+                        required: BooleanDiff::from_bools(
+                            Self::is_required(derefed_base, &options.resolvers.base, options),
+                            Self::is_required(derefed_head, &options.resolvers.head, options),
+                        ),
                         content_changed: None,
                     };
 
-                    let content_diff =
-                        ContentDiff::from_content(&derefed_base.content, &derefed_head.content);
+                    let content_diff = ContentDiff::from_content(
+                        &derefed_base.content,
+                        &derefed_head.content,
+                        options,
+                    );
 
                     if content_diff.has_changes() {
                         diff.content_changed = Some(content_diff);
@@ -53,6 +106,9 @@ impl RequestBodyDiff {
                 None => Self {
                     added: None,
                     removed: Some(base.clone()),
+                    added_required: false,
+                    reference: None,
+                    required: None,
                     content_changed: None,
                 },
             },
@@ -60,11 +116,22 @@ impl RequestBodyDiff {
                 Some(head) => Self {
                     added: Some(head.clone()),
                     removed: None,
+                    added_required: match head {
+                        ReferenceOr::Item(head) => {
+                            Self::is_required(head, &options.resolvers.head, options)
+                        }
+                        ReferenceOr::Reference { .. } => true,
+                    },
+                    reference: None,
+                    required: None,
                     content_changed: None,
                 },
                 None => Self {
                     added: None,
                     removed: None,
+                    added_required: false,
+                    reference: None,
+                    required: None,
                     content_changed: None,
                 },
             },
@@ -75,18 +142,190 @@ impl RequestBodyDiff {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diff::{ChangeKind, Confidence};
     use std::path::PathBuf;
 
     #[test]
     fn property_removed() {
-        let base_body_string = std::fs::read_to_string(PathBuf::from("fixtures/request_body.yaml")).expect("Failed to parse request body");
-        let head_body_string = std::fs::read_to_string(PathBuf::from("fixtures/request_body_property_removed.yaml")).expect("Failed to parse request body");
-        let base_body: RequestBody = serde_yaml::from_str(&base_body_string).expect("Failed to parse request body");
-        let head_body: RequestBody = serde_yaml::from_str(&head_body_string).expect("Failed to parse request body");
+        let base_body_string = std::fs::read_to_string(PathBuf::from("fixtures/request_body.yaml"))
+            .expect("Failed to parse request body");
+        let head_body_string =
+            std::fs::read_to_string(PathBuf::from("fixtures/request_body_property_removed.yaml"))
+                .expect("Failed to parse request body");
+        let base_body: RequestBody =
+            serde_yaml::from_str(&base_body_string).expect("Failed to parse request body");
+        let head_body: RequestBody =
+            serde_yaml::from_str(&head_body_string).expect("Failed to parse request body");
 
-        let diff = RequestBodyDiff::from_request_bodies(&Some(ReferenceOr::Item(base_body)), &Some(ReferenceOr::Item(head_body)));
+        let diff = RequestBodyDiff::from_request_bodies(
+            &Some(ReferenceOr::Item(base_body)),
+            &Some(ReferenceOr::Item(head_body)),
+            &DiffOptions::default(),
+        );
 
         assert!(diff.has_changes());
         assert!(!diff.content_changed.unwrap().changed.is_empty());
     }
+
+    #[test]
+    fn became_required() {
+        let body = |required| {
+            Some(ReferenceOr::Item(RequestBody {
+                required: Some(required),
+                ..Default::default()
+            }))
+        };
+
+        let diff = RequestBodyDiff::from_request_bodies(
+            &body(false),
+            &body(true),
+            &DiffOptions::default(),
+        );
+
+        let required = diff.required.expect("required should have changed");
+        assert!(!required.from);
+        assert!(required.to);
+    }
+
+    #[test]
+    fn added() {
+        let spec = |request_body: &str| -> crate::openapi::OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    post:
+      {}
+      responses: {{}}
+"#,
+                request_body
+            ))
+            .expect("Failed to parse description")
+        };
+        let added = |request_body: &str| {
+            let diff = crate::diff::diff(spec(""), spec(request_body)).expect("Failed to diff");
+            diff.changes()
+                .next()
+                .expect("request body should have been added")
+        };
+
+        let required = added("requestBody: { required: true, content: {} }");
+        assert_eq!(
+            ChangeKind::RequestBodyAdded { required: true },
+            required.kind
+        );
+        assert!(required.is_breaking());
+
+        let optional = added("requestBody: { content: {} }");
+        assert_eq!(
+            ChangeKind::RequestBodyAdded { required: false },
+            optional.kind
+        );
+        assert!(!optional.is_breaking());
+
+        let referenced = added("requestBody: { $ref: '#/components/requestBodies/Pet' }");
+        assert!(referenced.is_breaking());
+        assert_eq!(Confidence::Heuristic, referenced.confidence);
+    }
+
+    #[test]
+    fn references() {
+        let pet = Some(ReferenceOr::ref_("#/components/requestBodies/Pet"));
+        let inline = Some(ReferenceOr::Item(RequestBody::default()));
+
+        let diff = RequestBodyDiff::from_request_bodies(&pet, &pet, &DiffOptions::default());
+        assert!(!diff.has_changes());
+
+        let diff = RequestBodyDiff::from_request_bodies(&pet, &inline, &DiffOptions::default());
+        let reference = diff.reference.expect("reference should have changed");
+        assert_eq!(
+            Some("#/components/requestBodies/Pet".into()),
+            reference.from
+        );
+        assert_eq!(None, reference.to);
+    }
+
+    #[test]
+    fn inferred_required() {
+        let body: RequestBody = serde_yaml::from_str(
+            r#"
+content:
+  application/json:
+    schema:
+      type: object
+      properties:
+        name:
+          type: string
+"#,
+        )
+        .expect("Failed to parse request body");
+        let mut head = body.clone();
+        if let Some(ReferenceOr::Item(schema)) =
+            &mut head.content.get_mut("application/json").unwrap().schema
+        {
+            schema.required = Some(vec!["name".into()]);
+        }
+        let base = Some(ReferenceOr::Item(body));
+        let head = Some(ReferenceOr::Item(head));
+
+        let diff = RequestBodyDiff::from_request_bodies(&base, &head, &DiffOptions::default());
+        assert!(diff.required.is_none());
+
+        let options = DiffOptions {
+            infer_request_body_required: true,
+            ..Default::default()
+        };
+        let diff = RequestBodyDiff::from_request_bodies(&base, &head, &options);
+        let required = diff.required.expect("required should have changed");
+        assert!(!required.from);
+        assert!(required.to);
+    }
+
+    #[test]
+    fn inferred_required_references() {
+        let spec = |required: &str| -> crate::openapi::OpenAPI {
+            serde_yaml::from_str(&format!(
+                r##"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: "#/components/schemas/Pet"
+      responses: {{}}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+      {}
+"##,
+                required
+            ))
+            .expect("Failed to parse description")
+        };
+
+        let options = DiffOptions {
+            infer_request_body_required: true,
+            ..Default::default()
+        };
+        let diff = crate::diff::diff_with_options(spec(""), spec("required: [name]"), &options)
+            .expect("Failed to diff");
+
+        assert!(diff
+            .changes()
+            .any(|change| change.kind == ChangeKind::RequestBodyBecameRequired));
+    }
 }
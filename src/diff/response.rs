@@ -1,41 +1,121 @@
+use super::common::OptionalStringDiff;
 use super::content::ContentDiff;
+use super::options::DiffOptions;
 use crate::openapi::{ReferenceOr, Response};
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
 pub struct ResponseDiff {
+    /// The component the response refers to, when either side is a
+    /// reference. References are compared by name and not followed.
+    pub reference: Option<OptionalStringDiff>,
+    pub description: Option<OptionalStringDiff>,
     pub content: Option<ContentDiff>,
 }
 
 impl ResponseDiff {
     pub fn has_changes(&self) -> bool {
-        self.content.is_some()
+        self.reference.is_some() || self.description.is_some() || self.content.is_some()
     }
 
-    pub fn from_responses(base: &ReferenceOr<Response>, head: &ReferenceOr<Response>) -> Self {
-        let base_response = match &base {
-            ReferenceOr::Item(i) => i,
-            ReferenceOr::Reference { reference: _ } => {
-                panic!("$ref not supported yet");
-            }
-        };
+    fn reference(response: &ReferenceOr<Response>) -> Option<String> {
+        match response {
+            ReferenceOr::Reference { reference } => Some(reference.clone()),
+            ReferenceOr::Item(_) => None,
+        }
+    }
 
-        let head_response = match &head {
-            ReferenceOr::Item(i) => i,
-            ReferenceOr::Reference { reference: _ } => {
-                panic!("$ref not supported yet");
+    pub fn from_responses(
+        base: &ReferenceOr<Response>,
+        head: &ReferenceOr<Response>,
+        options: &DiffOptions,
+    ) -> Self {
+        let (base_response, head_response) = match (base, head) {
+            (ReferenceOr::Item(base), ReferenceOr::Item(head)) => (base, head),
+            _ => {
+                return Self {
+                    reference: OptionalStringDiff::from_strings(
+                        &Self::reference(base),
+                        &Self::reference(head),
+                    ),
+                    description: None,
+                    content: None,
+                }
             }
         };
 
         let content_diff =
-            ContentDiff::from_content(&base_response.content, &head_response.content);
+            ContentDiff::from_content(&base_response.content, &head_response.content, options);
 
-        if content_diff.has_changes() {
-            Self {
-                content: Some(content_diff),
-            }
+        let description = if options.ignore_descriptions {
+            None
         } else {
-            Self { content: None }
+            OptionalStringDiff::from_strings(&base_response.description, &head_response.description)
+        };
+
+        Self {
+            reference: None,
+            description,
+            content: if content_diff.has_changes() {
+                Some(content_diff)
+            } else {
+                None
+            },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(description: &str) -> ReferenceOr<Response> {
+        ReferenceOr::Item(Response {
+            description: Some(description.into()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn description_changed() {
+        let diff = ResponseDiff::from_responses(
+            &response("A pet."),
+            &response("The requested pet."),
+            &DiffOptions::default(),
+        );
+
+        let description = diff.description.expect("description should have changed");
+        assert_eq!(Some("A pet.".into()), description.from);
+        assert_eq!(Some("The requested pet.".into()), description.to);
+    }
+
+    #[test]
+    fn ignore_descriptions() {
+        let options = DiffOptions {
+            ignore_descriptions: true,
+            ..Default::default()
+        };
+
+        let diff = ResponseDiff::from_responses(
+            &response("A pet."),
+            &response("The requested pet."),
+            &options,
+        );
+
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn references() {
+        let error = ReferenceOr::ref_("#/components/responses/Error");
+
+        let diff = ResponseDiff::from_responses(&error, &error, &DiffOptions::default());
+        assert!(!diff.has_changes());
+
+        let diff =
+            ResponseDiff::from_responses(&error, &response("A pet."), &DiffOptions::default());
+        let reference = diff.reference.expect("reference should have changed");
+        assert_eq!(Some("#/components/responses/Error".into()), reference.from);
+        assert_eq!(None, reference.to);
+    }
+}
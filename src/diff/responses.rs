@@ -1,3 +1,4 @@
+use super::options::DiffOptions;
 use super::response::ResponseDiff;
 use crate::openapi::{ReferenceOr, Response, Responses, StatusCode};
 use serde::Serialize;
@@ -10,14 +11,38 @@ pub struct ResponsesDiff {
     pub added: Vec<ResponseCodePair>,
     pub removed: Vec<ResponseCodePair>,
     pub changed: HashMap<StatusCode, ResponseDiff>,
+    /// The `default` response, covering status codes not declared otherwise.
+    pub default_added: Option<ReferenceOr<Response>>,
+    pub default_removed: Option<ReferenceOr<Response>>,
+    pub default_changed: Option<ResponseDiff>,
 }
 
 impl ResponsesDiff {
     pub fn has_changes(&self) -> bool {
-        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+        !self.added.is_empty()
+            || !self.removed.is_empty()
+            || !self.changed.is_empty()
+            || self.default_added.is_some()
+            || self.default_removed.is_some()
+            || self.default_changed.is_some()
     }
 
-    pub fn from_responses(base: &Responses, head: &Responses) -> Self {
+    /// Lists the newly documented error responses (4XX and 5XX status codes).
+    ///
+    /// Documenting a new error is not a breaking change, but it is notable
+    /// since clients may want to start handling it.
+    pub fn error_codes_added(&self) -> Vec<&StatusCode> {
+        let mut codes: Vec<_> = self
+            .added
+            .iter()
+            .map(|(status_code, _)| status_code)
+            .filter(|status_code| status_code.is_error())
+            .collect();
+        codes.sort();
+        codes
+    }
+
+    pub fn from_responses(base: &Responses, head: &Responses, options: &DiffOptions) -> Self {
         let mut responses_added = vec![];
         let mut responses_removed = vec![];
         let mut responses_changed: HashMap<StatusCode, ResponseDiff> = HashMap::new();
@@ -25,7 +50,8 @@ impl ResponsesDiff {
         for (status_code, response) in &base.responses {
             match head.responses.get(status_code) {
                 Some(head_response) => {
-                    let response_diff = ResponseDiff::from_responses(&response, &head_response);
+                    let response_diff =
+                        ResponseDiff::from_responses(response, head_response, options);
 
                     if response_diff.has_changes() {
                         responses_changed.insert(status_code.clone(), response_diff);
@@ -42,10 +68,84 @@ impl ResponsesDiff {
             }
         }
 
-        Self {
+        let mut diff = Self {
             added: responses_added,
             removed: responses_removed,
             changed: responses_changed,
+            default_added: None,
+            default_removed: None,
+            default_changed: None,
+        };
+
+        match (&base.default, &head.default) {
+            (Some(base), Some(head)) => {
+                let response_diff = ResponseDiff::from_responses(base, head, options);
+                if response_diff.has_changes() {
+                    diff.default_changed = Some(response_diff);
+                }
+            }
+            (Some(base), None) => diff.default_removed = Some(base.clone()),
+            (None, Some(head)) => diff.default_added = Some(head.clone()),
+            (None, None) => {}
         }
+
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_codes_added() {
+        let mut base = Responses::default();
+        base.responses.insert(
+            StatusCode::Code(200),
+            ReferenceOr::Item(Response::default()),
+        );
+
+        let mut head = base.clone();
+        head.responses.insert(
+            StatusCode::Code(429),
+            ReferenceOr::Item(Response::default()),
+        );
+        head.responses.insert(
+            StatusCode::Code(201),
+            ReferenceOr::Item(Response::default()),
+        );
+        head.responses
+            .insert(StatusCode::Range(5), ReferenceOr::Item(Response::default()));
+
+        let diff = ResponsesDiff::from_responses(&base, &head, &DiffOptions::default());
+
+        assert_eq!(diff.added.len(), 3);
+        assert_eq!(
+            vec![&StatusCode::Code(429), &StatusCode::Range(5)],
+            diff.error_codes_added()
+        );
+    }
+
+    #[test]
+    fn default_response() {
+        let base = Responses::default();
+        let mut head = Responses::default();
+        head.default = Some(ReferenceOr::Item(Response::default()));
+
+        let diff = ResponsesDiff::from_responses(&base, &head, &DiffOptions::default());
+        assert!(diff.default_added.is_some());
+        assert!(diff.has_changes());
+
+        let diff = ResponsesDiff::from_responses(&head, &base, &DiffOptions::default());
+        assert!(diff.default_removed.is_some());
+
+        let mut changed = head.clone();
+        changed.default = Some(ReferenceOr::Item(Response {
+            description: Some("An unexpected error.".into()),
+            ..Default::default()
+        }));
+
+        let diff = ResponsesDiff::from_responses(&head, &changed, &DiffOptions::default());
+        assert!(diff.default_changed.is_some());
     }
 }
@@ -1,61 +1,127 @@
 use super::common::{BooleanDiff, OptionalStringDiff, StringListDiff};
-use crate::openapi::{ReferenceOr, Schema};
+use super::options::DiffOptions;
+use crate::openapi::{ReferenceOr, Schema, SchemaType};
 use serde::Serialize;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 #[derive(Debug, Serialize, Default)]
 pub struct SchemaDiff {
-    pub type_changed: Option<OptionalStringDiff>,
+    /// Set when either schema is a reference, which is not followed.
+    pub reference_changed: Option<OptionalStringDiff>,
+    /// Dynamic references are compared like references, as opaque strings.
+    pub dynamic_ref_changed: Option<OptionalStringDiff>,
+    pub dynamic_anchor_changed: Option<OptionalStringDiff>,
+    /// Set when the schema is typed in both base and head.
+    pub type_changed: Option<SchemaTypeDiff>,
+    /// The type given to a previously untyped schema.
+    pub type_added: Option<SchemaType>,
+    /// The type of a schema that became untyped, accepting any value.
+    pub type_removed: Option<SchemaType>,
+    /// Set when the type moved between scalar, object and array, which
+    /// reshapes the value entirely.
+    pub category_changed: Option<CategoryDiff>,
     pub description_changed: Option<OptionalStringDiff>,
     pub format_changed: Option<OptionalStringDiff>,
+    pub pattern_changed: Option<OptionalStringDiff>,
+    pub content_encoding_changed: Option<OptionalStringDiff>,
+    pub content_media_type_changed: Option<OptionalStringDiff>,
     pub required_changed: Option<StringListDiff>,
     pub properties_changed: Option<PropertiesDiff>,
     pub enum_changed: Option<EnumDiff>,
     pub items_changed: Option<Box<SchemaDiff>>,
     pub nullable_changed: Option<BooleanDiff>,
+    pub max_length_changed: Option<MaxLengthDiff>,
+    pub multiple_of_changed: Option<MultipleOfDiff>,
+    pub examples_changed: Option<ExamplesDiff>,
+    pub not_added: Option<ReferenceOr<Schema>>,
+    pub not_removed: Option<ReferenceOr<Schema>>,
+    pub not_changed: Option<Box<SchemaDiff>>,
+    pub if_changed: Option<SubschemaDiff>,
+    pub then_changed: Option<SubschemaDiff>,
+    pub else_changed: Option<SubschemaDiff>,
+    pub property_names_changed: Option<SubschemaDiff>,
+    pub dependent_required_changed: Option<DependentRequiredDiff>,
+    pub dependent_schemas_changed: Option<DependentSchemasDiff>,
 }
 
 impl SchemaDiff {
     pub fn has_changes(&self) -> bool {
-        self.type_changed.is_some()
+        self.reference_changed.is_some()
+            || self.dynamic_ref_changed.is_some()
+            || self.dynamic_anchor_changed.is_some()
+            || self.type_changed.is_some()
+            || self.type_added.is_some()
+            || self.type_removed.is_some()
+            || self.category_changed.is_some()
             || self.properties_changed.is_some()
             || self.description_changed.is_some()
             || self.format_changed.is_some()
+            || self.pattern_changed.is_some()
+            || self.content_encoding_changed.is_some()
+            || self.content_media_type_changed.is_some()
             || self.required_changed.is_some()
             || self.enum_changed.is_some()
             || self.items_changed.is_some()
             || self.nullable_changed.is_some()
+            || self.max_length_changed.is_some()
+            || self.multiple_of_changed.is_some()
+            || self.examples_changed.is_some()
+            || self.not_added.is_some()
+            || self.not_removed.is_some()
+            || self.not_changed.is_some()
+            || self.if_changed.is_some()
+            || self.then_changed.is_some()
+            || self.else_changed.is_some()
+            || self.property_names_changed.is_some()
+            || self.dependent_required_changed.is_some()
+            || self.dependent_schemas_changed.is_some()
     }
 
+    /// Diffs two schemas with the default options.
     pub fn from_schemas(base: &ReferenceOr<Schema>, head: &ReferenceOr<Schema>) -> Self {
-        let base_schema = if let ReferenceOr::Item(s) = base {
-            s
-        } else {
-            panic!("Refs are not handled yet.");
-        };
+        Self::from_schemas_with_options(base, head, &DiffOptions::default())
+    }
 
-        let head_schema = if let ReferenceOr::Item(s) = head {
-            s
-        } else {
-            panic!("Refs are not handled yet.");
+    pub fn from_schemas_with_options(
+        base: &ReferenceOr<Schema>,
+        head: &ReferenceOr<Schema>,
+        options: &DiffOptions,
+    ) -> Self {
+        // Most schemas of large descriptions are unchanged, and comparing
+        // them is much cheaper than walking them keyword by keyword.
+        if base == head {
+            return Self::default();
+        }
+
+        let (base_schema, head_schema) = match (base, head) {
+            (ReferenceOr::Item(base), ReferenceOr::Item(head)) => (base, head),
+            _ => {
+                return Self {
+                    reference_changed: OptionalStringDiff::from_strings(
+                        &reference(base),
+                        &reference(head),
+                    ),
+                    ..Default::default()
+                }
+            }
         };
 
         let mut diff = Self::default();
 
-        let enum_diff = EnumDiff::from_schemas(&base_schema, &head_schema);
+        let enum_diff = EnumDiff::from_schemas(base_schema, head_schema, options);
         if enum_diff.has_changes() {
             diff.enum_changed = Some(enum_diff);
         }
 
         let required_diff = StringListDiff::from_lists(
-            &base_schema.required.clone().unwrap_or_else(|| vec![]),
-            &head_schema.required.clone().unwrap_or_else(|| vec![]),
+            &base_schema.required.clone().unwrap_or_default(),
+            &head_schema.required.clone().unwrap_or_default(),
         );
         if required_diff.has_changes() {
             diff.required_changed = Some(required_diff);
         }
 
-        let properties_diff = PropertiesDiff::from_schemas(&base_schema, &head_schema);
+        let properties_diff = PropertiesDiff::from_schemas(base_schema, head_schema, options);
         if properties_diff.has_changes() {
             diff.properties_changed = Some(properties_diff);
         }
@@ -65,9 +131,10 @@ impl SchemaDiff {
         // type was changed to array and that is enough of a diff.
         if let Some(base_items) = &base_schema.items {
             if let Some(head_items) = &head_schema.items {
-                let items_diff = SchemaDiff::from_schemas(
+                let items_diff = SchemaDiff::from_schemas_with_options(
                     &ReferenceOr::Item(*base_items.clone()),
                     &ReferenceOr::Item(*head_items.clone()),
+                    options,
                 );
                 if items_diff.has_changes() {
                     diff.items_changed = Some(Box::new(items_diff))
@@ -75,6 +142,37 @@ impl SchemaDiff {
             }
         }
 
+        if !options.ignore_examples {
+            let examples_diff = ExamplesDiff::from_schemas(base_schema, head_schema);
+            if examples_diff.has_changes() {
+                diff.examples_changed = Some(examples_diff);
+            }
+        }
+
+        match (&base_schema.not, &head_schema.not) {
+            (Some(base_not), Some(head_not)) => match (base_not.as_ref(), head_not.as_ref()) {
+                (ReferenceOr::Item(_), ReferenceOr::Item(_)) => {
+                    let not_diff =
+                        SchemaDiff::from_schemas_with_options(base_not, head_not, options);
+                    if not_diff.has_changes() {
+                        diff.not_changed = Some(Box::new(not_diff));
+                    }
+                }
+                _ => {
+                    if base_not != head_not {
+                        diff.not_removed = Some(*base_not.clone());
+                        diff.not_added = Some(*head_not.clone());
+                    }
+                }
+            },
+            (Some(base_not), None) => diff.not_removed = Some(*base_not.clone()),
+            (None, Some(head_not)) => diff.not_added = Some(*head_not.clone()),
+            (None, None) => {}
+        }
+
+        diff.max_length_changed = MaxLengthDiff::from_schemas(base_schema, head_schema);
+        diff.multiple_of_changed = MultipleOfDiff::from_schemas(base_schema, head_schema);
+
         let base_nullable = base_schema.nullable.unwrap_or(false);
         let head_nullable = head_schema.nullable.unwrap_or(false);
 
@@ -82,17 +180,253 @@ impl SchemaDiff {
             diff.nullable_changed = BooleanDiff::from_bools(base_nullable, head_nullable);
         }
 
-        diff.type_changed =
-            OptionalStringDiff::from_strings(&base_schema.schema_type, &head_schema.schema_type);
+        match (&base_schema.schema_type, &head_schema.schema_type) {
+            (Some(base_type), Some(head_type)) => {
+                diff.type_changed = SchemaTypeDiff::from_types(base_type, head_type)
+            }
+            (Some(base_type), None) => diff.type_removed = Some(base_type.clone()),
+            (None, Some(head_type)) => diff.type_added = Some(head_type.clone()),
+            (None, None) => {}
+        }
+        diff.category_changed = CategoryDiff::from_schemas(base_schema, head_schema);
         diff.description_changed =
             OptionalStringDiff::from_strings(&base_schema.description, &head_schema.description);
         diff.format_changed =
             OptionalStringDiff::from_strings(&base_schema.format, &head_schema.format);
+        diff.pattern_changed =
+            OptionalStringDiff::from_strings(&base_schema.pattern, &head_schema.pattern);
+
+        // Keywords OpenAPI 3.0 schemas don't have, see
+        // `DiffOptions::json_schema`.
+        if options.json_schema != Some(false) {
+            diff.if_changed = SubschemaDiff::from_schemas(
+                &base_schema.if_schema,
+                &head_schema.if_schema,
+                options,
+            );
+            diff.then_changed = SubschemaDiff::from_schemas(
+                &base_schema.then_schema,
+                &head_schema.then_schema,
+                options,
+            );
+            diff.else_changed = SubschemaDiff::from_schemas(
+                &base_schema.else_schema,
+                &head_schema.else_schema,
+                options,
+            );
+            diff.property_names_changed = SubschemaDiff::from_schemas(
+                &base_schema.property_names,
+                &head_schema.property_names,
+                options,
+            );
+
+            let dependent_required_diff =
+                DependentRequiredDiff::from_schemas(base_schema, head_schema);
+            if dependent_required_diff.has_changes() {
+                diff.dependent_required_changed = Some(dependent_required_diff);
+            }
+
+            let dependent_schemas_diff =
+                DependentSchemasDiff::from_schemas(base_schema, head_schema, options);
+            if dependent_schemas_diff.has_changes() {
+                diff.dependent_schemas_changed = Some(dependent_schemas_diff);
+            }
+
+            diff.dynamic_ref_changed = OptionalStringDiff::from_strings(
+                &base_schema.dynamic_ref,
+                &head_schema.dynamic_ref,
+            );
+            diff.dynamic_anchor_changed = OptionalStringDiff::from_strings(
+                &base_schema.dynamic_anchor,
+                &head_schema.dynamic_anchor,
+            );
+            diff.content_encoding_changed = OptionalStringDiff::from_strings(
+                &base_schema.content_encoding,
+                &head_schema.content_encoding,
+            );
+            diff.content_media_type_changed = OptionalStringDiff::from_strings(
+                &base_schema.content_media_type,
+                &head_schema.content_media_type,
+            );
+        }
 
         diff
     }
 }
 
+/// The reference a schema is given by, if any.
+fn reference(schema: &ReferenceOr<Schema>) -> Option<String> {
+    match schema {
+        ReferenceOr::Reference { reference } => Some(reference.clone()),
+        ReferenceOr::Item(_) => None,
+    }
+}
+
+/// String formats clients commonly parse into dedicated types, so that
+/// moving a value from one to another changes what clients accept.
+const WELL_KNOWN_FORMATS: &[&str] = &[
+    "date",
+    "date-time",
+    "time",
+    "duration",
+    "uuid",
+    "email",
+    "uri",
+    "uri-reference",
+    "hostname",
+    "ipv4",
+    "ipv6",
+    "byte",
+    "binary",
+];
+
+/// Whether a format is one of the well-known formats whose values clients
+/// parse, as opposed to a custom, informational format.
+pub fn is_well_known_format(format: &str) -> bool {
+    WELL_KNOWN_FORMATS.contains(&format)
+}
+
+impl SchemaDiff {
+    /// Whether the format change alters the accepted value space, that is
+    /// whether the format moved from or to a well-known format.
+    pub fn format_change_is_well_known(&self) -> bool {
+        self.format_changed.as_ref().is_some_and(|format| {
+            [&format.from, &format.to]
+                .iter()
+                .any(|format| format.as_deref().is_some_and(is_well_known_format))
+        })
+    }
+}
+
+/// A change of type, where type lists are compared as sets.
+#[derive(Debug, Serialize)]
+pub struct SchemaTypeDiff {
+    pub from: SchemaType,
+    pub to: SchemaType,
+}
+
+impl SchemaTypeDiff {
+    pub fn from_types(base: &SchemaType, head: &SchemaType) -> Option<Self> {
+        if base != head {
+            Some(Self {
+                from: base.clone(),
+                to: head.clone(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeCategory {
+    Scalar,
+    Object,
+    Array,
+}
+
+impl TypeCategory {
+    /// The category of a schema type. A type list has the category shared
+    /// by its types other than `null`, and none when they span several.
+    pub fn from_type(schema_type: &SchemaType) -> Option<Self> {
+        let mut categories = schema_type
+            .types()
+            .into_iter()
+            .filter(|schema_type| *schema_type != "null")
+            .map(|schema_type| match schema_type {
+                "object" => TypeCategory::Object,
+                "array" => TypeCategory::Array,
+                _ => TypeCategory::Scalar,
+            });
+
+        let category = categories.next().unwrap_or(TypeCategory::Scalar);
+        if categories.all(|other| other == category) {
+            Some(category)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for TypeCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeCategory::Scalar => write!(f, "scalar"),
+            TypeCategory::Object => write!(f, "object"),
+            TypeCategory::Array => write!(f, "array"),
+        }
+    }
+}
+
+/// A type change across categories, carrying both complete schemas so the
+/// new structure can be reviewed.
+#[derive(Debug, Serialize)]
+pub struct CategoryDiff {
+    pub from: TypeCategory,
+    pub to: TypeCategory,
+    pub base: Box<Schema>,
+    pub head: Box<Schema>,
+}
+
+impl CategoryDiff {
+    pub fn from_schemas(base: &Schema, head: &Schema) -> Option<Self> {
+        let from = TypeCategory::from_type(base.schema_type.as_ref()?)?;
+        let to = TypeCategory::from_type(head.schema_type.as_ref()?)?;
+
+        if from != to {
+            Some(Self {
+                from,
+                to,
+                base: Box::new(base.clone()),
+                head: Box::new(head.clone()),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Changes to the examples of a schema. A 3.0 singular `example` is
+/// treated as a one-element 3.1 `examples` array.
+#[derive(Debug, Serialize, Default)]
+pub struct ExamplesDiff {
+    pub added: Vec<serde_json::Value>,
+    pub removed: Vec<serde_json::Value>,
+}
+
+impl ExamplesDiff {
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty()
+    }
+
+    pub fn from_schemas(base: &Schema, head: &Schema) -> Self {
+        let base_examples = Self::examples(base);
+        let head_examples = Self::examples(head);
+
+        Self {
+            added: head_examples
+                .iter()
+                .filter(|example| !base_examples.contains(example))
+                .cloned()
+                .collect(),
+            removed: base_examples
+                .iter()
+                .filter(|example| !head_examples.contains(example))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn examples(schema: &Schema) -> Vec<serde_json::Value> {
+        match (&schema.examples, &schema.example) {
+            (Some(examples), _) => examples.clone(),
+            (None, Some(example)) => vec![example.clone()],
+            (None, None) => vec![],
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Default)]
 pub struct EnumDiff {
     pub added: bool,
@@ -109,21 +443,28 @@ impl EnumDiff {
             || !self.values_removed.is_empty()
     }
 
-    pub fn from_schemas(base: &Schema, head: &Schema) -> Self {
+    /// Diffs the enum values of two schemas. String values are compared
+    /// ignoring case with `DiffOptions::enum_case_insensitive`.
+    pub fn from_schemas(base: &Schema, head: &Schema, options: &DiffOptions) -> Self {
+        let key = |value: &Option<String>| match value {
+            Some(value) if options.enum_case_insensitive => Some(value.to_lowercase()),
+            value => value.clone(),
+        };
+
         match &base.enum_values {
             Some(base_enum) => match &head.enum_values {
                 Some(head_enum) => {
-                    let base_set: HashSet<_> = base_enum.iter().collect();
+                    let base_set: HashSet<_> = base_enum.iter().map(key).collect();
                     let added: Vec<_> = head_enum
                         .iter()
-                        .filter(|item| !base_set.contains(item))
+                        .filter(|item| !base_set.contains(&key(item)))
                         .cloned()
                         .collect();
 
-                    let head_set: HashSet<_> = head_enum.iter().collect();
+                    let head_set: HashSet<_> = head_enum.iter().map(key).collect();
                     let removed: Vec<_> = base_enum
                         .iter()
-                        .filter(|item| !head_set.contains(item))
+                        .filter(|item| !head_set.contains(&key(item)))
                         .cloned()
                         .collect();
 
@@ -154,6 +495,216 @@ impl EnumDiff {
     }
 }
 
+/// A change to the `maxLength` of a string schema.
+#[derive(Debug, Serialize)]
+pub struct MaxLengthDiff {
+    pub from: Option<u32>,
+    pub to: Option<u32>,
+}
+
+impl MaxLengthDiff {
+    pub fn from_schemas(base: &Schema, head: &Schema) -> Option<Self> {
+        if base.max_length != head.max_length {
+            Some(Self {
+                from: base.max_length,
+                to: head.max_length,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Whether values valid in base may be too long in head, including
+    /// values already stored and echoed back in responses.
+    pub fn truncation_risk(&self) -> bool {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) => to < from,
+            (None, Some(_)) => true,
+            (_, None) => false,
+        }
+    }
+}
+
+/// A change to the `multipleOf` of a numeric schema.
+#[derive(Debug, Serialize)]
+pub struct MultipleOfDiff {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+impl MultipleOfDiff {
+    pub fn from_schemas(base: &Schema, head: &Schema) -> Option<Self> {
+        if base.multiple_of != head.multiple_of {
+            Some(Self {
+                from: base.multiple_of,
+                to: head.multiple_of,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Whether some numbers valid in base are rejected in head: a new
+    /// `multipleOf`, or one the base `multipleOf` is not a multiple of, such
+    /// as `0.01` becoming `1`.
+    pub fn narrows(&self) -> bool {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) => {
+                let ratio = from / to;
+                (ratio - ratio.round()).abs() > 1e-9 * ratio.abs().max(1.0)
+            }
+            (None, Some(_)) => true,
+            (_, None) => false,
+        }
+    }
+}
+
+/// Changes to an optional subschema, such as the `if`, `then` and `else`
+/// conditionals or the `propertyNames` of OpenAPI 3.1. A changed reference is reported as the
+/// base subschema removed and the head one added.
+#[derive(Debug, Serialize, Default)]
+pub struct SubschemaDiff {
+    pub added: Option<ReferenceOr<Schema>>,
+    pub removed: Option<ReferenceOr<Schema>>,
+    pub changed: Option<Box<SchemaDiff>>,
+}
+
+impl SubschemaDiff {
+    pub fn from_schemas(
+        base: &Option<Box<ReferenceOr<Schema>>>,
+        head: &Option<Box<ReferenceOr<Schema>>>,
+        options: &DiffOptions,
+    ) -> Option<Self> {
+        let mut diff = Self::default();
+
+        match (base, head) {
+            (Some(base), Some(head)) => match (base.as_ref(), head.as_ref()) {
+                (ReferenceOr::Item(_), ReferenceOr::Item(_)) => {
+                    let schema_diff = SchemaDiff::from_schemas_with_options(base, head, options);
+                    if schema_diff.has_changes() {
+                        diff.changed = Some(Box::new(schema_diff));
+                    }
+                }
+                _ => {
+                    if base != head {
+                        diff.removed = Some(*base.clone());
+                        diff.added = Some(*head.clone());
+                    }
+                }
+            },
+            (Some(base), None) => diff.removed = Some(*base.clone()),
+            (None, Some(head)) => diff.added = Some(*head.clone()),
+            (None, None) => {}
+        }
+
+        if diff.added.is_some() || diff.removed.is_some() || diff.changed.is_some() {
+            Some(diff)
+        } else {
+            None
+        }
+    }
+}
+
+/// Changes to `dependentRequired`, keyed by the property whose presence
+/// triggers the requirement. Absent in 3.0 descriptions, where no changes
+/// are reported.
+#[derive(Debug, Serialize, Default)]
+pub struct DependentRequiredDiff {
+    pub changed: HashMap<String, StringListDiff>,
+}
+
+impl DependentRequiredDiff {
+    pub fn has_changes(&self) -> bool {
+        !self.changed.is_empty()
+    }
+
+    pub fn from_schemas(base: &Schema, head: &Schema) -> Self {
+        let base_dependencies = base.dependent_required.clone().unwrap_or_default();
+        let head_dependencies = head.dependent_required.clone().unwrap_or_default();
+        let mut changed = HashMap::new();
+
+        let properties: BTreeSet<_> = base_dependencies
+            .keys()
+            .chain(head_dependencies.keys())
+            .collect();
+
+        for property in properties {
+            let list_diff = StringListDiff::from_lists(
+                base_dependencies
+                    .get(property)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+                head_dependencies
+                    .get(property)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+            );
+            if list_diff.has_changes() {
+                changed.insert(property.clone(), list_diff);
+            }
+        }
+
+        Self { changed }
+    }
+}
+
+type DependentSchema = (String, ReferenceOr<Schema>);
+
+/// Changes to `dependentSchemas`, keyed by the property whose presence
+/// applies the schema. Absent in 3.0 descriptions, where no changes are
+/// reported.
+#[derive(Debug, Serialize, Default)]
+pub struct DependentSchemasDiff {
+    pub added: Vec<DependentSchema>,
+    pub removed: Vec<DependentSchema>,
+    pub changed: HashMap<String, SchemaDiff>,
+}
+
+impl DependentSchemasDiff {
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+
+    pub fn from_schemas(base: &Schema, head: &Schema, options: &DiffOptions) -> Self {
+        let mut diff = Self::default();
+
+        let base_schemas = base.dependent_schemas.clone().unwrap_or_default();
+        let head_schemas = head.dependent_schemas.clone().unwrap_or_default();
+
+        for (property, base_schema) in &base_schemas {
+            match head_schemas.get(property) {
+                Some(head_schema) => match (base_schema, head_schema) {
+                    (ReferenceOr::Item(_), ReferenceOr::Item(_)) => {
+                        let schema_diff = SchemaDiff::from_schemas_with_options(
+                            base_schema,
+                            head_schema,
+                            options,
+                        );
+                        if schema_diff.has_changes() {
+                            diff.changed.insert(property.clone(), schema_diff);
+                        }
+                    }
+                    _ => {
+                        if base_schema != head_schema {
+                            diff.removed.push((property.clone(), base_schema.clone()));
+                            diff.added.push((property.clone(), head_schema.clone()));
+                        }
+                    }
+                },
+                None => diff.removed.push((property.clone(), base_schema.clone())),
+            }
+        }
+
+        for (property, head_schema) in &head_schemas {
+            if !base_schemas.contains_key(property) {
+                diff.added.push((property.clone(), head_schema.clone()));
+            }
+        }
+
+        diff
+    }
+}
+
 type Property = (String, Schema);
 
 #[derive(Debug, Serialize, Default)]
@@ -161,33 +712,44 @@ pub struct PropertiesDiff {
     pub added: Vec<Property>,
     pub removed: Vec<Property>,
     pub changed: HashMap<String, SchemaDiff>,
+    /// Required properties that are no longer `readOnly`. Clients never
+    /// had to send them, but code generators now make them required in
+    /// request types too.
+    pub no_longer_read_only: Vec<String>,
 }
 
 impl PropertiesDiff {
     pub fn has_changes(&self) -> bool {
-        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+        !self.added.is_empty()
+            || !self.removed.is_empty()
+            || !self.changed.is_empty()
+            || !self.no_longer_read_only.is_empty()
     }
 
-    pub fn from_schemas(base: &Schema, head: &Schema) -> Self {
+    pub fn from_schemas(base: &Schema, head: &Schema, options: &DiffOptions) -> Self {
         let mut added = vec![];
         let mut removed = vec![];
         let mut changed: HashMap<String, SchemaDiff> = HashMap::new();
+        let mut no_longer_read_only = vec![];
 
-        let base_properties = base
-            .properties
-            .clone()
-            .unwrap_or_else(|| BTreeMap::default());
-        let head_properties = head
-            .properties
-            .clone()
-            .unwrap_or_else(|| BTreeMap::default());
+        let base_properties = base.properties.clone().unwrap_or_default();
+        let head_properties = head.properties.clone().unwrap_or_default();
+        let head_required = head.required.clone().unwrap_or_default();
 
         for (property_name, schema) in &base_properties {
             match head_properties.get(property_name) {
                 Some(head_property) => {
-                    let schema_diff = SchemaDiff::from_schemas(
+                    if schema.read_only == Some(true)
+                        && head_property.read_only != Some(true)
+                        && head_required.contains(property_name)
+                    {
+                        no_longer_read_only.push(property_name.clone());
+                    }
+
+                    let schema_diff = SchemaDiff::from_schemas_with_options(
                         &ReferenceOr::Item(schema.clone()),
                         &ReferenceOr::Item(head_property.clone()),
+                        options,
                     );
                     if schema_diff.has_changes() {
                         changed.insert(property_name.clone(), schema_diff);
@@ -208,6 +770,561 @@ impl PropertiesDiff {
             added,
             removed,
             changed,
+            no_longer_read_only,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string_schema() -> Schema {
+        Schema {
+            schema_type: Some("string".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reference_changed() {
+        let diff = SchemaDiff::from_schemas(
+            &ReferenceOr::ref_("#/components/schemas/Pet"),
+            &ReferenceOr::ref_("#/components/schemas/Cat"),
+        );
+
+        let reference = diff
+            .reference_changed
+            .expect("reference should have changed");
+        assert_eq!(Some("#/components/schemas/Pet"), reference.from.as_deref());
+        assert_eq!(Some("#/components/schemas/Cat"), reference.to.as_deref());
+
+        let diff = SchemaDiff::from_schemas(
+            &ReferenceOr::ref_("#/components/schemas/Pet"),
+            &ReferenceOr::Item(string_schema()),
+        );
+        assert!(diff.reference_changed.unwrap().to.is_none());
+    }
+
+    #[test]
+    fn required_property_no_longer_read_only() {
+        let schema = |read_only: bool, required: &[&str]| -> ReferenceOr<Schema> {
+            let id = Schema {
+                read_only: Some(read_only),
+                ..string_schema()
+            };
+            ReferenceOr::Item(Schema {
+                schema_type: Some("object".into()),
+                required: Some(required.iter().map(|name| name.to_string()).collect()),
+                properties: Some(vec![("id".to_string(), id)].into_iter().collect()),
+                ..Default::default()
+            })
+        };
+
+        let diff = SchemaDiff::from_schemas(&schema(true, &["id"]), &schema(false, &["id"]));
+        let properties = diff
+            .properties_changed
+            .expect("properties should have changed");
+        assert_eq!(vec!["id".to_string()], properties.no_longer_read_only);
+
+        let diff = SchemaDiff::from_schemas(&schema(true, &[]), &schema(false, &[]));
+        assert!(diff.properties_changed.is_none());
+    }
+
+    fn not_schema(not: Schema) -> ReferenceOr<Schema> {
+        ReferenceOr::Item(Schema {
+            not: Some(Box::new(ReferenceOr::Item(not))),
+            ..string_schema()
+        })
+    }
+
+    #[test]
+    fn scalar_to_object() {
+        let mut properties = std::collections::BTreeMap::new();
+        properties.insert("street".into(), string_schema());
+        let head = Schema {
+            schema_type: Some("object".into()),
+            properties: Some(properties),
+            ..Default::default()
+        };
+
+        let diff = SchemaDiff::from_schemas(
+            &ReferenceOr::Item(string_schema()),
+            &ReferenceOr::Item(head),
+        );
+
+        let category = diff.category_changed.expect("category should have changed");
+        assert_eq!(TypeCategory::Scalar, category.from);
+        assert_eq!(TypeCategory::Object, category.to);
+        assert!(category.head.properties.unwrap().contains_key("street"));
+        assert_eq!(Some("string".into()), category.base.schema_type);
+    }
+
+    #[test]
+    fn object_to_array() {
+        let base = Schema {
+            schema_type: Some("object".into()),
+            ..Default::default()
+        };
+        let head = Schema {
+            schema_type: Some("array".into()),
+            items: Some(Box::new(base.clone())),
+            ..Default::default()
+        };
+
+        let diff = SchemaDiff::from_schemas(&ReferenceOr::Item(base), &ReferenceOr::Item(head));
+
+        let category = diff.category_changed.expect("category should have changed");
+        assert_eq!(TypeCategory::Object, category.from);
+        assert_eq!(TypeCategory::Array, category.to);
+        assert!(category.head.items.is_some());
+    }
+
+    #[test]
+    fn type_lists() {
+        let list = |types: &[&str]| Schema {
+            schema_type: Some(SchemaType::List(
+                types
+                    .iter()
+                    .map(|schema_type| schema_type.to_string())
+                    .collect(),
+            )),
+            ..Default::default()
+        };
+
+        let diff = SchemaDiff::from_schemas(
+            &ReferenceOr::Item(list(&["integer", "string"])),
+            &ReferenceOr::Item(list(&["string", "integer"])),
+        );
+        assert!(!diff.has_changes());
+
+        let diff = SchemaDiff::from_schemas(
+            &ReferenceOr::Item(list(&["object", "null"])),
+            &ReferenceOr::Item(list(&["array", "null"])),
+        );
+        let category = diff.category_changed.expect("category should have changed");
+        assert_eq!(TypeCategory::Object, category.from);
+        assert_eq!(TypeCategory::Array, category.to);
+
+        // Lists spanning several categories have none.
+        let diff = SchemaDiff::from_schemas(
+            &ReferenceOr::Item(list(&["object", "string"])),
+            &ReferenceOr::Item(list(&["array", "string"])),
+        );
+        assert!(diff.category_changed.is_none());
+        assert!(diff.type_changed.is_some());
+    }
+
+    #[test]
+    fn property_type_removed() {
+        let mut base_properties = std::collections::BTreeMap::new();
+        base_properties.insert("name".into(), string_schema());
+        let mut head_properties = std::collections::BTreeMap::new();
+        head_properties.insert("name".into(), Schema::default());
+
+        let diff = SchemaDiff::from_schemas(
+            &ReferenceOr::Item(Schema {
+                properties: Some(base_properties),
+                ..Default::default()
+            }),
+            &ReferenceOr::Item(Schema {
+                properties: Some(head_properties),
+                ..Default::default()
+            }),
+        );
+
+        let name_diff = &diff.properties_changed.unwrap().changed["name"];
+        assert_eq!(Some("string".into()), name_diff.type_removed);
+        assert!(name_diff.type_changed.is_none());
+        assert!(name_diff.type_added.is_none());
+    }
+
+    #[test]
+    fn scalar_type_change_keeps_category() {
+        let head = Schema {
+            schema_type: Some("integer".into()),
+            ..Default::default()
+        };
+
+        let diff = SchemaDiff::from_schemas(
+            &ReferenceOr::Item(string_schema()),
+            &ReferenceOr::Item(head),
+        );
+
+        assert!(diff.type_changed.is_some());
+        assert!(diff.category_changed.is_none());
+    }
+
+    #[test]
+    fn examples_added_and_removed() {
+        let base = Schema {
+            examples: Some(vec!["Rex".into(), "Fido".into()]),
+            ..string_schema()
+        };
+        let head = Schema {
+            examples: Some(vec!["Rex".into(), "Felix".into()]),
+            ..string_schema()
+        };
+
+        let diff = SchemaDiff::from_schemas(&ReferenceOr::Item(base), &ReferenceOr::Item(head));
+
+        let examples = diff.examples_changed.expect("examples should have changed");
+        assert_eq!(vec![serde_json::Value::from("Felix")], examples.added);
+        assert_eq!(vec![serde_json::Value::from("Fido")], examples.removed);
+    }
+
+    #[test]
+    fn singular_example_equals_single_examples() {
+        let base = Schema {
+            example: Some("Rex".into()),
+            ..string_schema()
+        };
+        let head = Schema {
+            examples: Some(vec!["Rex".into()]),
+            ..string_schema()
+        };
+
+        let diff = SchemaDiff::from_schemas(&ReferenceOr::Item(base), &ReferenceOr::Item(head));
+
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn not_added() {
+        let diff = SchemaDiff::from_schemas(
+            &ReferenceOr::Item(string_schema()),
+            &not_schema(Schema {
+                enum_values: Some(vec![Some("admin".into())]),
+                ..Default::default()
+            }),
+        );
+
+        assert!(diff.has_changes());
+        assert!(diff.not_added.is_some());
+        assert!(diff.not_removed.is_none());
+    }
+
+    #[test]
+    fn not_changed() {
+        let diff = SchemaDiff::from_schemas(
+            &not_schema(Schema {
+                enum_values: Some(vec![Some("admin".into())]),
+                ..Default::default()
+            }),
+            &not_schema(Schema {
+                enum_values: Some(vec![Some("admin".into()), Some("root".into())]),
+                ..Default::default()
+            }),
+        );
+
+        let not_diff = diff.not_changed.expect("not should have changed");
+        assert_eq!(
+            vec![Some(String::from("root"))],
+            not_diff.enum_changed.unwrap().values_added
+        );
+    }
+
+    fn dependent_schema(
+        dependent_required: &[(&str, &[&str])],
+        dependent_schemas: &[(&str, Schema)],
+    ) -> ReferenceOr<Schema> {
+        ReferenceOr::Item(Schema {
+            schema_type: Some("object".into()),
+            dependent_required: Some(
+                dependent_required
+                    .iter()
+                    .map(|(property, required)| {
+                        (
+                            property.to_string(),
+                            required.iter().map(|name| name.to_string()).collect(),
+                        )
+                    })
+                    .collect(),
+            ),
+            dependent_schemas: Some(
+                dependent_schemas
+                    .iter()
+                    .map(|(property, schema)| {
+                        (property.to_string(), ReferenceOr::Item(schema.clone()))
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn dependent_required_changed() {
+        let base = dependent_schema(&[("credit_card", &["billing_address"])], &[]);
+        let head = dependent_schema(
+            &[
+                ("credit_card", &["billing_address", "cvc"]),
+                ("iban", &["bic"]),
+            ],
+            &[],
+        );
+
+        let diff = SchemaDiff::from_schemas(&base, &head);
+
+        let dependencies = diff
+            .dependent_required_changed
+            .expect("dependentRequired should have changed");
+        assert_eq!(
+            vec!["cvc".to_string()],
+            dependencies.changed["credit_card"].added
+        );
+        assert_eq!(vec!["bic".to_string()], dependencies.changed["iban"].added);
+    }
+
+    #[test]
+    fn dependent_schemas_changed() {
+        let base = dependent_schema(&[], &[("credit_card", Schema::default())]);
+        let head = dependent_schema(&[], &[("credit_card", string_schema())]);
+
+        let diff = SchemaDiff::from_schemas(&base, &head);
+
+        let schemas = diff
+            .dependent_schemas_changed
+            .expect("dependentSchemas should have changed");
+        assert!(schemas.changed["credit_card"].type_added.is_some());
+
+        let diff = SchemaDiff::from_schemas(&dependent_schema(&[], &[]), &head);
+        assert_eq!(1, diff.dependent_schemas_changed.unwrap().added.len());
+    }
+
+    #[test]
+    fn dependents_absent_in_3_0() {
+        let diff = SchemaDiff::from_schemas(
+            &ReferenceOr::Item(string_schema()),
+            &ReferenceOr::Item(string_schema()),
+        );
+
+        assert!(diff.dependent_required_changed.is_none());
+        assert!(diff.dependent_schemas_changed.is_none());
+    }
+
+    #[test]
+    fn max_length_decreased() {
+        let schema = |max_length| {
+            ReferenceOr::Item(Schema {
+                max_length,
+                ..string_schema()
+            })
+        };
+
+        let diff = SchemaDiff::from_schemas(&schema(Some(50)), &schema(Some(20)));
+        assert!(diff.max_length_changed.unwrap().truncation_risk());
+
+        let diff = SchemaDiff::from_schemas(&schema(Some(20)), &schema(None));
+        assert!(!diff.max_length_changed.unwrap().truncation_risk());
+    }
+
+    #[test]
+    fn multiple_of_changes() {
+        let schema = |multiple_of| {
+            ReferenceOr::Item(Schema {
+                schema_type: Some("number".into()),
+                multiple_of,
+                ..Default::default()
+            })
+        };
+
+        let added = SchemaDiff::from_schemas(&schema(None), &schema(Some(0.01)));
+        assert!(added.multiple_of_changed.unwrap().narrows());
+
+        let tightened = SchemaDiff::from_schemas(&schema(Some(0.01)), &schema(Some(1.0)));
+        let tightened = tightened.multiple_of_changed.unwrap();
+        assert_eq!((Some(0.01), Some(1.0)), (tightened.from, tightened.to));
+        assert!(tightened.narrows());
+
+        let coarser = SchemaDiff::from_schemas(&schema(Some(2.0)), &schema(Some(4.0)));
+        assert!(coarser.multiple_of_changed.unwrap().narrows());
+
+        let finer = SchemaDiff::from_schemas(&schema(Some(1.0)), &schema(Some(0.01)));
+        assert!(!finer.multiple_of_changed.unwrap().narrows());
+
+        let removed = SchemaDiff::from_schemas(&schema(Some(5.0)), &schema(None));
+        assert!(!removed.multiple_of_changed.unwrap().narrows());
+    }
+
+    #[test]
+    fn well_known_format_changes() {
+        let schema = |format: Option<&str>| {
+            ReferenceOr::Item(Schema {
+                format: format.map(String::from),
+                ..string_schema()
+            })
+        };
+
+        for (from, to) in [
+            (Some("date"), Some("uuid")),
+            (Some("date"), Some("date-time")),
+            (Some("email"), Some("uri")),
+            (None, Some("uuid")),
+            (Some("date-time"), Some("custom-timestamp")),
+        ] {
+            let diff = SchemaDiff::from_schemas(&schema(from), &schema(to));
+            assert!(diff.format_change_is_well_known(), "{:?} -> {:?}", from, to);
         }
+
+        let diff = SchemaDiff::from_schemas(&schema(Some("sku")), &schema(Some("ean")));
+        assert!(diff.format_changed.is_some());
+        assert!(!diff.format_change_is_well_known());
+    }
+
+    #[test]
+    fn property_order() {
+        let base: ReferenceOr<Schema> = serde_json::from_str(
+            r#"{ "type": "object", "properties": { "a": { "type": "string" }, "b": { "type": "integer" } } }"#,
+        )
+        .unwrap();
+        let head: ReferenceOr<Schema> = serde_json::from_str(
+            r#"{ "properties": { "b": { "type": "integer" }, "a": { "type": "string" } }, "type": "object" }"#,
+        )
+        .unwrap();
+
+        assert!(!SchemaDiff::from_schemas(&base, &head).has_changes());
+
+        let changed: ReferenceOr<Schema> = serde_json::from_str(
+            r#"{ "type": "object", "properties": { "a": { "type": "string" }, "b": { "type": "number" } } }"#,
+        )
+        .unwrap();
+        assert!(SchemaDiff::from_schemas(&base, &changed).has_changes());
+    }
+
+    #[test]
+    fn unchanged_references() {
+        let schema: ReferenceOr<Schema> = ReferenceOr::Reference {
+            reference: String::from("#/components/schemas/Pet"),
+        };
+
+        assert!(!SchemaDiff::from_schemas(&schema, &schema.clone()).has_changes());
+    }
+
+    #[test]
+    fn enum_case_insensitive() {
+        let schema = |values: &[&str]| {
+            ReferenceOr::Item(Schema {
+                enum_values: Some(values.iter().map(|value| Some(value.to_string())).collect()),
+                ..Default::default()
+            })
+        };
+        let base = schema(&["ACTIVE", "SUSPENDED"]);
+        let head = schema(&["active", "suspended", "deleted"]);
+
+        let diff = SchemaDiff::from_schemas(&base, &head);
+        assert_eq!(3, diff.enum_changed.unwrap().values_added.len());
+
+        let options = DiffOptions {
+            enum_case_insensitive: true,
+            ..Default::default()
+        };
+        let diff = SchemaDiff::from_schemas_with_options(&base, &head, &options);
+        let enum_diff = diff.enum_changed.expect("enum should have changed");
+        assert_eq!(vec![Some(String::from("deleted"))], enum_diff.values_added);
+        assert!(enum_diff.values_removed.is_empty());
+
+        let renamed = schema(&["Active", "Suspended"]);
+        assert!(!SchemaDiff::from_schemas_with_options(&base, &renamed, &options).has_changes());
+    }
+
+    fn json_schema(value: serde_json::Value) -> ReferenceOr<Schema> {
+        serde_json::from_value(value).expect("Failed to parse schema")
+    }
+
+    #[test]
+    fn conditional_added() {
+        let base = json_schema(serde_json::json!({
+            "type": "object",
+            "properties": { "method": { "type": "string" }, "iban": { "type": "string" } }
+        }));
+        let head = json_schema(serde_json::json!({
+            "type": "object",
+            "properties": { "method": { "type": "string" }, "iban": { "type": "string" } },
+            "if": { "properties": { "method": { "enum": ["sepa"] } } },
+            "then": { "required": ["iban"] }
+        }));
+
+        let diff = SchemaDiff::from_schemas(&base, &head);
+        assert!(diff.if_changed.unwrap().added.is_some());
+        assert!(diff.then_changed.unwrap().added.is_some());
+        assert!(diff.else_changed.is_none());
+
+        // OpenAPI 3.0 schemas do not define `if` and `then`.
+        let options = DiffOptions {
+            json_schema: Some(false),
+            ..Default::default()
+        };
+        assert!(!SchemaDiff::from_schemas_with_options(&base, &head, &options).has_changes());
+    }
+
+    #[test]
+    fn property_names_pattern_changed() {
+        let schema = |pattern: &str| {
+            json_schema(serde_json::json!({
+                "type": "object",
+                "propertyNames": { "pattern": pattern }
+            }))
+        };
+
+        let diff = SchemaDiff::from_schemas(&schema("^[a-z]+$"), &schema("^[a-z]{1,16}$"));
+        let property_names = diff
+            .property_names_changed
+            .and_then(|property_names| property_names.changed)
+            .expect("propertyNames should have changed");
+        let pattern = property_names.pattern_changed.unwrap();
+        assert_eq!(Some("^[a-z]{1,16}$"), pattern.to.as_deref());
+    }
+
+    #[test]
+    fn dynamic_references() {
+        let schema = |anchor: &str| {
+            json_schema(serde_json::json!({
+                "$dynamicAnchor": anchor,
+                "type": "object",
+                "properties": {
+                    "children": {
+                        "type": "array",
+                        "items": { "$dynamicRef": format!("#{}", anchor) }
+                    }
+                }
+            }))
+        };
+
+        assert!(!SchemaDiff::from_schemas(&schema("node"), &schema("node")).has_changes());
+
+        let diff = SchemaDiff::from_schemas(&schema("node"), &schema("tree"));
+        assert_eq!(
+            Some("tree"),
+            diff.dynamic_anchor_changed.unwrap().to.as_deref()
+        );
+        let children = &diff.properties_changed.unwrap().changed["children"];
+        let dynamic_ref = children
+            .items_changed
+            .as_ref()
+            .and_then(|items| items.dynamic_ref_changed.as_ref())
+            .expect("$dynamicRef should have changed");
+        assert_eq!(Some("#node"), dynamic_ref.from.as_deref());
+        assert_eq!(Some("#tree"), dynamic_ref.to.as_deref());
+    }
+
+    #[test]
+    fn content_encoding_added() {
+        let base = json_schema(serde_json::json!({ "type": "string" }));
+        let head =
+            json_schema(serde_json::json!({ "type": "string", "contentEncoding": "base64" }));
+
+        let diff = SchemaDiff::from_schemas(&base, &head);
+        let encoding = diff
+            .content_encoding_changed
+            .expect("contentEncoding should have changed");
+        assert_eq!(Some("base64"), encoding.to.as_deref());
+
+        // OpenAPI 3.0 schemas do not define `contentEncoding`.
+        let options = DiffOptions {
+            json_schema: Some(false),
+            ..Default::default()
+        };
+        assert!(!SchemaDiff::from_schemas_with_options(&base, &head, &options).has_changes());
     }
 }
@@ -0,0 +1,428 @@
+use super::common::StringListDiff;
+use crate::openapi::{OpenAPI, SecurityRequirement};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A set of security schemes that must all be satisfied together, keyed by
+/// scheme name along with the scopes each of them requires.
+pub type Alternative = BTreeMap<String, BTreeSet<String>>;
+
+/// Changes to the security requirements of an operation, or of the whole
+/// description.
+///
+/// The `security` list of an operation is a disjunction: a request must
+/// satisfy any one of its requirement objects, and within a requirement
+/// object every scheme must be satisfied. Requirements are compared with
+/// these semantics rather than as flat sets of schemes.
+#[derive(Debug, Serialize)]
+pub struct SecurityDiff {
+    /// The alternatives accepted by base.
+    pub from: Vec<Alternative>,
+    /// The alternatives accepted by head.
+    pub to: Vec<Alternative>,
+    /// Alternatives accepted by head only.
+    pub added: Vec<Alternative>,
+    /// Alternatives accepted by base only.
+    pub removed: Vec<Alternative>,
+    /// Changes to the scopes required for the schemes listed by both base
+    /// and head, keyed by scheme name. Scopes are gathered across every
+    /// requirement object listing the scheme.
+    pub scopes: BTreeMap<String, StringListDiff>,
+}
+
+impl SecurityDiff {
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty()
+    }
+
+    pub fn from_requirements(
+        base: &Option<Vec<SecurityRequirement>>,
+        head: &Option<Vec<SecurityRequirement>>,
+    ) -> Option<Self> {
+        let from = Self::alternatives(base);
+        let to = Self::alternatives(head);
+
+        let base_scopes = Self::scopes(base);
+        let head_scopes = Self::scopes(head);
+        let scopes = base_scopes
+            .iter()
+            .filter_map(|(name, scopes)| {
+                let diff = StringListDiff::from_lists(scopes, head_scopes.get(name)?);
+                if diff.has_changes() {
+                    Some((name.clone(), diff))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let diff = Self {
+            added: to.iter().filter(|a| !from.contains(a)).cloned().collect(),
+            removed: from.iter().filter(|a| !to.contains(a)).cloned().collect(),
+            from,
+            to,
+            scopes,
+        };
+
+        if diff.has_changes() {
+            Some(diff)
+        } else {
+            None
+        }
+    }
+
+    /// Whether some client authenticating with a base alternative can no
+    /// longer satisfy any head alternative, e.g. when a scheme is ANDed
+    /// onto an existing requirement or a scope is newly required.
+    pub fn is_breaking(&self) -> bool {
+        self.from.iter().any(|base_alternative| {
+            !self.to.iter().any(|head_alternative| {
+                // Credentials for a base scheme satisfy the same head scheme
+                // when the base scopes include the head scopes.
+                head_alternative.iter().all(|(name, scopes)| {
+                    base_alternative
+                        .get(name)
+                        .is_some_and(|base_scopes| scopes.is_subset(base_scopes))
+                })
+            })
+        })
+    }
+
+    /// The sorted scopes required for each scheme across all requirements.
+    fn scopes(requirements: &Option<Vec<SecurityRequirement>>) -> BTreeMap<String, Vec<String>> {
+        let mut scopes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for requirement in requirements.iter().flatten() {
+            for (name, requirement_scopes) in requirement {
+                scopes
+                    .entry(name.clone())
+                    .or_default()
+                    .extend(requirement_scopes.iter().cloned());
+            }
+        }
+
+        for scheme_scopes in scopes.values_mut() {
+            scheme_scopes.sort();
+            scheme_scopes.dedup();
+        }
+
+        scopes
+    }
+
+    /// Describes alternatives in words, e.g. `A and B (read, write) or C`.
+    pub fn describe(alternatives: &[Alternative]) -> String {
+        alternatives
+            .iter()
+            .map(|alternative| {
+                if alternative.is_empty() {
+                    return String::from("no authentication");
+                }
+
+                alternative
+                    .iter()
+                    .map(|(name, scopes)| {
+                        if scopes.is_empty() {
+                            name.clone()
+                        } else {
+                            let scopes: Vec<_> = scopes.iter().map(String::as_str).collect();
+                            format!("{} ({})", name, scopes.join(", "))
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" and ")
+            })
+            .collect::<Vec<_>>()
+            .join(" or ")
+    }
+
+    /// Normalizes requirements into sorted alternatives. A missing or empty
+    /// list requires no authentication, like an empty requirement object.
+    fn alternatives(requirements: &Option<Vec<SecurityRequirement>>) -> Vec<Alternative> {
+        let requirements = requirements.clone().unwrap_or_default();
+
+        if requirements.is_empty() {
+            return vec![Alternative::new()];
+        }
+
+        let mut alternatives: Vec<Alternative> = requirements
+            .into_iter()
+            .map(|requirement| {
+                requirement
+                    .into_iter()
+                    .map(|(name, scopes)| (name, scopes.into_iter().collect()))
+                    .collect()
+            })
+            .collect();
+
+        alternatives.sort();
+        alternatives.dedup();
+        alternatives
+    }
+}
+
+/// The document level `security` of the base and head descriptions, which
+/// operations without their own `security` inherit.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct InheritedSecurity {
+    pub base: Option<Vec<SecurityRequirement>>,
+    pub head: Option<Vec<SecurityRequirement>>,
+}
+
+impl InheritedSecurity {
+    pub fn from_specs(base: &OpenAPI, head: &OpenAPI) -> Self {
+        Self {
+            base: base.security.clone(),
+            head: head.security.clone(),
+        }
+    }
+}
+
+impl SecurityDiff {
+    /// Diffs the effective security of two operations. Operations that
+    /// both inherit the document security are skipped, so a change to it
+    /// is reported once, by the document diff, rather than once per
+    /// operation.
+    pub(crate) fn from_operations(
+        base: &Option<Vec<SecurityRequirement>>,
+        head: &Option<Vec<SecurityRequirement>>,
+        inherited: &InheritedSecurity,
+    ) -> Option<Self> {
+        if base.is_none() && head.is_none() {
+            return None;
+        }
+
+        Self::from_requirements(
+            &base.clone().or_else(|| inherited.base.clone()),
+            &head.clone().or_else(|| inherited.head.clone()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirement(schemes: &[&str]) -> SecurityRequirement {
+        schemes
+            .iter()
+            .map(|scheme| (scheme.to_string(), vec![]))
+            .collect()
+    }
+
+    fn alternative(schemes: &[&str]) -> Alternative {
+        schemes
+            .iter()
+            .map(|scheme| (scheme.to_string(), BTreeSet::new()))
+            .collect()
+    }
+
+    #[test]
+    fn or_to_and_is_breaking() {
+        let diff = SecurityDiff::from_requirements(
+            &Some(vec![requirement(&["A"]), requirement(&["B"])]),
+            &Some(vec![requirement(&["A", "B"])]),
+        )
+        .expect("security should have changed");
+
+        assert!(diff.is_breaking());
+        assert_eq!("A or B", SecurityDiff::describe(&diff.from));
+        assert_eq!("A and B", SecurityDiff::describe(&diff.to));
+    }
+
+    #[test]
+    fn and_to_or_is_not_breaking() {
+        let diff = SecurityDiff::from_requirements(
+            &Some(vec![requirement(&["A", "B"])]),
+            &Some(vec![requirement(&["A"]), requirement(&["B"])]),
+        )
+        .expect("security should have changed");
+
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn reordered_requirements() {
+        let diff = SecurityDiff::from_requirements(
+            &Some(vec![requirement(&["A"]), requirement(&["B"])]),
+            &Some(vec![requirement(&["B"]), requirement(&["A"])]),
+        );
+
+        assert!(diff.is_none());
+    }
+
+    fn scoped(scheme: &str, scopes: &[&str]) -> SecurityRequirement {
+        std::iter::once((
+            scheme.to_string(),
+            scopes.iter().map(|scope| scope.to_string()).collect(),
+        ))
+        .collect()
+    }
+
+    #[test]
+    fn scheme_added() {
+        let diff = SecurityDiff::from_requirements(
+            &Some(vec![requirement(&["A"])]),
+            &Some(vec![requirement(&["A"]), requirement(&["B"])]),
+        )
+        .expect("security should have changed");
+
+        assert_eq!(vec![alternative(&["B"])], diff.added);
+        assert!(diff.removed.is_empty());
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn scheme_removed() {
+        let diff = SecurityDiff::from_requirements(
+            &Some(vec![requirement(&["A"]), requirement(&["B"])]),
+            &Some(vec![requirement(&["A"])]),
+        )
+        .expect("security should have changed");
+
+        assert_eq!(vec![alternative(&["B"])], diff.removed);
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn scopes_narrowed() {
+        let diff = SecurityDiff::from_requirements(
+            &Some(vec![scoped("oauth", &["pets:read", "pets:write"])]),
+            &Some(vec![scoped("oauth", &["pets:read"])]),
+        )
+        .expect("security should have changed");
+
+        let scopes = &diff.scopes["oauth"];
+        assert_eq!(vec![String::from("pets:write")], scopes.removed);
+        assert!(scopes.added.is_empty());
+        assert!(!diff.is_breaking());
+
+        let diff = SecurityDiff::from_requirements(
+            &Some(vec![scoped("oauth", &["pets:read"])]),
+            &Some(vec![scoped("oauth", &["pets:read", "pets:write"])]),
+        )
+        .expect("security should have changed");
+
+        assert_eq!(vec![String::from("pets:write")], diff.scopes["oauth"].added);
+        assert!(diff.is_breaking());
+        assert_eq!(
+            "oauth (pets:read, pets:write)",
+            SecurityDiff::describe(&diff.to)
+        );
+    }
+
+    #[test]
+    fn authentication_required() {
+        let diff = SecurityDiff::from_requirements(&None, &Some(vec![requirement(&["A"])]))
+            .expect("security should have changed");
+
+        assert!(diff.is_breaking());
+        assert_eq!("no authentication", SecurityDiff::describe(&diff.from));
+    }
+
+    #[test]
+    fn document_security() {
+        let spec = |security: &str| -> OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+security: {}
+paths: {{}}
+"#,
+                security
+            ))
+            .expect("Failed to parse description")
+        };
+
+        let diff = crate::diff::diff(
+            spec("[{ apiKey: [] }]"),
+            spec("[{ apiKey: [], bearer: [] }]"),
+        )
+        .expect("Failed to diff");
+
+        let security = diff
+            .security
+            .as_ref()
+            .expect("security should have changed");
+        assert!(security.is_breaking());
+
+        let change = diff.changes().next().expect("a change should be reported");
+        assert_eq!("/security", change.pointer);
+        assert!(change.is_breaking());
+    }
+
+    #[test]
+    fn inherited_document_security() {
+        let spec = |security: &str| -> OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+security: {}
+paths:
+  /pets:
+    get:
+      responses: {{}}
+    post:
+      responses: {{}}
+"#,
+                security
+            ))
+            .expect("Failed to parse description")
+        };
+
+        let diff = crate::diff::diff(
+            spec("[{ apiKey: [] }]"),
+            spec("[{ apiKey: [], bearer: [] }]"),
+        )
+        .expect("Failed to diff");
+
+        let changes: Vec<_> = diff.changes().collect();
+        assert_eq!(1, changes.len());
+        assert_eq!("/security", changes[0].pointer);
+        assert!(changes[0].is_breaking());
+        assert!(diff.paths.is_none());
+    }
+
+    #[test]
+    fn bearer_requirement_lost() {
+        let spec = |security: &str| -> OpenAPI {
+            serde_yaml::from_str(&format!(
+                r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+security:
+  - bearer: []
+paths:
+  /pets:
+    get:
+      {}
+      responses: {{}}
+"#,
+                security
+            ))
+            .expect("Failed to parse description")
+        };
+
+        // An explicit empty list disables the inherited document security.
+        for head in ["security: []", "security: [{ apiKey: [] }]"] {
+            let diff = crate::diff::diff(spec(""), spec(head)).expect("Failed to diff");
+
+            let security = diff.paths.as_ref().unwrap().changed["/pets"].operations_changed["get"]
+                .security
+                .as_ref()
+                .expect("security should have changed");
+            assert_eq!(vec![alternative(&["bearer"])], security.removed, "{}", head);
+        }
+
+        let diff = crate::diff::diff(spec(""), spec("security: [{ bearer: [] }]"))
+            .expect("Failed to diff");
+        assert!(!diff.has_changes());
+    }
+}
@@ -30,7 +30,7 @@ impl ServersDiff {
         for (url, server) in &base_map {
             match head_map.get(url) {
                 Some(head_server) => {
-                    let server_diff = ServerDiff::from_servers(&server, &head_server);
+                    let server_diff = ServerDiff::from_servers(server, head_server);
 
                     if server_diff.has_changes() {
                         servers_changed.insert(url.clone(), server_diff);
@@ -67,9 +67,10 @@ pub struct ServerDiff {
 
 impl ServerDiff {
     pub fn from_servers(base: &Server, head: &Server) -> Self {
-        let mut diff = Self::default();
-
-        diff.description = OptionalStringDiff::from_strings(&base.description, &head.description);
+        let mut diff = Self {
+            description: OptionalStringDiff::from_strings(&base.description, &head.description),
+            ..Default::default()
+        };
 
         let extensions_diff = ExtensionsDiff::from_extensions(&base.extensions, &head.extensions);
         if extensions_diff.has_changes() {
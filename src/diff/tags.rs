@@ -0,0 +1,115 @@
+use crate::openapi::{OpenAPI, PathItem, Paths, ReferenceOr};
+
+/// Removes the operations carrying any of `tags` from the paths and
+/// webhooks of a description, along with the path items that are left
+/// without operations. See `DiffOptions::exclude_tags`.
+pub(crate) fn exclude_tags(mut spec: OpenAPI, tags: &[String]) -> OpenAPI {
+    exclude_operations(&mut spec.paths, tags);
+    exclude_operations(&mut spec.webhooks, tags);
+    spec
+}
+
+fn exclude_operations(paths: &mut Paths, tags: &[String]) {
+    paths.retain(|_, path_item| match path_item {
+        ReferenceOr::Item(path_item) => {
+            let excluded = exclude_path_item_operations(path_item, tags);
+            !excluded || path_item.operations().next().is_some()
+        }
+        ReferenceOr::Reference { .. } => true,
+    });
+}
+
+/// Removes the excluded operations of a path item, telling whether there
+/// were any.
+fn exclude_path_item_operations(path_item: &mut PathItem, tags: &[String]) -> bool {
+    let mut excluded = false;
+
+    for operation in [
+        &mut path_item.get,
+        &mut path_item.put,
+        &mut path_item.post,
+        &mut path_item.delete,
+        &mut path_item.options,
+        &mut path_item.head,
+        &mut path_item.patch,
+        &mut path_item.trace,
+    ] {
+        let is_excluded = operation
+            .as_ref()
+            .is_some_and(|operation| operation.tags.iter().any(|tag| tags.contains(tag)));
+
+        if is_excluded {
+            *operation = None;
+            excluded = true;
+        }
+    }
+
+    excluded
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::{diff_with_options, DiffOptions};
+    use crate::openapi::OpenAPI;
+
+    fn spec(operations: &str) -> OpenAPI {
+        serde_yaml::from_str(&format!(
+            r#"
+openapi: "3.0.0"
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      tags: [pets]
+      responses: {{}}
+{}
+"#,
+            operations
+        ))
+        .expect("Failed to parse description")
+    }
+
+    #[test]
+    fn excluded_tags() {
+        let options = DiffOptions {
+            exclude_tags: vec!["internal".into()],
+            ..Default::default()
+        };
+
+        // Exclusion wins over the other tags of an operation.
+        let diff = diff_with_options(
+            spec(""),
+            spec(
+                r#"
+    post:
+      tags: [pets, internal]
+      responses: {}
+  /admin:
+    get:
+      tags: [internal]
+      responses: {}
+"#,
+            ),
+            &options,
+        )
+        .expect("Failed to diff");
+        assert!(!diff.has_changes());
+
+        let diff = diff_with_options(
+            spec(""),
+            spec(
+                r#"
+    post:
+      tags: [pets]
+      responses: {}
+"#,
+            ),
+            &options,
+        )
+        .expect("Failed to diff");
+        let paths = diff.paths.expect("paths should have changed");
+        assert_eq!("post", paths.changed["/pets"].operations_added[0].0);
+    }
+}
@@ -1,3 +1,8 @@
+#![cfg_attr(test, allow(clippy::field_reassign_with_default))]
+
+pub mod baseline;
 pub mod cli;
 pub mod diff;
 pub mod openapi;
+pub mod overlay;
+pub mod resolver;
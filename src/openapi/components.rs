@@ -36,6 +36,10 @@ pub struct Components {
     /// An object to hold reusable Callback Objects.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub callbacks:BTreeMap<String, ReferenceOr<Callback>>,
+    /// OpenAPI 3.1 reusable Path Item Objects, which paths and webhooks
+    /// can reference.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub path_items:BTreeMap<String, ReferenceOr<PathItem>>,
     /// Inline extensions to this object.
     #[serde(flatten)]
     pub extensions:BTreeMap<String, serde_json::Value>,
@@ -16,6 +16,11 @@ pub struct OpenAPI {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<Server>,
     pub paths: Paths,
+    /// Incoming webhooks that MAY be received as part of this API, keyed
+    /// by a unique name. Introduced in OpenAPI 3.1.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub webhooks: Paths,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Components>,
     #[serde(default)]
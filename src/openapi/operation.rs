@@ -49,6 +49,11 @@ pub struct Operation {
     /// REQUIRED. The list of possible responses as they are returned
     /// from executing this operation.
     pub responses: Responses,
+    /// A map of possible out-of band callbacks related to the parent operation.
+    /// The key is a unique identifier for the Callback Object.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub callbacks: BTreeMap<String, ReferenceOr<Callback>>,
     /// Declares this operation to be deprecated.Default value is false.
     pub deprecated: Option<bool>,
     /// A declaration of which security mechanisms can be used for this operation.
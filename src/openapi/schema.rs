@@ -1,6 +1,7 @@
 use super::reference::ReferenceOr;
-use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 
 /// The Schema Object allows the definition of input and output data types.
 /// These types can be objects, but also primitives and arrays.
@@ -19,7 +20,7 @@ pub struct Schema {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "type")]
-    pub schema_type: Option<String>,
+    pub schema_type: Option<SchemaType>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<String>,
@@ -40,6 +41,9 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none", rename = "readOnly")]
     pub read_only: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none", rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nullable: Option<bool>,
 
@@ -64,6 +68,11 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<serde_json::value::Value>,
 
+    /// OpenAPI 3.1 (JSON Schema) list of examples, superseding the
+    /// singular `example`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<serde_json::value::Value>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
 
@@ -99,7 +108,60 @@ pub struct Schema {
     /// JSON Schema.
     /// [not](https://swagger.io/docs/specification/data-models/oneof-anyof-allof-not/#not)
     #[serde(rename = "not", skip_serializing_if = "Option::is_none")]
-    pub not: Option<Vec<ReferenceOr<Schema>>>,
+    pub not: Option<Box<ReferenceOr<Schema>>>,
+
+    /// OpenAPI 3.1 (JSON Schema) condition selecting whether `then` or
+    /// `else` applies.
+    #[serde(rename = "if", skip_serializing_if = "Option::is_none")]
+    pub if_schema: Option<Box<ReferenceOr<Schema>>>,
+
+    /// Schema the instance must match when it matches `if`.
+    #[serde(rename = "then", skip_serializing_if = "Option::is_none")]
+    pub then_schema: Option<Box<ReferenceOr<Schema>>>,
+
+    /// Schema the instance must match when it does not match `if`.
+    #[serde(rename = "else", skip_serializing_if = "Option::is_none")]
+    pub else_schema: Option<Box<ReferenceOr<Schema>>>,
+
+    /// OpenAPI 3.1 (JSON Schema) properties that become required when the
+    /// property of the same key is present.
+    #[serde(rename = "dependentRequired", skip_serializing_if = "Option::is_none")]
+    pub dependent_required: Option<BTreeMap<String, Vec<String>>>,
+
+    /// OpenAPI 3.1 (JSON Schema) schemas the instance must also match when
+    /// the property of the same key is present.
+    #[serde(rename = "dependentSchemas", skip_serializing_if = "Option::is_none")]
+    pub dependent_schemas: Option<BTreeMap<String, ReferenceOr<Schema>>>,
+
+    /// Regular expression a string value must match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    /// OpenAPI 3.1 (JSON Schema) dynamic reference, resolved at validation
+    /// time against the `$dynamicAnchor`s in scope, such as `#node` in
+    /// recursive schemas. Dynamic references are not resolved: they are
+    /// compared as opaque strings, like references.
+    #[serde(rename = "$dynamicRef", skip_serializing_if = "Option::is_none")]
+    pub dynamic_ref: Option<String>,
+
+    /// OpenAPI 3.1 (JSON Schema) anchor `$dynamicRef`s can resolve to.
+    #[serde(rename = "$dynamicAnchor", skip_serializing_if = "Option::is_none")]
+    pub dynamic_anchor: Option<String>,
+
+    /// OpenAPI 3.1 (JSON Schema) encoding of the string content, such as
+    /// `base64`.
+    #[serde(rename = "contentEncoding", skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+
+    /// OpenAPI 3.1 (JSON Schema) media type of the string content, such as
+    /// `image/png`.
+    #[serde(rename = "contentMediaType", skip_serializing_if = "Option::is_none")]
+    pub content_media_type: Option<String>,
+
+    /// OpenAPI 3.1 (JSON Schema) schema every property name of an object
+    /// must match, such as the keys of a map.
+    #[serde(rename = "propertyNames", skip_serializing_if = "Option::is_none")]
+    pub property_names: Option<Box<ReferenceOr<Schema>>>,
 
     #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
     pub max_length: Option<u32>,
@@ -107,9 +169,49 @@ pub struct Schema {
     #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
     pub min_length: Option<u32>,
 
+    /// Numbers must be a multiple of this value, such as `0.01` for amounts
+    /// in cents.
+    #[serde(rename = "multipleOf", skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<f64>,
+
     /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.2.md#specificationExtensions)
     #[serde(flatten)]
-    pub extensions:BTreeMap<String, serde_json::Value>,
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// The `type` of a schema: a single type, or an OpenAPI 3.1 (JSON Schema)
+/// list of types such as `["string", "null"]`. A list is a set, the order
+/// of its types is irrelevant.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SchemaType {
+    Single(String),
+    List(BTreeSet<String>),
+}
+
+impl SchemaType {
+    /// The types this schema type allows.
+    pub fn types(&self) -> Vec<&str> {
+        match self {
+            SchemaType::Single(schema_type) => vec![schema_type.as_str()],
+            SchemaType::List(types) => types.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl From<&str> for SchemaType {
+    fn from(schema_type: &str) -> Self {
+        SchemaType::Single(schema_type.to_string())
+    }
+}
+
+impl fmt::Display for SchemaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaType::Single(schema_type) => write!(f, "{}", schema_type),
+            SchemaType::List(_) => write!(f, "[{}]", self.types().join(", ")),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -22,6 +22,7 @@ pub type SecurityRequirement = BTreeMap<String, Vec<String>>;
 /// application and access code) as defined in RFC6749, and OpenID Connect Discovery.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type")]
+#[allow(clippy::large_enum_variant)]
 pub enum SecurityScheme {
     #[serde(rename = "apiKey")]
     APIKey {
@@ -7,6 +7,17 @@ pub enum StatusCode {
     Range(u16),
 }
 
+impl StatusCode {
+    /// Whether this status code (or range) describes a client or server error,
+    /// i.e. anything in the `4XX` or `5XX` classes.
+    pub fn is_error(&self) -> bool {
+        match self {
+            StatusCode::Code(n) => *n >= 400,
+            StatusCode::Range(n) => *n >= 4,
+        }
+    }
+}
+
 impl fmt::Display for StatusCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -125,4 +136,13 @@ mod tests {
     fn deserialize_invalid_range() {
         let _: StatusCode = from_str("2XY").unwrap();
     }
+
+    #[test]
+    fn error_codes() {
+        assert!(StatusCode::Code(429).is_error());
+        assert!(StatusCode::Code(503).is_error());
+        assert!(StatusCode::Range(4).is_error());
+        assert!(!StatusCode::Code(204).is_error());
+        assert!(!StatusCode::Range(3).is_error());
+    }
 }
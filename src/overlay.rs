@@ -0,0 +1,379 @@
+//! Support for the [OpenAPI Overlay](https://spec.openapis.org/overlay/v1.0.0.html)
+//! specification, used to derive per-environment descriptions from a
+//! shared one. Apply an overlay with `apply_overlay` and diff the results
+//! as usual.
+
+use crate::openapi::OpenAPI;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum OverlayError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid overlay: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("overlay produced an invalid description: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("invalid overlay target `{0}`")]
+    InvalidTarget(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Overlay {
+    /// REQUIRED. The version of the Overlay specification used.
+    pub overlay: String,
+    pub info: OverlayInfo,
+    /// URL of the description this overlay is meant for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// REQUIRED. Actions applied in order to the description.
+    pub actions: Vec<Action>,
+    #[serde(flatten)]
+    pub extensions: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct OverlayInfo {
+    pub title: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Action {
+    /// REQUIRED. A JSONPath expression selecting the nodes to act on.
+    pub target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Merged into each selected node. Ignored when `remove` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update: Option<Value>,
+    /// Removes the selected nodes from their parents.
+    #[serde(default)]
+    pub remove: bool,
+}
+
+impl Overlay {
+    /// Loads an overlay document, in YAML or JSON.
+    pub fn from_file(path: &Path) -> Result<Self, OverlayError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_yaml::from_str(&contents)?)
+    }
+}
+
+/// Applies the actions of an overlay to a description, in order.
+///
+/// Only the child (`.name`, `['name']`), wildcard (`.*`, `[*]`), index
+/// (`[0]`) and recursive descent (`..name`) JSONPath selectors are
+/// supported. Other selectors, such as filters, are rejected.
+pub fn apply_overlay(spec: OpenAPI, overlay: &Overlay) -> Result<OpenAPI, OverlayError> {
+    let mut document = serde_json::to_value(spec)?;
+
+    for action in &overlay.actions {
+        let selectors = parse_target(&action.target)?;
+        let mut targets = vec![];
+        select(&document, &selectors, &mut vec![], &mut targets);
+
+        if action.remove {
+            // Later array elements are removed first so the indices of the
+            // remaining targets stay valid.
+            targets.sort();
+            for target in targets.iter().rev() {
+                remove(&mut document, target);
+            }
+        } else if let Some(update) = &action.update {
+            for target in &targets {
+                if let Some(node) = node_mut(&mut document, target) {
+                    merge(node, update);
+                }
+            }
+        }
+    }
+
+    Ok(OpenAPI::deserialize(document)?)
+}
+
+#[derive(Debug, PartialEq)]
+enum Selector {
+    Child(String),
+    Index(usize),
+    Wildcard,
+    Descendant(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn parse_target(target: &str) -> Result<Vec<Selector>, OverlayError> {
+    let invalid = || OverlayError::InvalidTarget(target.to_string());
+
+    let mut rest = target.strip_prefix('$').ok_or_else(invalid)?;
+    let mut selectors = vec![];
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("..") {
+            let (name, remaining) = split_name(after);
+            if name.is_empty() {
+                return Err(invalid());
+            }
+            selectors.push(Selector::Descendant(name.to_string()));
+            rest = remaining;
+        } else if let Some(after) = rest.strip_prefix('.') {
+            let (name, remaining) = split_name(after);
+            match name {
+                "" => return Err(invalid()),
+                "*" => selectors.push(Selector::Wildcard),
+                name => selectors.push(Selector::Child(name.to_string())),
+            }
+            rest = remaining;
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = bracket_end(after).ok_or_else(invalid)?;
+            let inner = after[..end].trim();
+            let selector = if inner == "*" {
+                Selector::Wildcard
+            } else if let Some(name) = quoted(inner) {
+                Selector::Child(name.to_string())
+            } else {
+                Selector::Index(inner.parse().map_err(|_| invalid())?)
+            };
+            selectors.push(selector);
+            rest = &after[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+
+    Ok(selectors)
+}
+
+/// Splits a dot-notation member name from the rest of the expression.
+fn split_name(expression: &str) -> (&str, &str) {
+    let end = expression.find(['.', '[']).unwrap_or(expression.len());
+    expression.split_at(end)
+}
+
+/// The position of the `]` closing a bracketed selector, skipping over
+/// quoted names which may contain brackets, such as paths with parameters.
+fn bracket_end(expression: &str) -> Option<usize> {
+    let mut quote = None;
+
+    for (position, character) in expression.char_indices() {
+        match (quote, character) {
+            (None, '\'') | (None, '"') => quote = Some(character),
+            (Some(open), character) if open == character => quote = None,
+            (None, ']') => return Some(position),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn quoted(selector: &str) -> Option<&str> {
+    selector
+        .strip_prefix('\'')
+        .and_then(|name| name.strip_suffix('\''))
+        .or_else(|| {
+            selector
+                .strip_prefix('"')
+                .and_then(|name| name.strip_suffix('"'))
+        })
+}
+
+/// Collects the location of every node matching `selectors` under `node`.
+fn select(
+    node: &Value,
+    selectors: &[Selector],
+    location: &mut Vec<Segment>,
+    targets: &mut Vec<Vec<Segment>>,
+) {
+    let (selector, rest) = match selectors.split_first() {
+        Some(split) => split,
+        None => {
+            targets.push(location.clone());
+            return;
+        }
+    };
+
+    let mut visit = |segment: Segment, child: &Value, selectors: &[Selector]| {
+        location.push(segment);
+        select(child, selectors, location, targets);
+        location.pop();
+    };
+
+    match (selector, node) {
+        (Selector::Child(name), Value::Object(object)) => {
+            if let Some(child) = object.get(name) {
+                visit(Segment::Key(name.clone()), child, rest);
+            }
+        }
+        (Selector::Index(index), Value::Array(array)) => {
+            if let Some(child) = array.get(*index) {
+                visit(Segment::Index(*index), child, rest);
+            }
+        }
+        (Selector::Wildcard, Value::Object(object)) => {
+            for (key, child) in object {
+                visit(Segment::Key(key.clone()), child, rest);
+            }
+        }
+        (Selector::Wildcard, Value::Array(array)) => {
+            for (index, child) in array.iter().enumerate() {
+                visit(Segment::Index(index), child, rest);
+            }
+        }
+        (Selector::Descendant(name), Value::Object(object)) => {
+            for (key, child) in object {
+                if key == name {
+                    visit(Segment::Key(key.clone()), child, rest);
+                }
+                visit(Segment::Key(key.clone()), child, selectors);
+            }
+        }
+        (Selector::Descendant(_), Value::Array(array)) => {
+            for (index, child) in array.iter().enumerate() {
+                visit(Segment::Index(index), child, selectors);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn node_mut<'a>(document: &'a mut Value, location: &[Segment]) -> Option<&'a mut Value> {
+    location
+        .iter()
+        .try_fold(document, |node, segment| match (segment, node) {
+            (Segment::Key(key), Value::Object(object)) => object.get_mut(key),
+            (Segment::Index(index), Value::Array(array)) => array.get_mut(*index),
+            _ => None,
+        })
+}
+
+fn remove(document: &mut Value, location: &[Segment]) {
+    let (last, parent) = match location.split_last() {
+        Some(split) => split,
+        // The root itself cannot be removed.
+        None => return,
+    };
+
+    match (last, node_mut(document, parent)) {
+        (Segment::Key(key), Some(Value::Object(object))) => {
+            object.remove(key);
+        }
+        (Segment::Index(index), Some(Value::Array(array))) if *index < array.len() => {
+            array.remove(*index);
+        }
+        _ => {}
+    }
+}
+
+/// Merges an update into a node: objects are merged recursively, arrays
+/// are appended to, and any other node is replaced.
+fn merge(node: &mut Value, update: &Value) {
+    match (node, update) {
+        (Value::Object(object), Value::Object(update)) => {
+            for (key, value) in update {
+                match object.get_mut(key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        object.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (Value::Array(array), Value::Array(update)) => array.extend(update.iter().cloned()),
+        (Value::Array(array), update) => array.push(update.clone()),
+        (node, update) => *node = update.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::diff;
+
+    fn pet_store() -> OpenAPI {
+        let contents =
+            std::fs::read_to_string("fixtures/pet-store.yaml").expect("Failed to read fixture");
+        serde_yaml::from_str(&contents).expect("Failed to parse fixture")
+    }
+
+    fn overlay(contents: &str) -> Overlay {
+        serde_yaml::from_str(contents).expect("Failed to parse overlay")
+    }
+
+    #[test]
+    fn parse_targets() {
+        assert_eq!(
+            vec![
+                Selector::Child("paths".into()),
+                Selector::Child("/pets/{petId}".into()),
+                Selector::Wildcard,
+                Selector::Index(0),
+                Selector::Descendant("description".into()),
+            ],
+            parse_target("$.paths['/pets/{petId}'].*[0]..description").unwrap()
+        );
+
+        assert!(parse_target("paths").is_err());
+        assert!(parse_target("$.paths[?(@.get)]").is_err());
+    }
+
+    #[test]
+    fn update_action() {
+        let overlay = overlay(
+            r#"
+overlay: 1.0.0
+info:
+  title: Production
+  version: 1.0.0
+actions:
+  - target: $.info
+    update:
+      title: Production pet store
+  - target: $.servers
+    update:
+      url: https://pets.example.com
+"#,
+        );
+
+        let spec = apply_overlay(pet_store(), &overlay).expect("Failed to apply overlay");
+
+        assert_eq!("Production pet store", spec.info.title);
+        assert_eq!("https://pets.example.com", spec.servers.last().unwrap().url);
+    }
+
+    #[test]
+    fn remove_action() {
+        let overlay = overlay(
+            r#"
+overlay: 1.0.0
+info:
+  title: Public
+  version: 1.0.0
+actions:
+  - target: $.paths.*.post
+    remove: true
+"#,
+        );
+
+        let base = pet_store();
+        let head = apply_overlay(base.clone(), &overlay).expect("Failed to apply overlay");
+
+        let diff = diff(base, head).expect("Failed to diff");
+        assert!(diff
+            .operations_removed()
+            .iter()
+            .all(|(_, method)| method == "post"));
+        assert!(!diff.operations_removed().is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+//! Looks up the components that local `$ref`s point to, such as
+//! `#/components/parameters/PageSize`. The diff resolves references with it
+//! when `DiffOptions::resolve_references` is set.
+
+use crate::diff::DiffError;
+use crate::openapi::{
+    Callback, Components, Example, Header, Link, OpenAPI, Parameter, PathItem, ReferenceOr,
+    RequestBody, Response, Schema, SecurityScheme,
+};
+use std::collections::BTreeMap;
+
+/// An object that can be reused from a section of `components`.
+pub trait Component: Sized {
+    /// The name of the section in `$ref`s, such as `requestBodies`.
+    const SECTION: &'static str;
+
+    /// The objects of the section.
+    fn section(components: &Components) -> &BTreeMap<String, ReferenceOr<Self>>;
+}
+
+macro_rules! component {
+    ($type:ty, $section:literal, $field:ident) => {
+        impl Component for $type {
+            const SECTION: &'static str = $section;
+
+            fn section(components: &Components) -> &BTreeMap<String, ReferenceOr<Self>> {
+                &components.$field
+            }
+        }
+    };
+}
+
+component!(Schema, "schemas", schemas);
+component!(Response, "responses", responses);
+component!(Parameter, "parameters", parameters);
+component!(Example, "examples", examples);
+component!(RequestBody, "requestBodies", request_bodies);
+component!(Header, "headers", headers);
+component!(SecurityScheme, "securitySchemes", security_schemes);
+component!(Link, "links", links);
+component!(Callback, "callbacks", callbacks);
+component!(PathItem, "pathItems", path_items);
+
+/// The components of a description, to resolve its references against.
+///
+/// ```
+/// use anicca::openapi::{OpenAPI, Parameter, ReferenceOr};
+/// use anicca::resolver::Resolver;
+///
+/// let spec: OpenAPI = serde_yaml::from_str(
+///     r##"
+/// openapi: "3.0.0"
+/// info: { title: Pets, version: 1.0.0 }
+/// paths: {}
+/// components:
+///   parameters:
+///     Limit: { $ref: "#/components/parameters/PageSize" }
+///     PageSize: { name: limit, in: query, required: true, schema: { type: integer } }
+/// "##,
+/// )?;
+///
+/// let reference: ReferenceOr<Parameter> = ReferenceOr::Reference {
+///     reference: "#/components/parameters/Limit".into(),
+/// };
+/// let resolver = Resolver::new(&spec);
+/// let parameter = resolver.resolve(&reference)?;
+/// assert!(matches!(
+///     parameter,
+///     Parameter::Query { parameter_data, .. } if parameter_data.required
+/// ));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Resolver {
+    components: Components,
+}
+
+impl Resolver {
+    pub fn new(spec: &OpenAPI) -> Self {
+        Self {
+            components: spec.components.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Follows a reference into its section of the components, along with
+    /// the references the component itself is defined with. Items are
+    /// returned as they are.
+    pub fn resolve<'a, T: Component>(
+        &'a self,
+        mut item: &'a ReferenceOr<T>,
+    ) -> Result<&'a T, DiffError> {
+        let prefix = format!("#/components/{}/", T::SECTION);
+        let mut followed: Vec<&str> = vec![];
+
+        loop {
+            let reference = match item {
+                ReferenceOr::Item(item) => return Ok(item),
+                ReferenceOr::Reference { reference } => reference,
+            };
+
+            if followed.contains(&reference.as_str()) {
+                followed.push(reference);
+                return Err(DiffError::ReferenceCycle(followed.join(" -> ")));
+            }
+
+            item = reference
+                .strip_prefix(&prefix)
+                .map(|name| name.replace("~1", "/").replace("~0", "~"))
+                .and_then(|name| T::section(&self.components).get(&name))
+                .ok_or_else(|| DiffError::UnresolvableReference(reference.clone()))?;
+            followed.push(reference);
+        }
+    }
+
+    /// The canonical form of a reference, so different notations of the
+    /// same target compare equal. The file a reference points into is
+    /// written relative to the description without `.` and `..` segments,
+    /// and local references are kept as they are.
+    ///
+    /// ```
+    /// use anicca::resolver::Resolver;
+    ///
+    /// assert_eq!(
+    ///     "models.yaml#/components/schemas/Pet",
+    ///     Resolver::canonical("./schemas/../models.yaml#/components/schemas/Pet"),
+    /// );
+    /// assert_eq!("#/components/schemas/Pet", Resolver::canonical("#/components/schemas/Pet"));
+    /// ```
+    pub fn canonical(reference: &str) -> String {
+        let (file, fragment) = match reference.find('#') {
+            Some(index) => reference.split_at(index),
+            None => (reference, ""),
+        };
+        let fragment = if fragment == "#" { "" } else { fragment };
+
+        // Remote references are kept as written.
+        if file.is_empty() || file.contains("://") {
+            return reference.to_string();
+        }
+
+        let mut segments: Vec<&str> = vec![];
+        for segment in file.split('/') {
+            match segment {
+                "" | "." => {}
+                ".." if segments.last().is_some_and(|last| *last != "..") => {
+                    segments.pop();
+                }
+                segment => segments.push(segment),
+            }
+        }
+
+        let root = if file.starts_with('/') { "/" } else { "" };
+        format!("{}{}{}", root, segments.join("/"), fragment)
+    }
+}